@@ -1,4 +1,4 @@
-// Horror post-processing shader: film grain + CRT scanlines + vignette
+// Horror post-processing shader: film grain + CRT scanlines + vignette + dithering
 // Combines multiple effects for Silent Hill 2 style atmosphere
 
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
@@ -17,6 +17,9 @@ struct PostProcessSettings {
     // Vignette
     vignette_intensity: f32,
     vignette_radius: f32,
+    // Dithering and color depth
+    dither_intensity: f32,
+    color_levels: f32,
     // Animation
     time: f32,
 }
@@ -51,6 +54,34 @@ fn vignette(uv: vec2<f32>, intensity: f32, radius: f32) -> f32 {
     return mix(1.0 - intensity, 1.0, vig);
 }
 
+// 4x4 Bayer matrix threshold for a pixel, in [0, 1)
+fn bayer4(pixel: vec2<f32>) -> f32 {
+    var bayer = array<f32, 16>(
+        0.0, 8.0, 2.0, 10.0,
+        12.0, 4.0, 14.0, 6.0,
+        3.0, 11.0, 1.0, 9.0,
+        15.0, 7.0, 13.0, 5.0,
+    );
+    let p = vec2<u32>(pixel) % vec2<u32>(4u);
+    return bayer[p.y * 4u + p.x] / 16.0;
+}
+
+// Reduce color depth to `levels` per channel, with ordered dithering
+// spreading the banding into a fixed pattern. Quantizes in gamma space like
+// the original hardware, since the screen texture is sampled as linear.
+fn dither_quantize(color: vec3<f32>, pixel: vec2<f32>, levels: f32, intensity: f32) -> vec3<f32> {
+    // Fewer than two levels can't represent anything
+    if levels < 2.0 {
+        return color;
+    }
+
+    let steps = levels - 1.0;
+    let threshold = (bayer4(pixel) - 0.5) * intensity;
+    let gamma = pow(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)), vec3<f32>(1.0 / 2.2));
+    let quantized = floor(gamma * steps + 0.5 + threshold) / steps;
+    return pow(clamp(quantized, vec3<f32>(0.0), vec3<f32>(1.0)), vec3<f32>(2.2));
+}
+
 @fragment
 fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     let uv = in.uv;
@@ -70,6 +101,10 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     let vig = vignette(uv, settings.vignette_intensity, settings.vignette_radius);
     color = vec4<f32>(color.rgb * vig, color.a);
 
+    // Apply ordered dithering and color depth reduction
+    let dithered = dither_quantize(color.rgb, in.position.xy, settings.color_levels, settings.dither_intensity);
+    color = vec4<f32>(dithered, color.a);
+
     // Clamp to valid range
     color = clamp(color, vec4<f32>(0.0), vec4<f32>(1.0));
 
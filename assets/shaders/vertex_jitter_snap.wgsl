@@ -0,0 +1,27 @@
+// Grid snapping shared by the vertex jitter main pass and prepass shaders.
+
+#define_import_path lunacid_clone::vertex_jitter_snap
+
+#import bevy_pbr::mesh_view_bindings::view
+
+// Vertical grid resolution at full intensity (PS1 output height)
+const JITTER_GRID_HEIGHT: f32 = 240.0;
+
+@group(2) @binding(100) var<uniform> jitter_intensity: f32;
+
+// Snap a clip-space position to the jitter grid.
+// Lower intensity means a finer grid and a subtler wobble.
+fn snap_to_grid(clip: vec4<f32>) -> vec4<f32> {
+    // Vertices behind the camera can't be projected
+    if jitter_intensity <= 0.0 || clip.w <= 0.0 {
+        return clip;
+    }
+
+    let aspect = view.viewport.z / view.viewport.w;
+    let grid_height = JITTER_GRID_HEIGHT / jitter_intensity;
+    let half_grid = vec2<f32>(grid_height * aspect, grid_height) * 0.5;
+
+    let ndc = clip.xy / clip.w;
+    let snapped = round(ndc * half_grid) / half_grid;
+    return vec4<f32>(snapped * clip.w, clip.zw);
+}
@@ -0,0 +1,54 @@
+//! Audio configuration loaded from external RON file.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fs;
+
+/// Audio configuration loaded from assets/data/audio/audio_config.ron.
+///
+/// Missing fields fall back to their defaults.
+#[derive(Resource, Clone, Deserialize)]
+#[serde(default)]
+pub struct AudioConfig {
+    // Alert stinger
+    /// Sample played when an enemy spots the player, relative to assets/
+    /// (empty = no stinger)
+    pub alert_stinger: String,
+    /// Stinger volume before the master volume is applied (0.0 to 1.0)
+    pub alert_stinger_volume: f32,
+    /// Minimum seconds between two stingers
+    pub alert_stinger_cooldown: f32,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            alert_stinger: String::new(),
+            alert_stinger_volume: 0.8,
+            alert_stinger_cooldown: 8.0,
+        }
+    }
+}
+
+impl AudioConfig {
+    /// Load audio configuration from RON file.
+    pub fn load() -> Self {
+        let path = "assets/data/audio/audio_config.ron";
+        match fs::read_to_string(path) {
+            Ok(contents) => match ron::from_str(&contents) {
+                Ok(config) => {
+                    info!("Loaded audio config from {}", path);
+                    config
+                }
+                Err(e) => {
+                    error!("Failed to parse {}: {}. Using defaults.", path, e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                warn!("Could not read {}: {}. Using defaults.", path, e);
+                Self::default()
+            }
+        }
+    }
+}
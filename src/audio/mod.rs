@@ -0,0 +1,8 @@
+//! Audio module - sound playback, volume and music cues.
+
+mod config;
+mod plugin;
+mod stinger;
+
+pub use config::AudioConfig;
+pub use plugin::AudioPlugin;
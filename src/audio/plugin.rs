@@ -0,0 +1,30 @@
+//! Audio plugin - sets up the audio backend and sound cues.
+
+use bevy::prelude::*;
+use bevy_kira_audio::{Audio, AudioControl, AudioPlugin as KiraAudioPlugin};
+
+use super::config::AudioConfig;
+use super::stinger;
+use crate::core::MasterVolume;
+
+/// Audio plugin - handles sound playback.
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(KiraAudioPlugin)
+            .insert_resource(AudioConfig::load())
+            .add_systems(Update, apply_master_volume);
+
+        // Setup alert stinger
+        stinger::setup_stinger_systems(app);
+    }
+}
+
+/// Apply the master volume to the main audio channel whenever it changes.
+fn apply_master_volume(master_volume: Res<MasterVolume>, audio: Res<Audio>) {
+    if !master_volume.is_changed() {
+        return;
+    }
+    audio.set_volume(master_volume.clamped() as f64);
+}
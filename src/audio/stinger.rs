@@ -0,0 +1,74 @@
+//! "You've been spotted" stinger.
+//!
+//! A short sample plays when an idle enemy notices the player. It only marks
+//! a fresh engagement: alerts while other enemies are already chasing don't
+//! play it, and a cooldown keeps a room full of enemies aggroing at once
+//! from stacking stingers.
+
+use bevy::prelude::*;
+use bevy_kira_audio::{Audio, AudioControl, AudioSource};
+
+use super::config::AudioConfig;
+use crate::core::{AlertEvent, GameState};
+use crate::enemies::{AiState, Enemy};
+
+/// The loaded stinger sample, if one is configured.
+#[derive(Resource, Default)]
+struct AlertStinger {
+    sample: Option<Handle<AudioSource>>,
+    /// Seconds until another stinger may play
+    cooldown: f32,
+}
+
+/// Set up alert stinger systems.
+pub fn setup_stinger_systems(app: &mut App) {
+    app.init_resource::<AlertStinger>()
+        .add_systems(Startup, load_alert_stinger)
+        .add_systems(Update, play_alert_stinger.run_if(in_state(GameState::InGame)));
+}
+
+/// Load the configured stinger sample.
+fn load_alert_stinger(
+    asset_server: Res<AssetServer>,
+    config: Res<AudioConfig>,
+    mut stinger: ResMut<AlertStinger>,
+) {
+    if config.alert_stinger.is_empty() {
+        return;
+    }
+    stinger.sample = Some(asset_server.load(config.alert_stinger.clone()));
+}
+
+/// Play the stinger when the player is spotted outside of a fight.
+fn play_alert_stinger(
+    time: Res<Time>,
+    config: Res<AudioConfig>,
+    audio: Res<Audio>,
+    mut stinger: ResMut<AlertStinger>,
+    mut alert_events: EventReader<AlertEvent>,
+    enemy_query: Query<(Entity, &AiState), With<Enemy>>,
+) {
+    stinger.cooldown = (stinger.cooldown - time.delta_secs()).max(0.0);
+
+    let alerted: Vec<Entity> = alert_events.read().map(|event| event.enemy).collect();
+    if alerted.is_empty() || stinger.cooldown > 0.0 {
+        return;
+    }
+
+    // Enemies that were already engaged before this frame's alerts
+    let already_engaged = enemy_query.iter().any(|(entity, state)| {
+        !alerted.contains(&entity) && matches!(state, AiState::Chasing | AiState::Attacking)
+    });
+    if already_engaged {
+        return;
+    }
+
+    let Some(sample) = stinger.sample.clone() else {
+        return;
+    };
+    // The main channel's volume applies the master volume on top
+    audio
+        .play(sample)
+        .with_volume(config.alert_stinger_volume.clamp(0.0, 1.0) as f64);
+    stinger.cooldown = config.alert_stinger_cooldown;
+}
@@ -0,0 +1,78 @@
+//! Level validator - checks every level and palette file without launching the game.
+//!
+//! Run from the project root:
+//!
+//! ```text
+//! cargo run --bin validate_levels
+//! ```
+//!
+//! Prints each problem found and exits with a nonzero status if any palette,
+//! enemy, trap or level file is invalid.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use lunacid_clone::enemies::data::{read_enemy_definitions, ENEMIES_DIR};
+use lunacid_clone::enemies::traps::{read_trap_definitions, TRAPS_DIR};
+use lunacid_clone::enemies::{EnemyRegistry, TrapRegistry};
+use lunacid_clone::world::{read_palette_files, validate_level_dir, LEVELS_DIR, PALETTES_DIR};
+
+fn main() -> ExitCode {
+    let mut failed = false;
+
+    let (palettes, palette_errors) = read_palette_files(Path::new(PALETTES_DIR));
+    for error in &palette_errors {
+        println!("palette: {}", error);
+        failed = true;
+    }
+
+    let mut enemies = EnemyRegistry::default();
+    let enemy_errors = read_enemy_definitions(Path::new(ENEMIES_DIR), &mut enemies);
+    for error in &enemy_errors {
+        println!("enemy: {}", error);
+        failed = true;
+    }
+
+    let mut traps = TrapRegistry::default();
+    let trap_errors = read_trap_definitions(Path::new(TRAPS_DIR), &mut traps);
+    for error in &trap_errors {
+        println!("trap: {}", error);
+        failed = true;
+    }
+
+    let reports = match validate_level_dir(Path::new(LEVELS_DIR), &palettes, &enemies, &traps) {
+        Ok(reports) => reports,
+        Err(error) => {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for report in &reports {
+        if report.is_valid() {
+            println!("ok      {}", report.path.display());
+            continue;
+        }
+        failed = true;
+        println!("invalid {}", report.path.display());
+        for error in &report.errors {
+            println!("    {}", error);
+        }
+    }
+
+    let invalid = reports.iter().filter(|report| !report.is_valid()).count();
+    println!(
+        "{} level(s) checked, {} invalid, {} palette error(s), {} enemy error(s), {} trap error(s)",
+        reports.len(),
+        invalid,
+        palette_errors.len(),
+        enemy_errors.len(),
+        trap_errors.len()
+    );
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,286 @@
+//! Radial area-of-effect damage for explosions, slams and spells.
+//!
+//! `AreaDamage` is a system parameter bundling the physics query, target
+//! lookup and damage event writer, so any system can deal radial damage with
+//! a single call instead of reimplementing the overlap query. Entities with an
+//! `Explosive` component use it to blow up when they are destroyed.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::components::{DamageEvent, Dead, DeathEvent, Element, Health};
+use super::hit_fx::HitFxAssets;
+use crate::enemies::Enemy;
+use crate::player::Player;
+use crate::rendering::spawn_particle_burst;
+
+/// Impulse applied to targets at the center of the area.
+const AOE_KNOCKBACK: f32 = 2.0;
+
+/// How damage scales with distance from the center.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Falloff {
+    /// Full damage everywhere inside the radius
+    #[default]
+    None,
+    /// Full damage at the center, fading to zero at the edge
+    Linear,
+}
+
+impl Falloff {
+    /// Damage multiplier at `distance` from the center of an area of `radius`.
+    pub fn scale(self, distance: f32, radius: f32) -> f32 {
+        match self {
+            Falloff::None => 1.0,
+            Falloff::Linear if radius > 0.0 => (1.0 - distance / radius).clamp(0.0, 1.0),
+            Falloff::Linear => 1.0,
+        }
+    }
+}
+
+/// Deals area damage around the entity when it dies.
+#[derive(Component, Clone, Debug)]
+pub struct Explosive {
+    pub damage: f32,
+    pub radius: f32,
+    pub element: Element,
+}
+
+/// Side an entity fights on. Area damage never hurts the source's own side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Faction {
+    Player,
+    Enemy,
+    /// Environmental sources (barrels, hazards) hurt everyone
+    Neutral,
+}
+
+/// Position and side of a target area damage can reach.
+type AreaTargetData = (&'static GlobalTransform, Has<Player>, Has<Enemy>);
+
+/// System parameter for dealing radial damage.
+#[derive(SystemParam)]
+pub struct AreaDamage<'w, 's> {
+    rapier_context: Query<'w, 's, &'static RapierContext>,
+    targets: Query<'w, 's, AreaTargetData, (With<Health>, Without<Dead>)>,
+    factions: Query<'w, 's, (Has<Player>, Has<Enemy>)>,
+    /// Writer used for the damage events; also usable for direct hits
+    pub damage_events: EventWriter<'w, DamageEvent>,
+}
+
+impl AreaDamage<'_, '_> {
+    /// Damage every valid target within `radius` of `center`.
+    ///
+    /// Targets on the same side as `source` are skipped. Returns the number of
+    /// entities damaged.
+    pub fn apply_aoe_damage(
+        &mut self,
+        center: Vec3,
+        radius: f32,
+        damage: f32,
+        element: Element,
+        source: Entity,
+        falloff: Falloff,
+    ) -> usize {
+        let Ok(context) = self.rapier_context.get_single() else {
+            return 0;
+        };
+        if radius <= 0.0 {
+            return 0;
+        }
+
+        let source_faction = match self.factions.get(source) {
+            Ok((true, _)) => Faction::Player,
+            Ok((_, true)) => Faction::Enemy,
+            _ => Faction::Neutral,
+        };
+
+        let mut hits = Vec::new();
+        context.intersections_with_shape(
+            center,
+            Quat::IDENTITY,
+            &Collider::ball(radius),
+            QueryFilter::default(),
+            |entity| {
+                let Ok((transform, is_player, is_enemy)) = self.targets.get(entity) else {
+                    return true;
+                };
+                let faction = if is_player {
+                    Faction::Player
+                } else if is_enemy {
+                    Faction::Enemy
+                } else {
+                    Faction::Neutral
+                };
+                if source_faction != Faction::Neutral && faction == source_faction {
+                    return true;
+                }
+                hits.push((entity, transform.translation()));
+                true
+            },
+        );
+
+        let mut damaged = 0;
+        for (target, position) in hits {
+            let offset = position - center;
+            let scale = falloff.scale(offset.length(), radius);
+            if scale <= 0.0 {
+                continue;
+            }
+            let direction = Vec3::new(offset.x, 0.0, offset.z).normalize_or_zero();
+            self.damage_events.send(DamageEvent {
+                target,
+                source,
+                amount: damage * scale,
+                element,
+                knockback: direction * AOE_KNOCKBACK * scale,
+                was_crit: false,
+                melee: false,
+            });
+            damaged += 1;
+        }
+
+        damaged
+    }
+}
+
+/// Blow up explosives that died this frame.
+///
+/// The blast is sent as regular damage events, so other explosives caught in
+/// it detonate on a following frame, letting chains ripple outward.
+pub fn detonate_explosives(
+    mut commands: Commands,
+    mut death_events: EventReader<DeathEvent>,
+    explosive_query: Query<(&GlobalTransform, &Explosive)>,
+    fx_assets: Res<HitFxAssets>,
+    mut area_damage: AreaDamage,
+) {
+    for event in death_events.read() {
+        let Ok((transform, explosive)) = explosive_query.get(event.entity) else {
+            continue;
+        };
+
+        let center = transform.translation();
+        area_damage.apply_aoe_damage(
+            center,
+            explosive.radius,
+            explosive.damage,
+            explosive.element,
+            event.entity,
+            Falloff::Linear,
+        );
+        spawn_particle_burst(&mut commands, center, &fx_assets.explosion_burst());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::scene::ScenePlugin;
+
+    #[test]
+    fn no_falloff_is_full_damage_inside_radius() {
+        for distance in [0.0, 1.5, 3.0] {
+            assert_eq!(Falloff::None.scale(distance, 3.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn linear_falloff_fades_to_the_edge() {
+        assert_eq!(Falloff::Linear.scale(0.0, 4.0), 1.0);
+        assert_eq!(Falloff::Linear.scale(1.0, 4.0), 0.75);
+        assert_eq!(Falloff::Linear.scale(2.0, 4.0), 0.5);
+        assert_eq!(Falloff::Linear.scale(4.0, 4.0), 0.0);
+    }
+
+    #[test]
+    fn linear_falloff_ignores_out_of_range_targets() {
+        // Colliders overlapping the blast can have their center outside it
+        assert_eq!(Falloff::Linear.scale(4.5, 4.0), 0.0);
+        assert_eq!(Falloff::Linear.scale(100.0, 4.0), 0.0);
+    }
+
+    #[test]
+    fn linear_falloff_with_no_radius_is_full_damage() {
+        assert_eq!(Falloff::Linear.scale(0.0, 0.0), 1.0);
+    }
+
+    /// Physics with an enemy blast source at the origin and targets around it.
+    /// Returns the app, the source, and the targets that should be hit.
+    fn blast_app() -> (App, Entity, Vec<Entity>) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            AssetPlugin::default(),
+            ScenePlugin,
+            RapierPhysicsPlugin::<NoUserData>::default(),
+        ))
+        .init_asset::<Mesh>()
+        .add_event::<DamageEvent>();
+
+        let world = app.world_mut();
+        let mut spawn = |position: Vec3| {
+            world
+                .spawn((
+                    Health::new(50.0),
+                    Collider::ball(0.5),
+                    Transform::from_translation(position),
+                ))
+                .id()
+        };
+        let source = spawn(Vec3::ZERO);
+        let player = spawn(Vec3::new(1.0, 0.0, 0.0));
+        let barrel = spawn(Vec3::new(0.0, 0.0, 1.5));
+        let ally = spawn(Vec3::new(-1.0, 0.0, 0.0));
+        let far_player = spawn(Vec3::new(10.0, 0.0, 0.0));
+        // Its collider reaches into the blast but its center is outside
+        let grazing_player = spawn(Vec3::new(3.3, 0.0, 0.0));
+
+        world.entity_mut(source).insert(Enemy);
+        world.entity_mut(ally).insert(Enemy);
+        for entity in [player, far_player, grazing_player] {
+            world.entity_mut(entity).insert(Player);
+        }
+
+        // Let Rapier pick up the colliders
+        app.update();
+        (app, source, vec![player, barrel])
+    }
+
+    fn blast(app: &mut App, source: Entity) -> (usize, Vec<Entity>) {
+        let damaged = app
+            .world_mut()
+            .run_system_once(move |mut area_damage: AreaDamage| {
+                area_damage.apply_aoe_damage(
+                    Vec3::ZERO,
+                    3.0,
+                    10.0,
+                    Element::Fire,
+                    source,
+                    Falloff::Linear,
+                )
+            })
+            .unwrap();
+        let mut targets: Vec<Entity> = app
+            .world()
+            .resource::<Events<DamageEvent>>()
+            .iter_current_update_events()
+            .map(|event| event.target)
+            .collect();
+        targets.sort();
+        (damaged, targets)
+    }
+
+    #[test]
+    fn aoe_hits_only_other_factions_in_range() {
+        let (mut app, source, mut expected) = blast_app();
+        expected.sort();
+
+        let (damaged, targets) = blast(&mut app, source);
+        assert_eq!(targets, expected);
+        assert_eq!(damaged, expected.len());
+    }
+}
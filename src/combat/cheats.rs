@@ -0,0 +1,46 @@
+//! Debug cheats for testing levels and balance.
+//!
+//! Only compiled with the `cheats` cargo feature
+//! (`cargo run --features cheats`), so release builds never include them.
+//!
+//! - Home: god mode, the player ignores all damage
+//! - End: one-shot kills, damage from the player kills enemies outright
+
+use bevy::prelude::*;
+
+use crate::core::GameState;
+
+/// Key toggling god mode.
+const GOD_MODE_KEY: KeyCode = KeyCode::Home;
+
+/// Key toggling one-shot kills.
+const ONE_SHOT_KEY: KeyCode = KeyCode::End;
+
+/// Active debug cheats, checked by `apply_damage`.
+#[derive(Resource, Default)]
+pub struct Cheats {
+    /// Damage to the player is ignored
+    pub god_mode: bool,
+    /// Any damage the player deals to an enemy is lethal
+    pub one_shot: bool,
+}
+
+/// Set up cheat systems.
+pub fn setup_cheat_systems(app: &mut App) {
+    app.init_resource::<Cheats>().add_systems(
+        Update,
+        toggle_cheats_input.run_if(in_state(GameState::InGame)),
+    );
+}
+
+/// Toggle cheats with their keys.
+fn toggle_cheats_input(keyboard: Res<ButtonInput<KeyCode>>, mut cheats: ResMut<Cheats>) {
+    if keyboard.just_pressed(GOD_MODE_KEY) {
+        cheats.god_mode = !cheats.god_mode;
+        info!("God mode: {}", if cheats.god_mode { "on" } else { "off" });
+    }
+    if keyboard.just_pressed(ONE_SHOT_KEY) {
+        cheats.one_shot = !cheats.one_shot;
+        info!("One-shot kills: {}", if cheats.one_shot { "on" } else { "off" });
+    }
+}
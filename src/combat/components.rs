@@ -1,9 +1,12 @@
 //! Combat-related components.
 
 use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
 
 // Re-export from core to avoid duplication
-pub use crate::core::{DamageEvent, DeathEvent, Element};
+pub use crate::core::{DamageDealtEvent, DamageEvent, DeathEvent, Element};
 
 /// Component for entities that can take damage.
 #[derive(Component)]
@@ -67,9 +70,23 @@ impl Resistances {
     }
 }
 
+/// How a weapon's viewmodel moves during a swing (see `combat::viewmodel`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum SwingProfile {
+    /// Diagonal downward slash held for the whole swing
+    #[default]
+    Slash,
+    /// Pull back, then jab straight forward (spears, daggers)
+    Thrust,
+    /// Horizontal sweep from right to left (greatswords, axes)
+    WideArc,
+}
+
 /// Weapon definition component.
 #[derive(Component)]
 pub struct Weapon {
+    /// Weapon registry id it was built from (`None` for built-in weapons)
+    pub id: Option<String>,
     pub name: String,
     pub base_damage: f32,
     pub element: Element,
@@ -77,10 +94,26 @@ pub struct Weapon {
     pub reach: f32,
     /// Damage reduction when blocking (0.0 to 1.0)
     pub block_efficiency: f32,
+    /// Frontal arc in degrees within which blocking mitigates attacks
+    pub block_arc: f32,
     /// Stamina cost per swing
     pub stamina_cost: f32,
     /// Attack cooldown in seconds
     pub attack_cooldown: f32,
+    /// Duration of the swing animation in seconds
+    pub swing_duration: f32,
+    /// Swing progress (0.0-1.0) at which the hit lands
+    pub hit_frame: f32,
+    /// Viewmodel motion during the swing
+    pub swing_profile: SwingProfile,
+    /// Fraction of damage dealt that heals the wielder
+    pub lifesteal: f32,
+    /// Damage multiplier when hitting an enemy from behind
+    pub backstab_multiplier: f32,
+    /// Chance (0.0 to 1.0) that a swing is a critical hit
+    pub crit_chance: f32,
+    /// Damage multiplier of critical hits
+    pub crit_multiplier: f32,
     /// Path to the .glb model file
     pub model_path: String,
 }
@@ -88,13 +121,22 @@ pub struct Weapon {
 impl Default for Weapon {
     fn default() -> Self {
         Self {
+            id: None,
             name: "Fists".to_string(),
             base_damage: 5.0,
             element: Element::Physical,
             reach: 1.5,
             block_efficiency: 0.3,
+            block_arc: 120.0,
             stamina_cost: 10.0,
             attack_cooldown: 0.5,
+            swing_duration: 0.25,
+            hit_frame: 0.5,
+            swing_profile: SwingProfile::Slash,
+            lifesteal: 0.0,
+            backstab_multiplier: 1.5,
+            crit_chance: 0.0,
+            crit_multiplier: 1.5,
             model_path: String::new(),
         }
     }
@@ -107,7 +149,7 @@ pub struct EquippedWeapon;
 /// Combat state for an entity (player or enemy).
 #[derive(Component, Default)]
 pub struct CombatState {
-    /// Is currently in attack animation
+    /// Is currently swinging (see `AttackSwing`)
     pub is_attacking: bool,
     /// Is currently blocking
     pub is_blocking: bool,
@@ -115,8 +157,16 @@ pub struct CombatState {
     pub attack_cooldown: f32,
     /// Invincibility frames remaining
     pub i_frames: f32,
-    /// Whether the current attack has already consumed stamina and done hit detection
-    pub attack_executed: bool,
+    /// Attack button is held, winding up an attack
+    pub is_charging: bool,
+    /// Seconds the attack has been charged (capped at the heavy threshold)
+    pub charge_time: f32,
+    /// The requested attack is a heavy attack
+    pub heavy_attack: bool,
+    /// Step of the current light attack combo (0 = first swing)
+    pub combo_index: u32,
+    /// Time left to continue the combo with another light attack
+    pub combo_window_timer: f32,
 }
 
 impl CombatState {
@@ -129,6 +179,37 @@ impl CombatState {
     }
 }
 
+/// Progress of the player's weapon swing.
+///
+/// Inserted when an attack starts and removed when the swing finishes, like
+/// `OneShotTimer` for enemy attacks. The swing has its own duration, so swing
+/// speed can be tuned without touching `Weapon::attack_cooldown`.
+#[derive(Component)]
+pub struct AttackSwing {
+    pub timer: Timer,
+    /// Swing progress (0.0-1.0) at which hit detection runs
+    pub hit_frame: f32,
+    pub hit_fired: bool,
+    /// Fully charged heavy attack
+    pub heavy: bool,
+}
+
+impl AttackSwing {
+    pub fn new(duration: f32, hit_frame: f32, heavy: bool) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            hit_frame,
+            hit_fired: false,
+            heavy,
+        }
+    }
+
+    /// Swing progress from 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        self.timer.fraction()
+    }
+}
+
 /// Stamina resource for combat actions.
 #[derive(Component)]
 pub struct Stamina {
@@ -138,6 +219,8 @@ pub struct Stamina {
     /// Delay before stamina starts regenerating after use
     pub regen_delay: f32,
     pub regen_timer: f32,
+    /// Set when stamina runs out, cleared once it recovers past the threshold
+    pub exhausted: bool,
 }
 
 impl Default for Stamina {
@@ -148,6 +231,7 @@ impl Default for Stamina {
             regen_rate: 20.0,
             regen_delay: 0.5,
             regen_timer: 0.0,
+            exhausted: false,
         }
     }
 }
@@ -172,6 +256,51 @@ impl Stamina {
     }
 }
 
+/// Tracks time since the entity last took damage, for out-of-combat health regen.
+#[derive(Component, Default)]
+pub struct HealthRegen {
+    /// Time remaining before regeneration starts
+    pub regen_timer: f32,
+}
+
+/// Knockback velocity applied on hit, decaying over time.
+///
+/// The player's knockback is fed into its `KinematicCharacterController` so
+/// Rapier resolves wall collisions; other entities are moved directly.
+#[derive(Component, Default)]
+pub struct Knockback {
+    /// Current horizontal knockback velocity
+    pub velocity: Vec3,
+    /// Fraction of incoming knockback ignored (0.0 = none, 1.0 = immovable)
+    pub resistance: f32,
+}
+
+impl Knockback {
+    /// Knockback scaled down by the given resistance.
+    pub fn with_resistance(resistance: f32) -> Self {
+        Self {
+            velocity: Vec3::ZERO,
+            resistance: resistance.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Add an impulse from a damage event (horizontal only), reduced by resistance.
+    ///
+    /// `strength` converts the impulse into an initial velocity.
+    pub fn apply(&mut self, impulse: Vec3, strength: f32) {
+        let scale = strength * (1.0 - self.resistance);
+        self.velocity += Vec3::new(impulse.x, 0.0, impulse.z) * scale;
+    }
+
+    /// Decay the velocity exponentially at `rate` per second, snapping to zero once negligible.
+    pub fn decay(&mut self, delta: f32, rate: f32) {
+        self.velocity *= (-rate * delta).exp();
+        if self.velocity.length_squared() < 0.0001 {
+            self.velocity = Vec3::ZERO;
+        }
+    }
+}
+
 /// Event sent when an attack is executed.
 #[derive(Event)]
 pub struct AttackEvent {
@@ -181,6 +310,18 @@ pub struct AttackEvent {
     pub direction: Vec3,
 }
 
+/// Sent when an entity's stamina runs out.
+#[derive(Event)]
+pub struct StaminaDepletedEvent {
+    pub entity: Entity,
+}
+
+/// Sent when an exhausted entity's stamina recovers to a usable level.
+#[derive(Event)]
+pub struct StaminaRecoveredEvent {
+    pub entity: Entity,
+}
+
 /// Marker component for entities that have died (prevents multiple death events).
 #[derive(Component)]
 pub struct Dead;
@@ -193,6 +334,25 @@ pub struct AttackHitbox {
     pub element: Element,
 }
 
+/// Random number generator for combat rolls such as critical hits.
+///
+/// Seeded from entropy by default; insert `CombatRng::seeded` for
+/// reproducible rolls.
+#[derive(Resource)]
+pub struct CombatRng(pub StdRng);
+
+impl CombatRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for CombatRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
 /// Screen shake effect resource.
 #[derive(Resource, Default)]
 pub struct ScreenShake {
@@ -217,7 +377,7 @@ impl ScreenShake {
         }
 
         self.timer -= delta;
-        let progress = self.timer / self.duration;
+        let progress = (self.timer / self.duration).max(0.0);
         let current_intensity = self.intensity * progress;
 
         // Random offset
@@ -236,9 +396,11 @@ pub struct HitStop {
 }
 
 impl HitStop {
+    /// Start a freeze. Hits during an active freeze never extend it past the
+    /// longest single `duration`, so many hits in one frame don't stack.
     pub fn trigger(&mut self, duration: f32) {
-        self.duration = duration;
-        self.timer = duration;
+        self.duration = self.duration.max(duration);
+        self.timer = self.timer.max(duration);
     }
 
     pub fn is_active(&self) -> bool {
@@ -249,5 +411,9 @@ impl HitStop {
         if self.timer > 0.0 {
             self.timer -= delta;
         }
+        if self.timer <= 0.0 {
+            self.timer = 0.0;
+            self.duration = 0.0;
+        }
     }
 }
@@ -0,0 +1,121 @@
+//! Brief emissive flash on enemies when damage lands, colored by element.
+//!
+//! Enemy scenes share their glTF materials between instances, so on the first
+//! hit each mesh of the enemy gets its own copy of its material. The flash is
+//! then applied to those copies and fades back to the original emissive.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use super::components::{DamageDealtEvent, Element};
+use crate::enemies::EnemyStats;
+use crate::rendering::JitterSource;
+
+/// Seconds a hit flash takes to fade out.
+const FLASH_DURATION: f32 = 0.15;
+
+/// Brightness of the flash at its peak.
+const FLASH_INTENSITY: f32 = 3.0;
+
+/// Flash color for each damage element.
+fn flash_color(element: Element) -> LinearRgba {
+    let color = match element {
+        Element::Physical => Color::srgb(1.0, 0.1, 0.1),
+        Element::Fire => Color::srgb(1.0, 0.45, 0.05),
+        Element::Ice => Color::srgb(0.3, 0.6, 1.0),
+        Element::Lightning => Color::srgb(0.9, 0.9, 0.3),
+        Element::Poison => Color::srgb(0.3, 0.9, 0.2),
+        Element::Holy => Color::srgb(1.0, 0.95, 0.7),
+        Element::Dark => Color::srgb(0.5, 0.1, 0.7),
+    };
+    color.to_linear() * FLASH_INTENSITY
+}
+
+/// An active hit flash.
+#[derive(Component)]
+pub struct HitFlash {
+    pub color: LinearRgba,
+    /// Seconds until the flash has fully faded
+    pub remaining: f32,
+}
+
+/// Per-enemy material copies and their original emissive colors.
+#[derive(Component)]
+pub struct FlashMaterials(pub Vec<(Handle<StandardMaterial>, LinearRgba)>);
+
+/// Start a flash on enemies that took damage.
+pub fn trigger_hit_flash(
+    mut commands: Commands,
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    enemy_query: Query<Has<FlashMaterials>, With<EnemyStats>>,
+    children_query: Query<&Children>,
+    mesh_material_query: Query<&MeshMaterial3d<StandardMaterial>>,
+    jitter_source_query: Query<&JitterSource>,
+) {
+    // Enemies given material copies this frame (commands aren't applied yet)
+    let mut copied = HashSet::new();
+
+    for event in dealt_events.read() {
+        let Ok(has_materials) = enemy_query.get(event.target) else {
+            continue;
+        };
+
+        if !has_materials && !copied.contains(&event.target) {
+            // Give this enemy its own copies of its scene materials
+            let mut copies = Vec::new();
+            for descendant in children_query.iter_descendants(event.target) {
+                // Meshes drawn with vertex jitter keep their material in `JitterSource`
+                let material = match mesh_material_query.get(descendant) {
+                    Ok(material) => &material.0,
+                    Err(_) => match jitter_source_query.get(descendant) {
+                        Ok(source) => &source.0,
+                        Err(_) => continue,
+                    },
+                };
+                let Some(original) = materials.get(material).cloned() else {
+                    continue;
+                };
+                let emissive = original.emissive;
+                let copy = materials.add(original);
+                commands.entity(descendant).insert(MeshMaterial3d(copy.clone()));
+                copies.push((copy, emissive));
+            }
+
+            // Model not loaded yet - nothing to flash
+            if copies.is_empty() {
+                continue;
+            }
+            commands.entity(event.target).insert(FlashMaterials(copies));
+            copied.insert(event.target);
+        }
+
+        commands.entity(event.target).insert(HitFlash {
+            color: flash_color(event.element),
+            remaining: FLASH_DURATION,
+        });
+    }
+}
+
+/// Fade active flashes back to the original emissive colors.
+pub fn update_hit_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut query: Query<(Entity, &mut HitFlash, &FlashMaterials)>,
+) {
+    for (entity, mut flash, flash_materials) in query.iter_mut() {
+        flash.remaining -= time.delta_secs();
+        let strength = (flash.remaining / FLASH_DURATION).clamp(0.0, 1.0);
+
+        for (handle, original) in &flash_materials.0 {
+            if let Some(material) = materials.get_mut(handle) {
+                material.emissive = *original + flash.color * strength;
+            }
+        }
+
+        if flash.remaining <= 0.0 {
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
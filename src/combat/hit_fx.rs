@@ -0,0 +1,129 @@
+//! Directional particle bursts when damage lands.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::components::DamageDealtEvent;
+use crate::enemies::EnemyStats;
+use crate::player::Player;
+use crate::rendering::{spawn_particle_burst, ParticleBurst};
+
+/// Height above an enemy's origin (at its feet) where hit effects spawn.
+const ENEMY_HIT_HEIGHT: f32 = 0.9;
+
+/// Kind of particles emitted when an entity is hit.
+#[derive(Component, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HitFx {
+    /// Dark red droplets for organic enemies
+    #[default]
+    Blood,
+    /// Bright sparks for metal and stone
+    Sparks,
+    /// No hit particles
+    None,
+}
+
+/// Shared mesh and materials for hit particles.
+#[derive(Resource)]
+pub struct HitFxAssets {
+    mesh: Handle<Mesh>,
+    blood: Handle<StandardMaterial>,
+    sparks: Handle<StandardMaterial>,
+    fire: Handle<StandardMaterial>,
+}
+
+impl FromWorld for HitFxAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cuboid::new(0.05, 0.05, 0.05));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let blood = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.35, 0.02, 0.02),
+            perceptual_roughness: 0.4,
+            ..default()
+        });
+        let sparks = materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.75, 0.35),
+            emissive: LinearRgba::rgb(4.0, 2.5, 0.8),
+            unlit: true,
+            ..default()
+        });
+        let fire = materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.45, 0.1),
+            emissive: LinearRgba::rgb(6.0, 2.0, 0.3),
+            unlit: true,
+            ..default()
+        });
+
+        Self { mesh, blood, sparks, fire }
+    }
+}
+
+impl HitFxAssets {
+    /// Burst parameters for a hit effect travelling along `direction`.
+    fn burst(&self, fx: HitFx, direction: Vec3) -> Option<ParticleBurst> {
+        match fx {
+            HitFx::Blood => Some(ParticleBurst {
+                mesh: self.mesh.clone(),
+                material: self.blood.clone(),
+                count: 12,
+                direction: direction + Vec3::Y * 0.3,
+                spread: 0.5,
+                speed: (1.5, 3.5),
+                gravity: 9.8,
+                lifetime: (0.4, 0.7),
+            }),
+            HitFx::Sparks => Some(ParticleBurst {
+                mesh: self.mesh.clone(),
+                material: self.sparks.clone(),
+                count: 10,
+                direction: direction + Vec3::Y * 0.5,
+                spread: 0.8,
+                speed: (3.0, 6.0),
+                gravity: 6.0,
+                lifetime: (0.15, 0.35),
+            }),
+            HitFx::None => None,
+        }
+    }
+
+    /// Burst parameters for an explosion.
+    pub(super) fn explosion_burst(&self) -> ParticleBurst {
+        ParticleBurst {
+            mesh: self.mesh.clone(),
+            material: self.fire.clone(),
+            count: 40,
+            direction: Vec3::ZERO,
+            spread: 1.0,
+            speed: (3.0, 8.0),
+            gravity: 4.0,
+            lifetime: (0.3, 0.7),
+        }
+    }
+}
+
+/// Where a hit lands, its particle override and whether the target is an enemy.
+type HitFxTargetData<'a> = (&'a GlobalTransform, Option<&'a HitFx>, Has<EnemyStats>);
+
+/// Spawn hit particles on damaged entities, directed along the knockback.
+pub fn spawn_hit_fx(
+    mut commands: Commands,
+    assets: Res<HitFxAssets>,
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    target_query: Query<HitFxTargetData, Without<Player>>,
+) {
+    for event in dealt_events.read() {
+        // The player's own hits would burst inside the camera
+        let Ok((transform, fx, is_enemy)) = target_query.get(event.target) else {
+            continue;
+        };
+
+        let height = if is_enemy { ENEMY_HIT_HEIGHT } else { 0.0 };
+        let origin = transform.translation() + Vec3::Y * height;
+
+        if let Some(burst) = assets.burst(fx.copied().unwrap_or_default(), event.knockback) {
+            spawn_particle_burst(&mut commands, origin, &burst);
+        }
+    }
+}
@@ -1,10 +1,27 @@
 //! Combat module - weapons, attacks, blocking, and damage.
 
+mod aoe;
+#[cfg(feature = "cheats")]
+mod cheats;
 mod components;
+mod hit_flash;
+mod hit_fx;
 mod plugin;
+mod projectile;
+mod run_stats;
 mod systems;
+mod tuning;
 mod viewmodel;
+mod weapons;
 
+pub use aoe::{AreaDamage, Explosive, Falloff};
+#[cfg(feature = "cheats")]
+pub use cheats::Cheats;
 pub use components::*;
+pub use hit_fx::HitFx;
 pub use plugin::{create_starter_weapon, CombatPlugin};
-pub use viewmodel::WeaponViewmodel;
+pub use projectile::{sweep_projectile, ProjectileHit};
+pub use run_stats::RunStats;
+pub use tuning::CombatTuning;
+pub use viewmodel::{ViewmodelArms, WeaponViewmodel};
+pub use weapons::{EquipWeaponEvent, WeaponDefinition, WeaponRegistry, STARTER_WEAPON};
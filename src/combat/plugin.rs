@@ -2,9 +2,12 @@
 
 use bevy::prelude::*;
 
+#[cfg(feature = "cheats")]
+use super::cheats;
 use super::components::*;
 use super::systems;
 use super::viewmodel;
+use super::weapons;
 
 /// Combat plugin - handles all combat systems.
 pub struct CombatPlugin;
@@ -14,21 +17,40 @@ impl Plugin for CombatPlugin {
         // Setup combat systems
         systems::setup_combat_systems(app);
 
+        // Setup debug cheats
+        #[cfg(feature = "cheats")]
+        cheats::setup_cheat_systems(app);
+
+        // Setup weapon registry and equipping
+        weapons::setup_weapon_systems(app);
+
         // Setup viewmodel systems
         viewmodel::setup_viewmodel_systems(app);
     }
 }
 
-/// Create a default starter weapon.
+/// Create the built-in starter weapon.
+///
+/// Used when `assets/data/weapons/short_sword.ron` is missing; see
+/// `WeaponRegistry::starter_weapon`.
 pub fn create_starter_weapon() -> Weapon {
     Weapon {
+        id: None,
         name: "Short Sword".to_string(),
         base_damage: 15.0,
         element: Element::Physical,
         reach: 2.5,
         block_efficiency: 0.5,
+        block_arc: 120.0,
         stamina_cost: 0.6,
         attack_cooldown: 0.4,
+        swing_duration: 0.25,
+        hit_frame: 0.4,
+        swing_profile: SwingProfile::Slash,
+        lifesteal: 0.0,
+        backstab_multiplier: 2.0,
+        crit_chance: 0.05,
+        crit_multiplier: 1.5,
         model_path: "models/weapons/Sword.glb#Scene0".to_string(),
     }
 }
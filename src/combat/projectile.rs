@@ -0,0 +1,45 @@
+//! Swept collision shared by projectiles and thrown items.
+//!
+//! Moving objects are swept as small spheres along their per-frame step, so
+//! fast projectiles can't tunnel through thin walls or slip past the edge of
+//! the player's capsule the way a single ray can. A projectile that starts
+//! inside a collider hits it immediately.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+/// Where a projectile's sweep hit a collider.
+pub struct ProjectileHit {
+    pub entity: Entity,
+    /// Projectile position at the moment of contact
+    pub position: Vec3,
+}
+
+/// Sweep a sphere of `radius` from `position` along `step`.
+///
+/// Ignores the `source` collider and sensors. Returns the first collider
+/// touched, including one already overlapping the sphere at `position`.
+pub fn sweep_projectile(
+    context: &RapierContext,
+    position: Vec3,
+    step: Vec3,
+    radius: f32,
+    source: Entity,
+) -> Option<ProjectileHit> {
+    let options = ShapeCastOptions {
+        // Velocity is the whole step, so a time of impact of 1.0 is its end
+        max_time_of_impact: 1.0,
+        stop_at_penetration: true,
+        ..default()
+    };
+    let filter = QueryFilter::default()
+        .exclude_collider(source)
+        .exclude_sensors();
+
+    context
+        .cast_shape(position, Quat::IDENTITY, step, &Collider::ball(radius), options, filter)
+        .map(|(entity, hit)| ProjectileHit {
+            entity,
+            position: position + step * hit.time_of_impact,
+        })
+}
@@ -0,0 +1,32 @@
+//! Per-run statistics shown on the death screen.
+
+use bevy::prelude::*;
+
+use crate::core::PlayTime;
+
+/// Counters for the current run (since New Game or Retry).
+#[derive(Resource, Default, Clone)]
+pub struct RunStats {
+    pub enemies_killed: u32,
+    /// Damage dealt by the player, after resistances and blocking
+    pub damage_dealt: f32,
+    /// Damage taken by the player, after resistances and blocking
+    pub damage_taken: f32,
+    /// `PlayTime::total` when the run started
+    pub started_at: f64,
+}
+
+impl RunStats {
+    /// Start a new run at the current play time.
+    pub fn start(play_time: &PlayTime) -> Self {
+        Self {
+            started_at: play_time.total,
+            ..default()
+        }
+    }
+
+    /// Seconds of gameplay since the run started.
+    pub fn time_survived(&self, play_time: &PlayTime) -> f64 {
+        (play_time.total - self.started_at).max(0.0)
+    }
+}
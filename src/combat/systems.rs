@@ -1,12 +1,24 @@
 //! Combat systems - attack, block, damage handling.
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::time::Real;
 use bevy_rapier3d::prelude::*;
+use rand::Rng;
 
+use super::aoe::detonate_explosives;
+#[cfg(feature = "cheats")]
+use super::cheats::Cheats;
 use super::components::*;
-use crate::core::{GameState, PlayState};
-use crate::enemies::{Enemy, EnemyStats, AttackHitEvent};
-use crate::player::{Player, PlayerCamera};
+use super::hit_flash::{trigger_hit_flash, update_hit_flash};
+use super::hit_fx::{spawn_hit_fx, HitFxAssets};
+use super::run_stats::RunStats;
+use super::tuning::CombatTuning;
+use crate::core::{Action, ActionInput, EnemyKilledEvent, GameState, PlayState, ReduceMotion};
+use crate::enemies::{AiState, AttackTimer, Enemy, EnemyStats, EnemyType, AttackHitEvent};
+use crate::player::{
+    Attributes, CameraBaseTransform, HeadBob, Player, PlayerCamera, PlayerConfig,
+};
 
 /// System set ordering for combat.
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,13 +33,20 @@ pub enum CombatSet {
 pub fn setup_combat_systems(app: &mut App) {
     app
         // Resources
+        .insert_resource(CombatTuning::load())
         .init_resource::<ScreenShake>()
         .init_resource::<HitStop>()
+        .init_resource::<CombatRng>()
+        .init_resource::<RunStats>()
+        .init_resource::<HitFxAssets>()
 
         // Events
         .add_event::<AttackEvent>()
         .add_event::<DamageEvent>()
+        .add_event::<DamageDealtEvent>()
         .add_event::<DeathEvent>()
+        .add_event::<StaminaDepletedEvent>()
+        .add_event::<StaminaRecoveredEvent>()
 
         // System ordering
         .configure_sets(
@@ -57,9 +76,11 @@ pub fn setup_combat_systems(app: &mut App) {
         .add_systems(
             Update,
             (
-                execute_attack,
+                start_attack,
+                update_attack_swing,
                 handle_blocking,
                 update_cooldowns,
+                track_stamina_exhaustion.after(start_attack).after(handle_blocking),
             )
                 .in_set(CombatSet::Action),
         )
@@ -68,11 +89,15 @@ pub fn setup_combat_systems(app: &mut App) {
         .add_systems(
             Update,
             (
-                process_attack_hits,
                 process_enemy_attack_hits,
                 apply_damage,
+                process_attack_hits,
+                apply_knockback,
+                health_regen,
+                detonate_explosives,
                 check_deaths,
             )
+                .chain()
                 .in_set(CombatSet::Damage),
         )
 
@@ -81,15 +106,33 @@ pub fn setup_combat_systems(app: &mut App) {
             Update,
             (
                 update_screen_shake,
-                update_hit_stop,
+                update_knockback,
+                spawn_hit_fx,
+                count_enemy_kills,
+                (trigger_hit_flash, update_hit_flash).chain(),
             )
                 .in_set(CombatSet::Feedback),
-        );
+        )
+        // Hit stop drives virtual time, so it keeps running outside
+        // exploration (e.g. a killing blow that opens the level-up screen)
+        .add_systems(
+            Update,
+            update_hit_stop
+                .after(CombatSet::Damage)
+                .run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(OnExit(GameState::InGame), reset_hit_stop);
 }
 
 /// Handle combat input from the player.
+///
+/// Pressing attack starts charging; releasing attacks. Releasing before
+/// `CombatTuning::heavy_charge_time` is a light attack, after it a heavy one.
+/// The charge stops growing once it reaches the threshold.
 fn combat_input(
-    mouse: Res<ButtonInput<MouseButton>>,
+    time: Res<Time>,
+    input: ActionInput,
+    tuning: Res<CombatTuning>,
     mut query: Query<(&mut CombatState, &Stamina), With<Player>>,
     hit_stop: Res<HitStop>,
 ) {
@@ -102,13 +145,31 @@ fn combat_input(
         return;
     };
 
-    // Left click - light attack
-    if mouse.just_pressed(MouseButton::Left) && combat.can_attack() && stamina.current > 0.0 {
-        combat.is_attacking = true;
+    // Attack - start charging
+    if input.just_pressed(Action::Attack) && combat.can_attack() && stamina.current > 0.0 {
+        combat.is_charging = true;
+        combat.charge_time = 0.0;
     }
 
-    // Right click - block
-    combat.is_blocking = mouse.pressed(MouseButton::Right) && combat.can_block();
+    if combat.is_charging {
+        if input.pressed(Action::Attack) {
+            combat.charge_time =
+                (combat.charge_time + time.delta_secs()).min(tuning.heavy_charge_time);
+        } else {
+            // Released (also catches a release during hit stop)
+            combat.is_attacking = true;
+            combat.heavy_attack = combat.charge_time >= tuning.heavy_charge_time;
+            combat.is_charging = false;
+            combat.charge_time = 0.0;
+        }
+    }
+
+    // Block (cancels a charge)
+    combat.is_blocking = input.pressed(Action::Block) && combat.can_block();
+    if combat.is_blocking {
+        combat.is_charging = false;
+        combat.charge_time = 0.0;
+    }
 }
 
 /// Regenerate stamina over time.
@@ -118,15 +179,22 @@ fn stamina_regen(time: Res<Time>, mut query: Query<&mut Stamina>) {
     }
 }
 
-/// Execute attack when attack animation triggers.
-fn execute_attack(
+/// The player's side of an attack about to start.
+type AttackerData<'a> = (
+    Entity,
+    &'a mut CombatState,
+    &'a mut Stamina,
+    &'a Weapon,
+    Option<&'a Attributes>,
+);
+
+/// Start the swing for a requested attack.
+fn start_attack(
     mut commands: Commands,
-    mut query: Query<(Entity, &Transform, &mut CombatState, &mut Stamina, &Weapon), With<Player>>,
-    enemy_query: Query<Entity, With<Enemy>>,
-    mut attack_events: EventWriter<AttackEvent>,
-    rapier_context: Query<&RapierContext>,
+    tuning: Res<CombatTuning>,
+    mut query: Query<AttackerData, (With<Player>, Without<AttackSwing>)>,
 ) {
-    let Ok((player_entity, transform, mut combat, mut stamina, weapon)) = query.get_single_mut()
+    let Ok((player_entity, mut combat, mut stamina, weapon, attributes)) = query.get_single_mut()
     else {
         return;
     };
@@ -135,21 +203,113 @@ fn execute_attack(
         return;
     }
 
-    // Only consume stamina and do hit detection once per attack
-    if combat.attack_executed {
-        return;
-    }
+    let heavy = std::mem::take(&mut combat.heavy_attack);
 
     // Check stamina
-    if !stamina.use_stamina(weapon.stamina_cost) {
+    let stamina_cost = if heavy {
+        weapon.stamina_cost * tuning.heavy_stamina_multiplier
+    } else {
+        weapon.stamina_cost
+    };
+    if !stamina.use_stamina(stamina_cost) {
         combat.is_attacking = false;
         return;
     }
 
-    // Mark attack as executed so we don't consume stamina again
-    combat.attack_executed = true;
+    // Light attacks inside the combo window continue the combo; anything
+    // else starts over. Heavy attacks always end it.
+    combat.combo_index = if !heavy && combat.combo_window_timer > 0.0 {
+        (combat.combo_index + 1) % tuning.combo_steps()
+    } else {
+        0
+    };
+    combat.combo_window_timer = 0.0;
+
+    // Dexterity speeds up both the swing and the cooldown
+    let attack_speed = attributes.map_or(1.0, Attributes::attack_speed_multiplier);
+    commands.entity(player_entity).insert(AttackSwing::new(
+        weapon.swing_duration / attack_speed,
+        weapon.hit_frame,
+        heavy,
+    ));
+
+    // Cooldown runs independently of the swing
+    combat.attack_cooldown = weapon.attack_cooldown / attack_speed;
+}
+
+/// Sneak-attack state, transform and enemy marker of a swing target.
+type SwingTargetData = (Option<&'static AiState>, &'static Transform, Has<Enemy>);
+
+/// Anything a swing can hit: enemies and destructible objects.
+type SwingTargetFilter = Or<(With<Enemy>, With<Health>)>;
+
+/// The player's side of a swing in progress.
+type SwingerData<'a> = (
+    Entity,
+    &'a Transform,
+    &'a mut CombatState,
+    &'a mut AttackSwing,
+    &'a Weapon,
+    Option<&'a Attributes>,
+);
+
+/// What a swing's hit detection reads and rolls against.
+#[derive(SystemParam)]
+struct SwingTargets<'w, 's> {
+    rapier_context: Query<'w, 's, &'static RapierContext>,
+    targets: Query<'w, 's, SwingTargetData, SwingTargetFilter>,
+    screen_shake: ResMut<'w, ScreenShake>,
+    rng: ResMut<'w, CombatRng>,
+}
+
+/// Advance the player's swing, running hit detection at the weapon's hit frame.
+fn update_attack_swing(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<SwingerData, With<Player>>,
+    tuning: Res<CombatTuning>,
+    mut attack_events: EventWriter<AttackEvent>,
+    mut targets: SwingTargets,
+) {
+    let Ok((player_entity, transform, mut combat, mut swing, weapon, attributes)) =
+        query.get_single_mut()
+    else {
+        return;
+    };
+
+    swing.timer.tick(time.delta());
+
+    if swing.timer.finished() {
+        combat.is_attacking = false;
+        if !swing.heavy {
+            combat.combo_window_timer = tuning.combo_window;
+        }
+        commands.entity(player_entity).remove::<AttackSwing>();
+    }
+
+    // Only do hit detection once per swing, when it reaches the hit frame
+    if swing.hit_fired || swing.progress() < swing.hit_frame {
+        return;
+    }
+    swing.hit_fired = true;
 
-    let damage = weapon.base_damage;
+    let (heavy_damage, heavy_reach) = if swing.heavy {
+        (tuning.heavy_damage_multiplier, tuning.heavy_reach_multiplier)
+    } else {
+        (1.0, 1.0)
+    };
+    let combo_damage = if swing.heavy {
+        1.0
+    } else {
+        tuning.combo_damage_multiplier(combat.combo_index)
+    };
+    // One crit roll per swing, shared by everything it hits
+    let (was_crit, crit_damage) = roll_crit(&mut targets.rng, weapon);
+    let damage = weapon.base_damage
+        * attributes.map_or(1.0, Attributes::damage_multiplier)
+        * heavy_damage
+        * combo_damage
+        * crit_damage;
 
     // Get attack direction (forward)
     let direction = transform.forward().as_vec3();
@@ -163,10 +323,16 @@ fn execute_attack(
     });
 
     // Sphere overlap for hit detection (better for melee combat)
+    let SwingTargets {
+        rapier_context,
+        targets,
+        screen_shake,
+        ..
+    } = &mut targets;
     if let Ok(context) = rapier_context.get_single() {
         // Position the sphere slightly in front of the player
         let sphere_center = transform.translation + direction * (weapon.reach * 0.5) + Vec3::Y * 0.5;
-        let sphere_radius = weapon.reach * 0.6;
+        let sphere_radius = weapon.reach * 0.6 * heavy_reach;
 
         // Use shape intersection for sphere overlap
         let shape = Collider::ball(sphere_radius);
@@ -179,70 +345,153 @@ fn execute_attack(
             &shape,
             QueryFilter::default().exclude_collider(player_entity),
             |hit_entity| {
-                // Only damage enemies
-                if enemy_query.get(hit_entity).is_ok() {
+                // Only damage enemies and destructible objects
+                if let Ok((ai_state, target_transform, is_enemy)) = targets.get(hit_entity) {
+                    let mut amount = if is_sneak_attack(ai_state) {
+                        screen_shake
+                            .shake(tuning.sneak_shake_intensity, tuning.sneak_shake_duration);
+                        damage * tuning.sneak_attack_multiplier
+                    } else {
+                        damage
+                    };
+
+                    if is_enemy
+                        && is_behind(target_transform, transform.translation, tuning.backstab_arc)
+                    {
+                        amount *= weapon.backstab_multiplier;
+                    }
+
                     commands.send_event(DamageEvent {
                         target: hit_entity,
                         source: player_entity,
-                        amount: damage,
+                        amount,
                         element: weapon.element,
-                        knockback: direction * 2.0,
+                        knockback: direction * tuning.player_attack_knockback,
+                        was_crit,
+                        melee: true,
                     });
                 }
                 true // Continue checking other entities
             },
         );
     }
+}
 
-    // Set cooldown - is_attacking will be reset by update_cooldowns
-    // when cooldown drops below half (giving time for attack animation)
-    combat.attack_cooldown = weapon.attack_cooldown;
+/// Whether a hit on a target with this AI state is a sneak attack: enemies
+/// that haven't noticed the player take bonus damage. Targets without AI,
+/// such as barrels, never do.
+fn is_sneak_attack(ai_state: Option<&AiState>) -> bool {
+    ai_state == Some(&AiState::Idle)
+}
+
+/// Roll whether a swing is a critical hit, returning the roll and the
+/// damage multiplier it applies.
+fn roll_crit(rng: &mut CombatRng, weapon: &Weapon) -> (bool, f32) {
+    let was_crit = rng.0.gen::<f32>() < weapon.crit_chance;
+    let multiplier = if was_crit { weapon.crit_multiplier } else { 1.0 };
+    (was_crit, multiplier)
+}
+
+/// Whether `position` is within the rear arc (in degrees) of an enemy.
+///
+/// Enemy models face +Z, so their facing is `Transform::back`. Only the
+/// horizontal offset counts.
+fn is_behind(enemy_transform: &Transform, position: Vec3, arc: f32) -> bool {
+    let facing = enemy_transform.back().as_vec3().with_y(0.0).normalize_or_zero();
+    let to_position = (position - enemy_transform.translation)
+        .with_y(0.0)
+        .normalize_or_zero();
+    if facing == Vec3::ZERO || to_position == Vec3::ZERO {
+        return false;
+    }
+    facing.dot(to_position) <= -(arc.to_radians() / 2.0).cos()
+}
+
+/// Send events when stamina runs out or recovers from exhaustion.
+fn track_stamina_exhaustion(
+    tuning: Res<CombatTuning>,
+    mut query: Query<(Entity, &mut Stamina)>,
+    mut depleted_events: EventWriter<StaminaDepletedEvent>,
+    mut recovered_events: EventWriter<StaminaRecoveredEvent>,
+) {
+    for (entity, mut stamina) in query.iter_mut() {
+        if !stamina.exhausted && stamina.current <= 0.0 {
+            stamina.exhausted = true;
+            depleted_events.send(StaminaDepletedEvent { entity });
+        } else if stamina.exhausted
+            && stamina.current >= stamina.maximum * tuning.stamina_recovery_threshold
+        {
+            stamina.exhausted = false;
+            recovered_events.send(StaminaRecoveredEvent { entity });
+        }
+    }
 }
 
 /// Handle blocking state.
 fn handle_blocking(
     mut query: Query<(&CombatState, &mut Stamina), With<Player>>,
     time: Res<Time>,
+    tuning: Res<CombatTuning>,
 ) {
     for (combat, mut stamina) in query.iter_mut() {
         if combat.is_blocking {
             // Blocking drains stamina slowly
-            stamina.current = (stamina.current - 5.0 * time.delta_secs()).max(0.0);
+            let drain = tuning.block_stamina_drain * time.delta_secs();
+            stamina.current = (stamina.current - drain).max(0.0);
             stamina.regen_timer = stamina.regen_delay;
         }
     }
 }
 
 /// Update combat cooldowns.
-fn update_cooldowns(time: Res<Time>, mut query: Query<(&mut CombatState, &Weapon)>) {
-    for (mut combat, weapon) in query.iter_mut() {
+fn update_cooldowns(time: Res<Time>, mut query: Query<&mut CombatState>) {
+    for mut combat in query.iter_mut() {
         if combat.attack_cooldown > 0.0 {
             combat.attack_cooldown -= time.delta_secs();
-
-            // Reset is_attacking after the attack animation portion (first 60% of cooldown)
-            // This gives visual feedback while still preventing spam attacks
-            let attack_anim_threshold = weapon.attack_cooldown * 0.4;
-            if combat.is_attacking && combat.attack_cooldown <= attack_anim_threshold {
-                combat.is_attacking = false;
-                combat.attack_executed = false;
-            }
         }
         if combat.i_frames > 0.0 {
             combat.i_frames -= time.delta_secs();
         }
+        if combat.combo_window_timer > 0.0 {
+            combat.combo_window_timer -= time.delta_secs();
+        }
     }
 }
 
-/// Process hits from attacks.
+/// Trigger combat feedback for hits that landed.
+///
+/// Only hits the player deals or takes shake the screen, so enemies on
+/// traps elsewhere in the level go unnoticed. Hit stop is further kept to
+/// melee hits: continuous sources like damage floors would otherwise re-arm
+/// it every frame and keep gameplay frozen.
 fn process_attack_hits(
-    mut damage_events: EventReader<DamageEvent>,
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    player_query: Query<(), With<Player>>,
+    tuning: Res<CombatTuning>,
     mut screen_shake: ResMut<ScreenShake>,
     mut hit_stop: ResMut<HitStop>,
 ) {
-    for _event in damage_events.read() {
-        // Trigger combat feedback
-        screen_shake.shake(0.1, 0.15);
-        hit_stop.trigger(0.05);
+    for event in dealt_events.read() {
+        let player_involved =
+            player_query.contains(event.source) || player_query.contains(event.target);
+        if !player_involved {
+            continue;
+        }
+
+        // Stronger feedback for critical hits
+        if event.was_crit {
+            screen_shake.shake(tuning.crit_shake_intensity, tuning.crit_shake_duration);
+        } else {
+            screen_shake.shake(tuning.hit_shake_intensity, tuning.hit_shake_duration);
+        }
+
+        if event.melee {
+            hit_stop.trigger(if event.was_crit {
+                tuning.crit_hit_stop_duration
+            } else {
+                tuning.hit_stop_duration
+            });
+        }
     }
 }
 
@@ -252,6 +501,7 @@ fn process_enemy_attack_hits(
     mut attack_hit_events: EventReader<AttackHitEvent>,
     enemy_query: Query<(Entity, &Transform, &EnemyStats), With<Enemy>>,
     player_query: Query<(Entity, &Transform), With<Player>>,
+    tuning: Res<CombatTuning>,
 ) {
     let Ok((player_entity, player_transform)) = player_query.get_single() else {
         return;
@@ -262,35 +512,97 @@ fn process_enemy_attack_hits(
             continue;
         };
 
-        // Check if player is still in attack range
+        // Check if player is still in range of the attack that fired
         let distance = enemy_transform.translation.distance(player_transform.translation);
-        if distance > stats.attack_range {
+        if distance > event.range {
             continue;
         }
 
         // Calculate knockback direction
         let direction = (player_transform.translation - enemy_transform.translation).normalize_or_zero();
 
+        // Player must be in front of the enemy (model forward is +Z)
+        let facing = enemy_transform.back().as_vec3();
+        if !stats.in_attack_arc(facing, direction) {
+            continue;
+        }
+
         // Send damage event to player
         commands.send_event(DamageEvent {
             target: player_entity,
             source: enemy_entity,
             amount: event.damage,
             element: Element::Physical,
-            knockback: direction * 2.0,
+            knockback: direction * tuning.enemy_attack_knockback,
+            was_crit: false,
+            melee: true,
         });
     }
 }
 
+/// Everything about a damage target that decides how much a hit removes.
+type DamageTargetData<'a> = (
+    &'a mut Health,
+    Option<&'a Resistances>,
+    Option<&'a mut CombatState>,
+    Option<&'a Weapon>,
+    Option<&'a Attributes>,
+    Option<&'a Dead>,
+);
+
+/// What `apply_damage` looks up about the sides of a hit.
+#[derive(SystemParam)]
+struct DamageLookups<'w, 's> {
+    transform_query: Query<'w, 's, &'static GlobalTransform>,
+    player_query: Query<'w, 's, (), With<Player>>,
+    tuning: Res<'w, CombatTuning>,
+}
+
+/// Where `apply_damage` reports the hits it applied.
+#[derive(SystemParam)]
+struct DamageOutcomes<'w> {
+    death_events: EventWriter<'w, DeathEvent>,
+    dealt_events: EventWriter<'w, DamageDealtEvent>,
+    run_stats: ResMut<'w, RunStats>,
+}
+
+/// Debug cheats that change how damage applies.
+#[cfg(feature = "cheats")]
+#[derive(SystemParam)]
+struct DamageCheats<'w, 's> {
+    cheats: Res<'w, Cheats>,
+    enemy_query: Query<'w, 's, (), With<Enemy>>,
+}
+
 /// Apply damage to entities.
 fn apply_damage(
     mut commands: Commands,
     mut damage_events: EventReader<DamageEvent>,
-    mut health_query: Query<(&mut Health, Option<&Resistances>, Option<&CombatState>, Option<&Dead>)>,
-    mut death_events: EventWriter<DeathEvent>,
+    mut health_query: Query<DamageTargetData>,
+    lookups: DamageLookups,
+    outcomes: DamageOutcomes,
+    #[cfg(feature = "cheats")] cheats: DamageCheats,
 ) {
+    let DamageLookups {
+        transform_query,
+        player_query,
+        tuning,
+    } = lookups;
+    let DamageOutcomes {
+        mut death_events,
+        mut dealt_events,
+        mut run_stats,
+    } = outcomes;
+    #[cfg(feature = "cheats")]
+    let DamageCheats {
+        cheats,
+        enemy_query,
+    } = cheats;
+
     // Track entities that died this frame to avoid duplicate death events
     let mut died_this_frame = std::collections::HashSet::new();
+    // Damage dealt by each source, for weapon lifesteal
+    let mut dealt_by_source = Vec::new();
 
     for event in damage_events.read() {
         // Skip if already processed death this frame
@@ -298,14 +610,21 @@ fn apply_damage(
             continue;
         }
 
-        if let Ok((mut health, resistances, combat_state, dead)) = health_query.get_mut(event.target) {
+        if let Ok((mut health, resistances, mut combat_state, weapon, attributes, dead)) =
+            health_query.get_mut(event.target)
+        {
             // Skip if already dead (from previous frames)
             if dead.is_some() {
                 continue;
             }
 
+            #[cfg(feature = "cheats")]
+            if cheats.god_mode && player_query.contains(event.target) {
+                continue;
+            }
+
             // Check for i-frames
-            if let Some(combat) = combat_state {
+            if let Some(combat) = &combat_state {
                 if combat.i_frames > 0.0 {
                     continue;
                 }
@@ -314,10 +633,14 @@ fn apply_damage(
             // Calculate resistance
             let resistance = resistances.map_or(0.0, |r| r.get(event.element));
 
-            // Check for blocking (reduces damage further)
-            let block_reduction = if let Some(combat) = combat_state {
-                if combat.is_blocking {
-                    0.5 // 50% reduction when blocking
+            // Blocking reduces attacks landing within the weapon's frontal
+            // arc; blocking without a weapon uses the fists' stats
+            let is_blocking = combat_state.as_ref().is_some_and(|combat| combat.is_blocking);
+            let block_reduction = if is_blocking {
+                let fists = Weapon::default();
+                let blocking_weapon = weapon.unwrap_or(&fists);
+                if is_within_block_arc(event, blocking_weapon.block_arc, &transform_query) {
+                    blocking_weapon.block_efficiency
                 } else {
                     0.0
                 }
@@ -325,8 +648,53 @@ fn apply_damage(
                 0.0
             };
 
-            let final_damage = event.amount * (1.0 - resistance) * (1.0 - block_reduction);
-            health.take_damage(final_damage);
+            // Defense attribute reduces everything that gets through
+            let defense_reduction = attributes.map_or(0.0, Attributes::damage_reduction);
+
+            let final_damage = event.amount
+                * (1.0 - resistance)
+                * (1.0 - block_reduction)
+                * (1.0 - defense_reduction);
+
+            #[cfg(feature = "cheats")]
+            let final_damage = if cheats.one_shot
+                && player_query.contains(event.source)
+                && enemy_query.contains(event.target)
+            {
+                health.current
+            } else {
+                final_damage
+            };
+
+            let dealt = health.take_damage(final_damage);
+            if event.source != event.target {
+                dealt_by_source.push((event.source, dealt));
+            }
+            if dealt > 0.0 {
+                dealt_events.send(DamageDealtEvent {
+                    target: event.target,
+                    source: event.source,
+                    amount: dealt,
+                    element: event.element,
+                    knockback: event.knockback,
+                    was_crit: event.was_crit,
+                    melee: event.melee,
+                });
+
+                // Brief invincibility so a crowd can't hit every frame; fully
+                // absorbed hits don't grant it
+                let is_player = player_query.contains(event.target);
+                if let Some(combat) = combat_state.as_mut().filter(|_| is_player) {
+                    combat.i_frames = tuning.player_i_frames;
+                }
+            }
+
+            // Track run statistics
+            if player_query.contains(event.target) {
+                run_stats.damage_taken += dealt;
+            } else if player_query.contains(event.source) {
+                run_stats.damage_dealt += dealt;
+            }
 
             if health.is_dead() {
                 // Mark as dead to prevent multiple death events
@@ -339,22 +707,224 @@ fn apply_damage(
             }
         }
     }
+
+    // Heal wielders of lifesteal weapons by a share of the damage they dealt
+    for (source, dealt) in dealt_by_source {
+        if let Ok((mut health, _, _, Some(weapon), _, None)) = health_query.get_mut(source) {
+            if weapon.lifesteal > 0.0 {
+                health.heal(dealt * weapon.lifesteal);
+            }
+        }
+    }
+}
+
+/// Whether an attack comes from within `arc` degrees centered on the
+/// target's facing.
+///
+/// The attack direction is taken from the knockback (which points away from
+/// the attacker), falling back to the source's position. Attacks with no
+/// known direction count as frontal.
+fn is_within_block_arc(
+    event: &DamageEvent,
+    arc: f32,
+    transform_query: &Query<&GlobalTransform>,
+) -> bool {
+    let Ok(target_transform) = transform_query.get(event.target) else {
+        return true;
+    };
+    let target_pos = target_transform.translation();
+
+    let toward_attacker = if event.knockback.xz() != Vec2::ZERO {
+        -event.knockback
+    } else if let Ok(source_transform) = transform_query.get(event.source) {
+        source_transform.translation() - target_pos
+    } else {
+        return true;
+    };
+
+    let toward_attacker = Vec3::new(toward_attacker.x, 0.0, toward_attacker.z).normalize_or_zero();
+    let forward = target_transform.forward().as_vec3();
+    let forward = Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero();
+    if toward_attacker == Vec3::ZERO || forward == Vec3::ZERO {
+        return true;
+    }
+
+    forward.angle_between(toward_attacker).to_degrees() <= arc * 0.5
+}
+
+/// A knockback target, with the block and wind-up state a shove interacts with.
+type KnockbackTargetData<'a> = (
+    &'a mut Knockback,
+    Option<&'a CombatState>,
+    Option<&'a mut AttackTimer>,
+);
+
+/// Add knockback from damage events to entities that can be knocked back.
+///
+/// A strong enough shove makes an enemy start its attack wind-up over.
+fn apply_knockback(
+    mut damage_events: EventReader<DamageEvent>,
+    mut query: Query<KnockbackTargetData, Without<Dead>>,
+    tuning: Res<CombatTuning>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut knockback, combat_state, attack_timer)) = query.get_mut(event.target) else {
+            continue;
+        };
+
+        // Blocking keeps the player planted
+        if combat_state.is_some_and(|combat| combat.is_blocking) {
+            continue;
+        }
+
+        knockback.apply(event.knockback, tuning.knockback_strength);
+
+        if let Some(mut attack_timer) = attack_timer {
+            if knockback.velocity.length() >= tuning.knockback_interrupt_speed {
+                attack_timer.0.reset();
+            }
+        }
+    }
+}
+
+/// Move knocked-back entities and decay their knockback.
+///
+/// The player is excluded from direct movement: `player_movement` feeds its
+/// knockback into the character controller so walls stop it. Enemies are
+/// kinematic bodies moved by transform, like in the AI systems; their
+/// collider is swept along the step so level geometry stops them instead of
+/// letting a hit shove them through a wall.
+fn update_knockback(
+    time: Res<Time>,
+    tuning: Res<CombatTuning>,
+    rapier_context: Query<&RapierContext>,
+    mut query: Query<(&mut Knockback, &mut Transform, Option<&Collider>), Without<Player>>,
+    mut player_query: Query<&mut Knockback, With<Player>>,
+) {
+    let delta = time.delta_secs();
+    let context = rapier_context.get_single().ok();
+
+    for (mut knockback, mut transform, collider) in query.iter_mut() {
+        if knockback.velocity == Vec3::ZERO {
+            continue;
+        }
+
+        let step = knockback.velocity * delta;
+        let blocked_at = context
+            .zip(collider)
+            .and_then(|(context, collider)| sweep_knockback(context, &transform, collider, step));
+
+        match blocked_at {
+            Some(time_of_impact) => {
+                // Stop at the wall; the rest of the shove is absorbed
+                transform.translation += step * time_of_impact;
+                knockback.velocity = Vec3::ZERO;
+            }
+            None => {
+                transform.translation += step;
+                knockback.decay(delta, tuning.knockback_decay);
+            }
+        }
+    }
+
+    for mut knockback in player_query.iter_mut() {
+        if knockback.velocity != Vec3::ZERO {
+            knockback.decay(delta, tuning.knockback_decay);
+        }
+    }
+}
+
+/// Sweep a knocked-back collider along `step` against level geometry.
+///
+/// Returns the fraction of the step that can be travelled before contact, or
+/// `None` if the path is clear. Colliders already touching a wall can still
+/// be pushed away from it.
+fn sweep_knockback(
+    context: &RapierContext,
+    transform: &Transform,
+    collider: &Collider,
+    step: Vec3,
+) -> Option<f32> {
+    // Rapier scales colliders by their entity's scale; the cast shape must match
+    let mut shape = collider.clone();
+    shape.set_scale(transform.scale, 8);
+
+    let options = ShapeCastOptions {
+        // Velocity is the whole step, so a time of impact of 1.0 is its end
+        max_time_of_impact: 1.0,
+        target_distance: 0.02,
+        stop_at_penetration: false,
+        ..default()
+    };
+    let filter = QueryFilter::only_fixed().exclude_sensors();
+
+    context
+        .cast_shape(transform.translation, transform.rotation, step, &shape, options, filter)
+        .map(|(_, hit)| hit.time_of_impact)
+}
+
+/// Filter for the player while still alive.
+type LivingPlayer = (With<Player>, Without<Dead>);
+
+/// Regenerate player health after a period without taking damage.
+///
+/// Disabled when `PlayerConfig::health_regen_rate` is zero.
+fn health_regen(
+    time: Res<Time>,
+    config: Res<PlayerConfig>,
+    mut dealt_events: EventReader<DamageDealtEvent>,
+    mut query: Query<(Entity, &mut Health, &mut HealthRegen), LivingPlayer>,
+) {
+    let Ok((player_entity, mut health, mut regen)) = query.get_single_mut() else {
+        dealt_events.clear();
+        return;
+    };
+
+    // Damage that landed on the player restarts the delay; hits absorbed by
+    // i-frames or god mode don't
+    if dealt_events.read().any(|event| event.target == player_entity) {
+        regen.regen_timer = config.health_regen_delay;
+        return;
+    }
+
+    if config.health_regen_rate <= 0.0 {
+        return;
+    }
+
+    if regen.regen_timer > 0.0 {
+        regen.regen_timer -= time.delta_secs();
+    } else {
+        health.heal(config.health_regen_rate * time.delta_secs());
+    }
 }
 
 /// Check for entity deaths.
+///
+/// `apply_damage` sends at most one `DeathEvent` per entity, so each killed
+/// enemy produces exactly one `EnemyKilledEvent`.
 fn check_deaths(
     mut commands: Commands,
     mut death_events: EventReader<DeathEvent>,
     player_query: Query<Entity, With<Player>>,
-    enemy_query: Query<Entity, With<Enemy>>,
+    enemy_query: Query<(&Transform, Option<&EnemyType>), With<Enemy>>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut killed_events: EventWriter<EnemyKilledEvent>,
 ) {
     for event in death_events.read() {
         if player_query.get(event.entity).is_ok() {
             info!("Player died! Transitioning to Game Over...");
             next_state.set(GameState::GameOver);
-        } else if enemy_query.get(event.entity).is_ok() {
-            // Skip enemies - handled by enemy death system with animation
+        } else if let Ok((transform, enemy_type)) = enemy_query.get(event.entity) {
+            // Despawn is handled by enemy death system with animation.
+            // Traps carry `Enemy` but aren't creatures, so they have no type.
+            if let Some(enemy_type) = enemy_type {
+                killed_events.send(EnemyKilledEvent {
+                    enemy: event.entity,
+                    enemy_type: enemy_type.0.clone(),
+                    killer: event.killed_by,
+                    position: transform.translation,
+                });
+            }
         } else {
             // Non-player, non-enemy entity died - despawn
             commands.entity(event.entity).despawn_recursive();
@@ -362,29 +932,381 @@ fn check_deaths(
     }
 }
 
-/// Update screen shake effect.
+/// Count enemies killed by the player.
+fn count_enemy_kills(
+    mut killed_events: EventReader<EnemyKilledEvent>,
+    player_query: Query<(), With<Player>>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    for event in killed_events.read() {
+        if event.killer.is_some_and(|killer| player_query.contains(killer)) {
+            run_stats.enemies_killed += 1;
+        }
+    }
+}
+
+/// Fraction of the shake offset applied to the camera position.
+const SHAKE_POSITION_SCALE: f32 = 0.25;
+
+/// Radians of rotation wobble per unit of shake offset.
+const SHAKE_ROTATION_SCALE: f32 = 0.1;
+
+/// Update screen shake and apply it to the camera.
+///
+/// The camera is rebuilt from its `CameraBaseTransform` every frame with the
+/// current (decaying) offset and the walking `HeadBob` on top, so the view
+/// returns exactly to its base once the shake ends and repeated hits never
+/// drift it.
 fn update_screen_shake(
     time: Res<Time>,
+    reduce_motion: Res<ReduceMotion>,
     mut screen_shake: ResMut<ScreenShake>,
-    camera_query: Query<&Transform, With<PlayerCamera>>,
+    mut camera_query: Query<
+        (&mut Transform, &CameraBaseTransform, Option<&HeadBob>),
+        With<PlayerCamera>,
+    >,
 ) {
+    // Reduce motion: cancel any pending shake
+    if reduce_motion.enabled {
+        screen_shake.timer = 0.0;
+    }
+
     let offset = screen_shake.update(time.delta_secs());
 
-    if let Ok(_transform) = camera_query.get_single() {
-        // Apply shake offset to camera
-        // Note: This is additive to the base position, so we need to
-        // store the original position or apply it differently
-        // For simplicity, we'll apply it as a rotation wobble
-        if offset != Vec3::ZERO {
-            let _shake_rotation =
-                Quat::from_euler(EulerRot::XYZ, offset.y * 0.1, offset.x * 0.1, 0.0);
-            // We need to preserve the existing pitch, so this is simplified
-            // In a full implementation, you'd separate shake from look rotation
-        }
+    let Ok((mut transform, base, head_bob)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let wobble = Quat::from_euler(
+        EulerRot::XYZ,
+        offset.y * SHAKE_ROTATION_SCALE,
+        offset.x * SHAKE_ROTATION_SCALE,
+        0.0,
+    );
+    transform.set_if_neq(Transform {
+        translation: base.0.translation
+            + offset * SHAKE_POSITION_SCALE
+            + head_bob.map_or(Vec3::ZERO, |bob| bob.offset),
+        rotation: base.0.rotation * wobble,
+        scale: base.0.scale,
+    });
+}
+
+/// Virtual time speed while hit stop is active.
+const HIT_STOP_TIME_SCALE: f32 = 0.0;
+
+/// Update hit stop and freeze virtual time while it's active.
+///
+/// Counts down in real time, since virtual time is what it stops. Everything
+/// driven by `Time` (AI, animation, physics, attack timers) holds still for
+/// the duration of the freeze.
+fn update_hit_stop(
+    real_time: Res<Time<Real>>,
+    reduce_motion: Res<ReduceMotion>,
+    mut hit_stop: ResMut<HitStop>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    // Reduce motion: skip the freeze entirely
+    if reduce_motion.enabled {
+        hit_stop.timer = 0.0;
+    }
+
+    hit_stop.update(real_time.delta_secs());
+
+    let speed = if hit_stop.is_active() {
+        HIT_STOP_TIME_SCALE
+    } else {
+        1.0
+    };
+    if virtual_time.relative_speed() != speed {
+        virtual_time.set_relative_speed(speed);
     }
 }
 
-/// Update hit stop effect.
-fn update_hit_stop(time: Res<Time>, mut hit_stop: ResMut<HitStop>) {
-    hit_stop.update(time.delta_secs());
+/// Cancel any hit stop and restore normal time when leaving gameplay.
+fn reset_hit_stop(mut hit_stop: ResMut<HitStop>, mut virtual_time: ResMut<Time<Virtual>>) {
+    hit_stop.timer = 0.0;
+    virtual_time.set_relative_speed(1.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+    use bevy_rapier3d::rapier::prelude::{ColliderBuilder, Vector};
+    use std::time::Duration;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn weapon_with_crit(crit_chance: f32, crit_multiplier: f32) -> Weapon {
+        Weapon {
+            crit_chance,
+            crit_multiplier,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn seeded_crit_rolls_follow_the_seed() {
+        let weapon = weapon_with_crit(0.5, 2.0);
+        let mut rng = CombatRng::seeded(7);
+        let mut expected = StdRng::seed_from_u64(7);
+
+        let rolls: Vec<bool> = (0..32).map(|_| roll_crit(&mut rng, &weapon).0).collect();
+        let expected_rolls: Vec<bool> = (0..32).map(|_| expected.gen::<f32>() < 0.5).collect();
+        assert_eq!(rolls, expected_rolls);
+
+        // A fair coin over 32 swings lands both ways
+        assert!(rolls.contains(&true));
+        assert!(rolls.contains(&false));
+
+        let mut replay = CombatRng::seeded(7);
+        let replayed: Vec<bool> = (0..32).map(|_| roll_crit(&mut replay, &weapon).0).collect();
+        assert_eq!(rolls, replayed);
+    }
+
+    #[test]
+    fn crit_applies_the_weapon_multiplier() {
+        let mut rng = CombatRng::seeded(1);
+
+        let always = weapon_with_crit(1.0, 1.75);
+        for _ in 0..16 {
+            assert_eq!(roll_crit(&mut rng, &always), (true, 1.75));
+        }
+
+        let never = weapon_with_crit(0.0, 1.75);
+        for _ in 0..16 {
+            assert_eq!(roll_crit(&mut rng, &never), (false, 1.0));
+        }
+    }
+
+    #[test]
+    fn sneak_attack_only_on_unaware_enemies() {
+        assert!(is_sneak_attack(Some(&AiState::Idle)));
+        assert!(!is_sneak_attack(Some(&AiState::Chasing)));
+        assert!(!is_sneak_attack(Some(&AiState::Attacking)));
+        assert!(!is_sneak_attack(None));
+        assert!(CombatTuning::default().sneak_attack_multiplier > 1.0);
+    }
+
+    /// A physics world with one fixed wall whose face is at x = 2.
+    fn context_with_wall() -> RapierContext {
+        let mut context = RapierContext::default();
+        let wall = ColliderBuilder::cuboid(0.5, 2.0, 5.0)
+            .translation(Vector::new(2.5, 0.0, 0.0))
+            .user_data(Entity::from_raw(1).to_bits() as u128)
+            .build();
+        context.colliders.insert(wall);
+        context.query_pipeline.update(&context.colliders);
+        context
+    }
+
+    fn enemy_collider() -> Collider {
+        Collider::capsule_y(0.5, 0.4)
+    }
+
+    #[test]
+    fn knockback_stops_at_wall() {
+        let context = context_with_wall();
+        let transform = Transform::default();
+        let step = Vec3::new(3.0, 0.0, 0.0);
+
+        let time_of_impact = sweep_knockback(&context, &transform, &enemy_collider(), step)
+            .expect("shove into the wall should be blocked");
+        assert!(time_of_impact < 1.0);
+
+        // The capsule ends up against the wall face, not through it
+        let stopped_at = transform.translation + step * time_of_impact;
+        assert!(stopped_at.x + 0.4 <= 2.0);
+        assert!(stopped_at.x > 1.0);
+    }
+
+    #[test]
+    fn knockback_pushes_away_from_touching_wall() {
+        let context = context_with_wall();
+        let transform = Transform::from_xyz(1.6, 0.0, 0.0);
+
+        let away = Vec3::new(-2.0, 0.0, 0.0);
+        assert_eq!(sweep_knockback(&context, &transform, &enemy_collider(), away), None);
+    }
+
+    #[test]
+    fn knockback_along_open_floor_is_not_blocked() {
+        let context = context_with_wall();
+        let transform = Transform::default();
+
+        let along_wall = Vec3::new(0.0, 0.0, 3.0);
+        assert_eq!(sweep_knockback(&context, &transform, &enemy_collider(), along_wall), None);
+    }
+
+    /// An app running only `apply_damage`, with a player and an enemy.
+    fn damage_app() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_event::<DamageEvent>()
+            .add_event::<DamageDealtEvent>()
+            .add_event::<DeathEvent>()
+            .insert_resource(CombatTuning::default())
+            .init_resource::<RunStats>()
+            .add_systems(Update, apply_damage);
+        #[cfg(feature = "cheats")]
+        app.init_resource::<Cheats>();
+
+        let player = app
+            .world_mut()
+            .spawn((Player, Health::new(100.0), CombatState::default()))
+            .id();
+        let enemy = app
+            .world_mut()
+            .spawn((Health::new(100.0), CombatState::default()))
+            .id();
+        (app, player, enemy)
+    }
+
+    fn hit(target: Entity, source: Entity, amount: f32) -> DamageEvent {
+        DamageEvent {
+            target,
+            source,
+            amount,
+            element: Element::Physical,
+            knockback: Vec3::ZERO,
+            was_crit: false,
+            melee: true,
+        }
+    }
+
+    #[test]
+    fn second_quick_hit_on_player_is_ignored() {
+        let (mut app, player, enemy) = damage_app();
+
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.update();
+
+        let world = app.world();
+        assert_eq!(world.get::<Health>(player).unwrap().current, 90.0);
+        let i_frames = world.get::<CombatState>(player).unwrap().i_frames;
+        assert_eq!(i_frames, CombatTuning::default().player_i_frames);
+    }
+
+    #[test]
+    fn player_is_hit_again_once_i_frames_end() {
+        let (mut app, player, enemy) = damage_app();
+
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.update();
+        app.world_mut().get_mut::<CombatState>(player).unwrap().i_frames = 0.0;
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(player).unwrap().current, 80.0);
+    }
+
+    #[test]
+    fn enemies_get_no_i_frames() {
+        let (mut app, player, enemy) = damage_app();
+
+        app.world_mut().send_event(hit(enemy, player, 10.0));
+        app.world_mut().send_event(hit(enemy, player, 10.0));
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(enemy).unwrap().current, 80.0);
+    }
+
+    #[test]
+    fn run_stats_count_health_removed_not_overkill() {
+        let (mut app, player, enemy) = damage_app();
+
+        app.world_mut().send_event(hit(enemy, player, 150.0));
+        app.world_mut().send_event(hit(player, enemy, 30.0));
+        app.update();
+
+        let run_stats = app.world().resource::<RunStats>();
+        assert_eq!(run_stats.damage_dealt, 100.0);
+        assert_eq!(run_stats.damage_taken, 30.0);
+    }
+
+    #[test]
+    fn only_landed_hits_restart_health_regen() {
+        let (mut app, player, enemy) = damage_app();
+        app.add_plugins(TimePlugin)
+            .init_resource::<PlayerConfig>()
+            .add_systems(Update, health_regen.after(apply_damage));
+        app.world_mut().entity_mut(player).insert(HealthRegen::default());
+        app.world_mut().get_mut::<CombatState>(player).unwrap().i_frames = 1.0;
+
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.update();
+        assert_eq!(app.world().get::<HealthRegen>(player).unwrap().regen_timer, 0.0);
+
+        app.world_mut().get_mut::<CombatState>(player).unwrap().i_frames = 0.0;
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.update();
+        let delay = PlayerConfig::default().health_regen_delay;
+        assert_eq!(app.world().get::<HealthRegen>(player).unwrap().regen_timer, delay);
+    }
+
+    #[test]
+    fn trap_damage_during_i_frames_does_not_hold_hit_stop() {
+        let (mut app, player, enemy) = damage_app();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(20)))
+            .init_resource::<ScreenShake>()
+            .init_resource::<HitStop>()
+            .init_resource::<ReduceMotion>()
+            .add_systems(
+                Update,
+                (process_attack_hits, update_hit_stop).chain().after(apply_damage),
+            );
+        let trap = app.world_mut().spawn_empty().id();
+
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.update();
+        assert!(app.world().resource::<HitStop>().is_active());
+        assert_eq!(app.world().resource::<Time<Virtual>>().relative_speed(), 0.0);
+
+        // A damage floor with no interval hits every frame, frozen or not
+        for _ in 0..20 {
+            app.world_mut().send_event(DamageEvent {
+                melee: false,
+                ..hit(player, trap, 5.0)
+            });
+            app.update();
+        }
+
+        assert!(!app.world().resource::<HitStop>().is_active());
+        assert_eq!(app.world().resource::<Time<Virtual>>().relative_speed(), 1.0);
+        assert_eq!(app.world().get::<Health>(player).unwrap().current, 90.0);
+    }
+
+    #[test]
+    fn absorbed_hit_grants_no_i_frames() {
+        let (mut app, player, enemy) = damage_app();
+
+        app.world_mut().send_event(hit(player, enemy, 0.0));
+        app.update();
+        assert_eq!(app.world().get::<CombatState>(player).unwrap().i_frames, 0.0);
+
+        // The next real hit still lands
+        app.world_mut().send_event(hit(player, enemy, 10.0));
+        app.update();
+        assert_eq!(app.world().get::<Health>(player).unwrap().current, 90.0);
+    }
+
+    #[test]
+    fn enemy_hit_by_trap_does_not_shake_camera() {
+        let (mut app, _, enemy) = damage_app();
+        app.init_resource::<ScreenShake>()
+            .init_resource::<HitStop>()
+            .add_systems(Update, process_attack_hits.after(apply_damage));
+        let trap = app.world_mut().spawn_empty().id();
+
+        app.world_mut().send_event(DamageEvent {
+            melee: false,
+            ..hit(enemy, trap, 10.0)
+        });
+        app.update();
+
+        assert_eq!(app.world().resource::<ScreenShake>().timer, 0.0);
+        assert_eq!(app.world().get::<Health>(enemy).unwrap().current, 90.0);
+    }
 }
@@ -0,0 +1,132 @@
+//! Combat tuning loaded from external RON file.
+//!
+//! Collects the numbers that define how combat feels so they can be balanced
+//! without recompiling.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fs;
+
+/// Combat tuning loaded from assets/data/combat/combat_tuning.ron.
+///
+/// Missing fields fall back to their defaults.
+#[derive(Resource, Clone, Deserialize)]
+#[serde(default)]
+pub struct CombatTuning {
+    // Hit feedback
+    pub hit_shake_intensity: f32,
+    pub hit_shake_duration: f32,
+    pub hit_stop_duration: f32,
+    // Critical hits
+    pub crit_shake_intensity: f32,
+    pub crit_shake_duration: f32,
+    pub crit_hit_stop_duration: f32,
+    // Sneak attacks
+    /// Damage multiplier when hitting an enemy that hasn't noticed the player
+    pub sneak_attack_multiplier: f32,
+    pub sneak_shake_intensity: f32,
+    pub sneak_shake_duration: f32,
+    /// Arc in degrees behind an enemy within which hits count as backstabs
+    pub backstab_arc: f32,
+    // Heavy attacks
+    /// Seconds the attack button must be held for a heavy attack
+    pub heavy_charge_time: f32,
+    /// Damage multiplier of heavy attacks
+    pub heavy_damage_multiplier: f32,
+    /// Stamina cost multiplier of heavy attacks
+    pub heavy_stamina_multiplier: f32,
+    /// Hit sphere radius multiplier of heavy attacks
+    pub heavy_reach_multiplier: f32,
+    // Combos
+    /// Seconds after a light swing ends in which another attack continues the combo
+    pub combo_window: f32,
+    /// Damage multiplier of each combo step; the combo restarts after the last
+    pub combo_damage_multipliers: Vec<f32>,
+    // Blocking
+    /// Stamina drained per second while blocking
+    pub block_stamina_drain: f32,
+    // Stamina
+    /// Fraction of maximum stamina an exhausted entity must regain to recover
+    pub stamina_recovery_threshold: f32,
+    // Damage
+    /// Seconds of invincibility after the player takes damage
+    pub player_i_frames: f32,
+    // Knockback
+    /// Impulse of the player's attacks
+    pub player_attack_knockback: f32,
+    /// Impulse of enemy attacks
+    pub enemy_attack_knockback: f32,
+    /// Multiplier converting `DamageEvent::knockback` into an initial velocity
+    pub knockback_strength: f32,
+    /// Exponential knockback decay rate per second
+    pub knockback_decay: f32,
+    /// Knockback speed that makes an enemy restart its attack wind-up
+    pub knockback_interrupt_speed: f32,
+}
+
+impl Default for CombatTuning {
+    fn default() -> Self {
+        Self {
+            hit_shake_intensity: 0.1,
+            hit_shake_duration: 0.15,
+            hit_stop_duration: 0.05,
+            crit_shake_intensity: 0.2,
+            crit_shake_duration: 0.2,
+            crit_hit_stop_duration: 0.12,
+            sneak_attack_multiplier: 2.5,
+            sneak_shake_intensity: 0.25,
+            sneak_shake_duration: 0.25,
+            backstab_arc: 90.0,
+            heavy_charge_time: 0.5,
+            heavy_damage_multiplier: 2.0,
+            heavy_stamina_multiplier: 2.0,
+            heavy_reach_multiplier: 1.3,
+            combo_window: 0.5,
+            combo_damage_multipliers: vec![1.0, 1.15, 1.4],
+            block_stamina_drain: 5.0,
+            stamina_recovery_threshold: 0.25,
+            player_i_frames: 0.5,
+            player_attack_knockback: 2.0,
+            enemy_attack_knockback: 2.0,
+            knockback_strength: 3.0,
+            knockback_decay: 8.0,
+            knockback_interrupt_speed: 4.0,
+        }
+    }
+}
+
+impl CombatTuning {
+    /// Number of steps in a light attack combo (at least 1).
+    pub fn combo_steps(&self) -> u32 {
+        self.combo_damage_multipliers.len().max(1) as u32
+    }
+
+    /// Damage multiplier of combo step `index`.
+    pub fn combo_damage_multiplier(&self, index: u32) -> f32 {
+        self.combo_damage_multipliers
+            .get(index as usize)
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Load combat tuning from RON file.
+    pub fn load() -> Self {
+        let path = "assets/data/combat/combat_tuning.ron";
+        match fs::read_to_string(path) {
+            Ok(contents) => match ron::from_str(&contents) {
+                Ok(tuning) => {
+                    info!("Loaded combat tuning from {}", path);
+                    tuning
+                }
+                Err(e) => {
+                    error!("Failed to parse {}: {}. Using defaults.", path, e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                warn!("Could not read {}: {}. Using defaults.", path, e);
+                Self::default()
+            }
+        }
+    }
+}
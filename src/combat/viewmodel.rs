@@ -2,26 +2,121 @@
 //!
 //! The viewmodel is spawned as a child of the player's camera, eliminating
 //! the one-frame trailing issue that occurs with manual position tracking.
+//! It lives entirely on the viewmodel render layer (see `rendering::layers`).
+//! Optional first-person arms are parented to the viewmodel so they follow
+//! the weapon's offsets, swings and idle bob.
+//!
+//! In single-camera mode (`VisualConfig::single_camera_viewmodel`) the weapon
+//! shares the world's depth buffer. It is shrunk toward the camera by
+//! `SINGLE_CAMERA_SCALE`, which looks the same on screen but keeps it inside
+//! the player's collider, so it doesn't clip into walls.
 
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use bevy::scene::SceneRoot;
 
 use super::components::*;
-use crate::core::{GameState, SmoothTransform};
-use crate::player::{Player, PlayerCamera};
+use super::tuning::CombatTuning;
+use crate::core::{GameState, ReduceMotion, SmoothTransform};
+use crate::player::{Player, PlayerCamera, PlayerConfig, WeaponCamera, WeaponLight};
+use crate::rendering::{viewmodel_layers, VisualConfig};
+
+/// Offset of the arms scene from the viewmodel origin (the weapon grip).
+const ARMS_OFFSET: Vec3 = Vec3::new(0.0, -0.05, 0.15);
+
+/// Scale of the arms scene.
+const ARMS_SCALE: f32 = 0.15;
+
+/// Viewmodel scale and distance factor in single-camera mode.
+const SINGLE_CAMERA_SCALE: f32 = 0.25;
+
+/// Viewmodel pose offset when a heavy attack is fully charged.
+const CHARGE_OFFSET: Vec3 = Vec3::new(0.05, -0.04, 0.15);
+
+/// Viewmodel tilt (pitch, roll) in radians when fully charged.
+const CHARGE_TILT: Vec2 = Vec2::new(0.45, -0.25);
+
+/// Charge progress toward a heavy attack (0.0-1.0).
+fn charge_fraction(combat: &CombatState, tuning: &CombatTuning) -> f32 {
+    if !combat.is_charging {
+        return 0.0;
+    }
+    if tuning.heavy_charge_time <= 0.0 {
+        return 1.0;
+    }
+    (combat.charge_time / tuning.heavy_charge_time).clamp(0.0, 1.0)
+}
 
 /// Marker for the weapon viewmodel entity.
 #[derive(Component)]
 pub struct WeaponViewmodel;
 
+/// Marker for the first-person arms holding the weapon.
+#[derive(Component)]
+pub struct ViewmodelArms;
+
+/// Viewmodel pose offset and rotation at `progress` (0.0-1.0) through a swing.
+///
+/// Offsets are relative to `PlayerConfig::weapon_offset`; rotations replace
+/// the idle rotation. `Slash` holds one pose for the whole swing.
+fn swing_pose(profile: SwingProfile, progress: f32) -> (Vec3, Quat) {
+    match profile {
+        SwingProfile::Slash => (
+            Vec3::new(-0.1, 0.1, -0.2),
+            Quat::from_euler(EulerRot::XYZ, -0.8, -0.3, 0.0),
+        ),
+        SwingProfile::Thrust => {
+            // Draw back for the first third, then jab forward
+            let drawn_back = Vec3::new(0.0, 0.0, 0.15);
+            let extended = Vec3::new(-0.05, 0.05, -0.45);
+            let offset = if progress < 0.3 {
+                Vec3::ZERO.lerp(drawn_back, progress / 0.3)
+            } else {
+                drawn_back.lerp(extended, ((progress - 0.3) / 0.7).min(1.0))
+            };
+            (offset, Quat::from_euler(EulerRot::XYZ, -1.4, 0.0, 0.0))
+        }
+        SwingProfile::WideArc => {
+            // Sweep across the view from right to left
+            let t = progress.clamp(0.0, 1.0);
+            let offset = Vec3::new(0.25, 0.1, -0.1).lerp(Vec3::new(-0.35, 0.0, -0.15), t);
+            let yaw = 0.9 - 1.8 * t;
+            (offset, Quat::from_euler(EulerRot::XYZ, -0.4, yaw, -1.2))
+        }
+    }
+}
+
+/// Swing pose for a combo step. Odd steps are mirrored left-to-right, so
+/// chained attacks alternate swing direction.
+fn combo_swing_pose(profile: SwingProfile, progress: f32, combo_index: u32) -> (Vec3, Quat) {
+    let (offset, rotation) = swing_pose(profile, progress);
+    if combo_index.is_multiple_of(2) {
+        return (offset, rotation);
+    }
+    (
+        Vec3::new(-offset.x, offset.y, offset.z),
+        Quat::from_xyzw(rotation.x, -rotation.y, -rotation.z, rotation.w),
+    )
+}
+
+/// Scale of the viewmodel relative to the camera.
+fn viewmodel_scale(visual_config: &VisualConfig) -> f32 {
+    if visual_config.single_camera_viewmodel {
+        SINGLE_CAMERA_SCALE
+    } else {
+        1.0
+    }
+}
+
 /// Setup weapon viewmodel systems.
 pub fn setup_viewmodel_systems(app: &mut App) {
     app.add_systems(
         Update,
         (
+            refresh_viewmodel_on_equip,
             spawn_viewmodel,
             propagate_viewmodel_render_layers,
+            check_viewmodel_layers,
             update_viewmodel_position,
             update_viewmodel_animation,
         )
@@ -30,13 +125,32 @@ pub fn setup_viewmodel_systems(app: &mut App) {
     );
 }
 
+/// Remove the viewmodel when the player's weapon changes.
+///
+/// `spawn_viewmodel` then rebuilds it with the new weapon's model.
+fn refresh_viewmodel_on_equip(
+    mut commands: Commands,
+    player_query: Query<(), (With<Player>, Changed<Weapon>)>,
+    viewmodel_query: Query<Entity, With<WeaponViewmodel>>,
+) {
+    if player_query.is_empty() {
+        return;
+    }
+    for entity in viewmodel_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 /// Spawn the weapon viewmodel as a child of the camera.
 ///
 /// This system checks if a viewmodel already exists - if not, it spawns one
-/// as a child of the player's camera using the weapon's model_path.
+/// as a child of the player's camera using the weapon's model_path. Arms are
+/// added alongside the weapon when `PlayerConfig::arms_model_path` is set.
 fn spawn_viewmodel(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    config: Res<PlayerConfig>,
+    visual_config: Res<VisualConfig>,
     camera_query: Query<Entity, With<PlayerCamera>>,
     player_query: Query<&Weapon, With<Player>>,
     viewmodel_query: Query<&WeaponViewmodel>,
@@ -59,32 +173,46 @@ fn spawn_viewmodel(
         return;
     }
 
+    let scale = viewmodel_scale(&visual_config);
+
     // Spawn viewmodel as child of camera
-    // Use RenderLayers::layer(1) so weapon renders on weapon camera only
+    // Viewmodel layer so the weapon renders on the weapon camera only
     commands.entity(camera_entity).with_children(|parent| {
         parent
             .spawn((
                 WeaponViewmodel,
-                Transform::from_xyz(0.3, -0.2, -0.5),
+                Transform::from_translation(config.weapon_offset * scale)
+                    .with_scale(Vec3::splat(scale)),
                 SmoothTransform::new(15.0, 12.0),
                 Visibility::default(),
-                RenderLayers::layer(1),
+                viewmodel_layers(),
             ))
             .with_children(|weapon_parent| {
                 weapon_parent.spawn((
                     SceneRoot(asset_server.load(&weapon.model_path)),
                     Transform::from_scale(Vec3::splat(0.15)),
-                    RenderLayers::layer(1),
+                    viewmodel_layers(),
                 ));
+
+                if let Some(arms_path) = &config.arms_model_path {
+                    weapon_parent.spawn((
+                        ViewmodelArms,
+                        SceneRoot(asset_server.load(arms_path)),
+                        Transform::from_translation(ARMS_OFFSET)
+                            .with_scale(Vec3::splat(ARMS_SCALE)),
+                        viewmodel_layers(),
+                    ));
+                }
             });
     });
 }
 
 /// Propagate RenderLayers to all descendants of the viewmodel.
 ///
-/// When a glTF scene loads, it creates child entities (meshes, etc.) that don't
-/// inherit RenderLayers. This system adds layer 1 to all descendants so they
-/// render on the weapon camera.
+/// When a glTF scene (weapon or arms) loads, it creates child entities
+/// (meshes, lights, etc.) that don't inherit RenderLayers. This system puts
+/// all descendants on the viewmodel layer only, so they render on the weapon
+/// camera and any lights in the scene can't reach world geometry.
 fn propagate_viewmodel_render_layers(
     mut commands: Commands,
     viewmodel_query: Query<Entity, With<WeaponViewmodel>>,
@@ -98,9 +226,9 @@ fn propagate_viewmodel_render_layers(
     // Recursively collect all descendants
     let mut to_process = vec![viewmodel_entity];
     while let Some(entity) = to_process.pop() {
-        // Add RenderLayers if missing
-        if render_layers_query.get(entity).is_err() {
-            commands.entity(entity).insert(RenderLayers::layer(1));
+        // Add RenderLayers if missing or not exactly the viewmodel layer
+        if render_layers_query.get(entity).ok() != Some(&viewmodel_layers()) {
+            commands.entity(entity).insert(viewmodel_layers());
         }
 
         // Queue children for processing
@@ -110,34 +238,71 @@ fn propagate_viewmodel_render_layers(
     }
 }
 
+/// Any of the entities rendered on the viewmodel layer.
+type ViewmodelLayerMember = Or<(With<WeaponViewmodel>, With<WeaponCamera>, With<WeaponLight>)>;
+
+/// One of the viewmodel layer entities was just spawned.
+type ViewmodelLayerAdded = Or<(Added<WeaponViewmodel>, Added<WeaponCamera>, Added<WeaponLight>)>;
+
+/// Warn if the viewmodel, weapon camera and weapon light don't share the viewmodel layer.
+///
+/// A mismatch means the weapon light leaks onto world geometry or the weapon
+/// isn't lit by its own light. Checked whenever one of them is (re)spawned.
+fn check_viewmodel_layers(
+    added_query: Query<(), ViewmodelLayerAdded>,
+    layered_query: Query<(Entity, Option<&RenderLayers>), ViewmodelLayerMember>,
+) {
+    if added_query.is_empty() {
+        return;
+    }
+
+    let expected = viewmodel_layers();
+    for (entity, layers) in layered_query.iter() {
+        if layers != Some(&expected) {
+            warn!(
+                "{:?} is on render layers {:?}, expected only the viewmodel layer",
+                entity, layers
+            );
+        }
+    }
+}
+
 /// Update viewmodel position based on combat state.
 ///
 /// Since the viewmodel is parented to the camera, we only need to adjust
-/// the local offset for combat states (blocking, attacking).
+/// the local offset for combat states (blocking, swinging). Combat poses are
+/// relative to `PlayerConfig::weapon_offset`, so they follow it when tuned.
 fn update_viewmodel_position(
-    combat_query: Query<&CombatState, With<Player>>,
+    config: Res<PlayerConfig>,
+    visual_config: Res<VisualConfig>,
+    tuning: Res<CombatTuning>,
+    combat_query: Query<(&CombatState, &Weapon, Option<&AttackSwing>), With<Player>>,
     mut viewmodel_query: Query<&mut SmoothTransform, With<WeaponViewmodel>>,
 ) {
-    let Ok(combat) = combat_query.get_single() else {
+    let Ok((combat, weapon, swing)) = combat_query.get_single() else {
         return;
     };
     let Ok(mut smooth) = viewmodel_query.get_single_mut() else {
         return;
     };
 
-    // Base position (local to camera)
-    let offset = if combat.is_blocking {
+    // Pose offset from the resting position (local to camera)
+    let pose = if combat.is_blocking {
         // Raise weapon for blocking stance
-        Vec3::new(0.1, 0.0, -0.4)
-    } else if combat.is_attacking {
-        // Thrust forward during attack
-        Vec3::new(0.2, -0.1, -0.7)
+        Vec3::new(-0.2, 0.2, 0.1)
+    } else if let Some(swing) = swing {
+        // Follow the weapon's swing motion
+        combo_swing_pose(weapon.swing_profile, swing.progress(), combat.combo_index).0
+    } else if combat.is_charging {
+        // Pull back while winding up a heavy attack
+        CHARGE_OFFSET * charge_fraction(combat, &tuning)
     } else {
         // Default idle position
-        Vec3::new(0.3, -0.2, -0.5)
+        Vec3::ZERO
     };
 
-    smooth.target_translation = Some(offset);
+    let scale = viewmodel_scale(&visual_config);
+    smooth.target_translation = Some((config.weapon_offset + pose) * scale);
 }
 
 /// Animate viewmodel based on combat state.
@@ -146,10 +311,12 @@ fn update_viewmodel_position(
 /// additively to the current transform.
 fn update_viewmodel_animation(
     time: Res<Time>,
-    combat_query: Query<&CombatState, With<Player>>,
+    reduce_motion: Res<ReduceMotion>,
+    tuning: Res<CombatTuning>,
+    combat_query: Query<(&CombatState, &Weapon, Option<&AttackSwing>), With<Player>>,
     mut viewmodel_query: Query<(&mut Transform, &mut SmoothTransform), With<WeaponViewmodel>>,
 ) {
-    let Ok(combat) = combat_query.get_single() else {
+    let Ok((combat, weapon, swing)) = combat_query.get_single() else {
         return;
     };
     let Ok((mut transform, mut smooth)) = viewmodel_query.get_single_mut() else {
@@ -160,9 +327,13 @@ fn update_viewmodel_animation(
     let base_rotation = if combat.is_blocking {
         // Horizontal blocking position
         Quat::from_euler(EulerRot::XYZ, -0.3, 0.0, 1.2)
-    } else if combat.is_attacking {
-        // Swing forward
-        Quat::from_euler(EulerRot::XYZ, -0.8, -0.3, 0.0)
+    } else if let Some(swing) = swing {
+        // Follow the weapon's swing motion
+        combo_swing_pose(weapon.swing_profile, swing.progress(), combat.combo_index).1
+    } else if combat.is_charging {
+        // Cock the weapon back while charging
+        let tilt = CHARGE_TILT * charge_fraction(combat, &tuning);
+        Quat::from_euler(EulerRot::XYZ, tilt.x, 0.0, tilt.y)
     } else {
         // Idle base rotation (identity)
         Quat::IDENTITY
@@ -170,8 +341,8 @@ fn update_viewmodel_animation(
 
     smooth.target_rotation = Some(base_rotation);
 
-    // Apply idle bob additively (only when not in combat state)
-    if !combat.is_blocking && !combat.is_attacking {
+    // Apply idle bob additively (only when not in combat state or reducing motion)
+    if !combat.is_blocking && !combat.is_charging && swing.is_none() && !reduce_motion.enabled {
         let idle_bob = (time.elapsed_secs() * 2.0).sin() * 0.005;
         let idle_sway = (time.elapsed_secs() * 1.5).cos() * 0.003;
         let idle_rotation = Quat::from_euler(EulerRot::XYZ, idle_bob, idle_sway, 0.0);
@@ -0,0 +1,260 @@
+//! Data-driven weapon definitions.
+//!
+//! Weapons are loaded from assets/data/weapons/ into the `WeaponRegistry`,
+//! keyed by file stem (e.g. `short_sword`). Pickups and equip events refer
+//! to weapons by that id, and `Weapon` components are built from the
+//! definition when equipped.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::components::{SwingProfile, Weapon};
+use super::plugin::create_starter_weapon;
+use crate::core::{Element, GameState};
+
+/// Id of the weapon the player starts with.
+pub const STARTER_WEAPON: &str = "short_sword";
+
+fn default_swing_duration() -> f32 {
+    0.25
+}
+
+fn default_hit_frame() -> f32 {
+    0.5
+}
+
+fn default_block_arc() -> f32 {
+    120.0
+}
+
+fn default_backstab_multiplier() -> f32 {
+    1.5
+}
+
+fn default_crit_multiplier() -> f32 {
+    1.5
+}
+
+/// Weapon definition loaded from RON file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct WeaponDefinition {
+    pub name: String,
+    pub damage: f32,
+    #[serde(default)]
+    pub element: Element,
+    /// Attack range in units
+    pub reach: f32,
+    /// Damage reduction when blocking (0.0 to 1.0)
+    pub block_efficiency: f32,
+    /// Frontal arc in degrees within which blocking works
+    #[serde(default = "default_block_arc")]
+    pub block_arc: f32,
+    pub stamina_cost: f32,
+    /// Attack cooldown in seconds
+    pub cooldown: f32,
+    #[serde(default = "default_swing_duration")]
+    pub swing_duration: f32,
+    #[serde(default = "default_hit_frame")]
+    pub hit_frame: f32,
+    #[serde(default)]
+    pub swing_profile: SwingProfile,
+    /// Fraction of damage dealt that heals the wielder
+    #[serde(default)]
+    pub lifesteal: f32,
+    /// Damage multiplier when hitting an enemy from behind
+    #[serde(default = "default_backstab_multiplier")]
+    pub backstab_multiplier: f32,
+    /// Chance (0.0 to 1.0) that a swing is a critical hit
+    #[serde(default)]
+    pub crit_chance: f32,
+    /// Damage multiplier of critical hits
+    #[serde(default = "default_crit_multiplier")]
+    pub crit_multiplier: f32,
+    /// Path to the .glb model file (empty = no viewmodel)
+    #[serde(default)]
+    pub model_path: String,
+}
+
+impl WeaponDefinition {
+    /// Convert to a Weapon component, remembering the registry id it has.
+    pub fn to_weapon(&self, id: &str) -> Weapon {
+        Weapon {
+            id: Some(id.to_string()),
+            name: self.name.clone(),
+            base_damage: self.damage,
+            element: self.element,
+            reach: self.reach,
+            block_efficiency: self.block_efficiency,
+            block_arc: self.block_arc,
+            stamina_cost: self.stamina_cost,
+            attack_cooldown: self.cooldown,
+            swing_duration: self.swing_duration,
+            hit_frame: self.hit_frame,
+            swing_profile: self.swing_profile,
+            lifesteal: self.lifesteal,
+            backstab_multiplier: self.backstab_multiplier,
+            crit_chance: self.crit_chance,
+            crit_multiplier: self.crit_multiplier,
+            model_path: self.model_path.clone(),
+        }
+    }
+}
+
+/// Resource holding all loaded weapon definitions.
+#[derive(Resource, Default)]
+pub struct WeaponRegistry {
+    pub definitions: HashMap<String, WeaponDefinition>,
+}
+
+impl WeaponRegistry {
+    /// Get a weapon definition by id.
+    pub fn get(&self, id: &str) -> Option<&WeaponDefinition> {
+        self.definitions.get(id)
+    }
+
+    /// Build a Weapon component for the weapon with this id.
+    pub fn create_weapon(&self, id: &str) -> Option<Weapon> {
+        self.get(id).map(|definition| definition.to_weapon(id))
+    }
+
+    /// The player's starting weapon, falling back to the built-in short
+    /// sword if its definition is missing.
+    pub fn starter_weapon(&self) -> Weapon {
+        self.create_weapon(STARTER_WEAPON).unwrap_or_else(create_starter_weapon)
+    }
+
+    /// Display name of a weapon, or its id if it isn't defined.
+    pub fn display_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.get(id).map_or(id, |definition| definition.name.as_str())
+    }
+}
+
+/// Sent to equip a weapon by id, replacing the entity's current weapon.
+#[derive(Event)]
+pub struct EquipWeaponEvent {
+    pub entity: Entity,
+    pub weapon_id: String,
+}
+
+/// Set up weapon loading and equipping.
+pub fn setup_weapon_systems(app: &mut App) {
+    app.init_resource::<WeaponRegistry>()
+        .add_event::<EquipWeaponEvent>()
+        .add_systems(Startup, load_weapon_definitions)
+        .add_systems(Update, equip_weapons.run_if(in_state(GameState::InGame)));
+}
+
+/// Load all weapon definitions from the assets/data/weapons/ directory.
+fn load_weapon_definitions(mut registry: ResMut<WeaponRegistry>) {
+    let weapons_dir = Path::new("assets/data/weapons");
+
+    if !weapons_dir.exists() {
+        warn!("Weapon definitions directory not found: {:?}", weapons_dir);
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(weapons_dir) else {
+        warn!("Failed to read weapon definitions directory");
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.extension().is_some_and(|ext| ext == "ron") {
+            let weapon_id = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown")
+                .to_string();
+
+            match fs::read_to_string(&path) {
+                Ok(contents) => match ron::from_str::<WeaponDefinition>(&contents) {
+                    Ok(definition) => {
+                        info!("Loaded weapon definition: {} ({})", definition.name, weapon_id);
+                        registry.definitions.insert(weapon_id, definition);
+                    }
+                    Err(e) => {
+                        error!("Failed to parse weapon definition {:?}: {}", path, e);
+                    }
+                },
+                Err(e) => {
+                    error!("Failed to read weapon definition {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    info!("Loaded {} weapon definitions", registry.definitions.len());
+}
+
+/// Replace an entity's weapon when it equips one.
+fn equip_weapons(
+    mut commands: Commands,
+    mut equip_events: EventReader<EquipWeaponEvent>,
+    registry: Res<WeaponRegistry>,
+) {
+    for event in equip_events.read() {
+        let Some(weapon) = registry.create_weapon(&event.weapon_id) else {
+            warn!("Cannot equip unknown weapon '{}'", event.weapon_id);
+            continue;
+        };
+        info!("Equipped {}", weapon.name);
+        commands.entity(event.entity).insert(weapon);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_weapons_parse() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/data/weapons");
+        let mut parsed = 0;
+
+        for entry in fs::read_dir(&dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "ron") {
+                continue;
+            }
+            let contents = fs::read_to_string(&path).unwrap();
+            let definition = ron::from_str::<WeaponDefinition>(&contents)
+                .unwrap_or_else(|e| panic!("{:?} failed to parse: {}", path, e));
+
+            assert!(!definition.name.is_empty(), "{:?} has no name", path);
+            assert!(definition.damage > 0.0, "{:?} deals no damage", path);
+            assert!(definition.reach > 0.0, "{:?} has no reach", path);
+            assert!((0.0..=1.0).contains(&definition.block_efficiency));
+            assert!((0.0..=1.0).contains(&definition.hit_frame));
+            assert!((0.0..=1.0).contains(&definition.crit_chance));
+            parsed += 1;
+        }
+
+        assert!(parsed > 0, "no weapon definitions in {:?}", dir);
+    }
+
+    #[test]
+    fn starter_weapon_is_shipped() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("assets/data/weapons")
+            .join(format!("{}.ron", STARTER_WEAPON));
+        assert!(path.exists(), "{:?} is missing", path);
+    }
+
+    #[test]
+    fn malformed_weapon_is_rejected() {
+        // Damage isn't a number and the required reach is missing
+        let contents = r#"(
+            name: "Broken Blade",
+            damage: "lots",
+            block_efficiency: 0.5,
+            stamina_cost: 10.0,
+            cooldown: 0.5,
+        )"#;
+        assert!(ron::from_str::<WeaponDefinition>(contents).is_err());
+    }
+}
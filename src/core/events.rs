@@ -5,12 +5,13 @@
 //! apply damage. This keeps systems independent and testable.
 
 use bevy::prelude::*;
+use serde::Deserialize;
 
 /// Element types for damage calculation.
 ///
 /// Each element has strengths and weaknesses against others.
 /// For example, Fire is strong against Ice enemies but weak against Water.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Component)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Component, Deserialize)]
 pub enum Element {
     #[default]
     Physical,
@@ -38,6 +39,33 @@ pub struct DamageEvent {
     pub element: Element,
     /// Knockback direction and force
     pub knockback: Vec3,
+    /// The hit was a critical hit (`amount` already includes the bonus)
+    pub was_crit: bool,
+    /// Dealt by a weapon swing or an enemy's melee attack, as opposed to
+    /// traps, projectiles and explosions
+    pub melee: bool,
+}
+
+/// Sent by the damage system for each hit that actually reduced health.
+///
+/// Hits absorbed by i-frames, or aimed at dead or health-less targets,
+/// never produce one, so feedback keyed to it only fires for real damage.
+#[derive(Event)]
+pub struct DamageDealtEvent {
+    /// Entity that lost health
+    pub target: Entity,
+    /// Entity that caused the damage
+    pub source: Entity,
+    /// Health actually removed, after resistances and blocking
+    pub amount: f32,
+    /// Copied from the `DamageEvent`
+    pub element: Element,
+    /// Copied from the `DamageEvent`
+    pub knockback: Vec3,
+    /// Copied from the `DamageEvent`
+    pub was_crit: bool,
+    /// Copied from the `DamageEvent`
+    pub melee: bool,
 }
 
 /// Sent when an entity dies (health reaches 0).
@@ -52,6 +80,31 @@ pub struct DeathEvent {
     pub killed_by: Option<Entity>,
 }
 
+/// Sent once when an enemy is killed.
+///
+/// The single place to react to enemy deaths (loot, stats, progression,
+/// audio), so listeners don't have to filter `DeathEvent` themselves.
+#[derive(Event)]
+pub struct EnemyKilledEvent {
+    /// The enemy that died
+    pub enemy: Entity,
+    /// Enemy type id (matches `EnemyRegistry` keys)
+    pub enemy_type: String,
+    /// Entity that landed the killing blow (if any)
+    pub killer: Option<Entity>,
+    /// Where the enemy died
+    pub position: Vec3,
+}
+
+/// Sent when an idle enemy notices the player and starts chasing.
+#[derive(Event)]
+pub struct AlertEvent {
+    /// The enemy that spotted the player
+    pub enemy: Entity,
+    /// Where the enemy was when alerted
+    pub position: Vec3,
+}
+
 /// Sent when the player picks up an item.
 #[derive(Event)]
 pub struct ItemPickupEvent {
@@ -0,0 +1,261 @@
+//! Remappable bindings for gameplay actions.
+//!
+//! Each `Action` has a primary and a secondary `Binding` in the `KeyBindings`
+//! resource, either of which can be a key or a mouse button. Input systems
+//! read actions through the `ActionInput` system param instead of checking
+//! keys directly, so an options menu can remap them by replacing bindings.
+//! Function-key toggles and debug keys aren't actions and stay fixed.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+/// A gameplay action that can be bound to input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Sprint,
+    Dodge,
+    Attack,
+    Block,
+    Interact,
+    Throw,
+    Attributes,
+    Map,
+    QuickSave,
+    QuickLoad,
+    Pause,
+}
+
+impl Action {
+    /// Every action, in the order an options menu would list them.
+    pub const ALL: [Action; 16] = [
+        Action::MoveForward,
+        Action::MoveBack,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Sprint,
+        Action::Dodge,
+        Action::Attack,
+        Action::Block,
+        Action::Interact,
+        Action::Throw,
+        Action::Attributes,
+        Action::Map,
+        Action::QuickSave,
+        Action::QuickLoad,
+        Action::Pause,
+    ];
+
+    /// Name shown to the player.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveForward => "Move forward",
+            Action::MoveBack => "Move back",
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::Jump => "Jump",
+            Action::Sprint => "Sprint",
+            Action::Dodge => "Dodge roll",
+            Action::Attack => "Attack",
+            Action::Block => "Block",
+            Action::Interact => "Interact",
+            Action::Throw => "Throw",
+            Action::Attributes => "Attributes",
+            Action::Map => "Map",
+            Action::QuickSave => "Quick save",
+            Action::QuickLoad => "Quick load",
+            Action::Pause => "Pause",
+        }
+    }
+}
+
+/// A single physical input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl Binding {
+    /// Short name shown to the player (e.g. "E", "Space", "Left Mouse").
+    pub fn label(self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{:?}", key);
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            Binding::Mouse(button) => format!("{:?} Mouse", button),
+        }
+    }
+}
+
+/// Primary and secondary binding of one action (either may be unbound).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionBindings {
+    pub primary: Option<Binding>,
+    pub secondary: Option<Binding>,
+}
+
+impl ActionBindings {
+    fn key(primary: KeyCode) -> Self {
+        Self {
+            primary: Some(Binding::Key(primary)),
+            secondary: None,
+        }
+    }
+
+    fn keys(primary: KeyCode, secondary: KeyCode) -> Self {
+        Self {
+            primary: Some(Binding::Key(primary)),
+            secondary: Some(Binding::Key(secondary)),
+        }
+    }
+
+    fn mouse(primary: MouseButton) -> Self {
+        Self {
+            primary: Some(Binding::Mouse(primary)),
+            secondary: None,
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Binding> {
+        self.primary.into_iter().chain(self.secondary)
+    }
+}
+
+/// Bindings for every gameplay action. Defaults match the original
+/// hardcoded controls.
+#[derive(Resource, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    pub move_forward: ActionBindings,
+    pub move_back: ActionBindings,
+    pub move_left: ActionBindings,
+    pub move_right: ActionBindings,
+    pub jump: ActionBindings,
+    pub sprint: ActionBindings,
+    pub dodge: ActionBindings,
+    pub attack: ActionBindings,
+    pub block: ActionBindings,
+    pub interact: ActionBindings,
+    pub throw: ActionBindings,
+    pub attributes: ActionBindings,
+    pub map: ActionBindings,
+    pub quick_save: ActionBindings,
+    pub quick_load: ActionBindings,
+    pub pause: ActionBindings,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            move_forward: ActionBindings::key(KeyCode::KeyW),
+            move_back: ActionBindings::key(KeyCode::KeyS),
+            move_left: ActionBindings::key(KeyCode::KeyA),
+            move_right: ActionBindings::key(KeyCode::KeyD),
+            jump: ActionBindings::key(KeyCode::Space),
+            sprint: ActionBindings::keys(KeyCode::ShiftLeft, KeyCode::ShiftRight),
+            dodge: ActionBindings::key(KeyCode::AltLeft),
+            attack: ActionBindings::mouse(MouseButton::Left),
+            block: ActionBindings::mouse(MouseButton::Right),
+            interact: ActionBindings::key(KeyCode::KeyE),
+            throw: ActionBindings::key(KeyCode::KeyG),
+            attributes: ActionBindings::key(KeyCode::KeyC),
+            map: ActionBindings::key(KeyCode::Tab),
+            quick_save: ActionBindings::key(KeyCode::Insert),
+            quick_load: ActionBindings::key(KeyCode::Delete),
+            pause: ActionBindings::key(KeyCode::Escape),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Bindings of an action.
+    pub fn get(&self, action: Action) -> &ActionBindings {
+        match action {
+            Action::MoveForward => &self.move_forward,
+            Action::MoveBack => &self.move_back,
+            Action::MoveLeft => &self.move_left,
+            Action::MoveRight => &self.move_right,
+            Action::Jump => &self.jump,
+            Action::Sprint => &self.sprint,
+            Action::Dodge => &self.dodge,
+            Action::Attack => &self.attack,
+            Action::Block => &self.block,
+            Action::Interact => &self.interact,
+            Action::Throw => &self.throw,
+            Action::Attributes => &self.attributes,
+            Action::Map => &self.map,
+            Action::QuickSave => &self.quick_save,
+            Action::QuickLoad => &self.quick_load,
+            Action::Pause => &self.pause,
+        }
+    }
+
+    /// Mutable bindings of an action, for remapping.
+    pub fn get_mut(&mut self, action: Action) -> &mut ActionBindings {
+        match action {
+            Action::MoveForward => &mut self.move_forward,
+            Action::MoveBack => &mut self.move_back,
+            Action::MoveLeft => &mut self.move_left,
+            Action::MoveRight => &mut self.move_right,
+            Action::Jump => &mut self.jump,
+            Action::Sprint => &mut self.sprint,
+            Action::Dodge => &mut self.dodge,
+            Action::Attack => &mut self.attack,
+            Action::Block => &mut self.block,
+            Action::Interact => &mut self.interact,
+            Action::Throw => &mut self.throw,
+            Action::Attributes => &mut self.attributes,
+            Action::Map => &mut self.map,
+            Action::QuickSave => &mut self.quick_save,
+            Action::QuickLoad => &mut self.quick_load,
+            Action::Pause => &mut self.pause,
+        }
+    }
+}
+
+/// Reads gameplay actions through the current `KeyBindings`.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    bindings: Res<'w, KeyBindings>,
+}
+
+impl ActionInput<'_> {
+    /// Whether any binding of the action is held.
+    pub fn pressed(&self, action: Action) -> bool {
+        self.any(action, |input, binding| match binding {
+            Binding::Key(key) => input.keyboard.pressed(key),
+            Binding::Mouse(button) => input.mouse.pressed(button),
+        })
+    }
+
+    /// Whether any binding of the action was pressed this frame.
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.any(action, |input, binding| match binding {
+            Binding::Key(key) => input.keyboard.just_pressed(key),
+            Binding::Mouse(button) => input.mouse.just_pressed(button),
+        })
+    }
+
+    /// Whether any binding of the action was released this frame.
+    pub fn just_released(&self, action: Action) -> bool {
+        self.any(action, |input, binding| match binding {
+            Binding::Key(key) => input.keyboard.just_released(key),
+            Binding::Mouse(button) => input.mouse.just_released(button),
+        })
+    }
+
+    fn any(&self, action: Action, check: impl Fn(&Self, Binding) -> bool) -> bool {
+        self.bindings.get(action).iter().any(|binding| check(self, binding))
+    }
+}
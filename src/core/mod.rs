@@ -3,11 +3,17 @@
 //! This module provides the foundation that all other game systems build upon.
 
 mod events;
+mod keybindings;
+mod options;
+mod play_time;
 mod plugin;
 mod states;
 mod tween;
 
 pub use events::*;
+pub use keybindings::*;
+pub use options::*;
+pub use play_time::*;
 pub use plugin::CorePlugin;
 pub use states::*;
 pub use tween::*;
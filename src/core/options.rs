@@ -0,0 +1,434 @@
+//! Player-facing options persisted between sessions.
+//!
+//! Options are stored in a small RON file next to the executable. Each option
+//! also lives as its own resource so gameplay systems only depend on what
+//! they actually read.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Path of the persisted options file (relative to the working directory).
+const OPTIONS_PATH: &str = "options.ron";
+
+/// On-disk representation of all persisted options.
+///
+/// Every field has a serde default so older option files keep loading
+/// when new options are added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedOptions {
+    pub reduce_motion: bool,
+    pub auto_pickup: bool,
+    pub crosshair: CrosshairConfig,
+    pub look: LookOptions,
+    pub enemy_health_bars: EnemyHealthBars,
+    pub hud: HudConfig,
+    pub master_volume: f32,
+    pub pause_on_focus_loss: bool,
+}
+
+impl Default for SavedOptions {
+    fn default() -> Self {
+        Self {
+            reduce_motion: false,
+            auto_pickup: true,
+            crosshair: CrosshairConfig::default(),
+            look: LookOptions::default(),
+            enemy_health_bars: EnemyHealthBars::default(),
+            hud: HudConfig::default(),
+            master_volume: 1.0,
+            pause_on_focus_loss: true,
+        }
+    }
+}
+
+impl SavedOptions {
+    /// Load options from disk, falling back to defaults.
+    pub fn load() -> Self {
+        match fs::read_to_string(OPTIONS_PATH) {
+            Ok(contents) => match ron::from_str(&contents) {
+                Ok(options) => {
+                    info!("Loaded options from {}", OPTIONS_PATH);
+                    options
+                }
+                Err(e) => {
+                    error!("Failed to parse {}: {}. Using defaults.", OPTIONS_PATH, e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Write options to disk.
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Failed to serialize options: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = fs::write(OPTIONS_PATH, contents) {
+            error!("Failed to write {}: {}", OPTIONS_PATH, e);
+        }
+    }
+}
+
+/// Accessibility option that minimises camera motion.
+///
+/// When enabled, screen shake, hit-stop, head bob and viewmodel sway are
+/// suppressed. Gameplay is otherwise unaffected.
+#[derive(Resource, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReduceMotion {
+    pub enabled: bool,
+}
+
+/// Whether pickups are collected on touch or require the interact key.
+#[derive(Resource, Clone, Copy, PartialEq, Eq)]
+pub struct AutoPickup {
+    pub enabled: bool,
+}
+
+impl Default for AutoPickup {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Whether the game pauses when its window loses focus (e.g. alt-tabbing).
+#[derive(Resource, Clone, Copy, PartialEq, Eq)]
+pub struct PauseOnFocusLoss {
+    pub enabled: bool,
+}
+
+impl Default for PauseOnFocusLoss {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Step applied by the master volume hotkeys.
+const VOLUME_STEP: f32 = 0.1;
+
+/// Overall volume multiplier applied to every sound (0.0 to 1.0).
+#[derive(Resource, Clone, Copy, PartialEq)]
+pub struct MasterVolume {
+    pub level: f32,
+}
+
+impl Default for MasterVolume {
+    fn default() -> Self {
+        Self { level: 1.0 }
+    }
+}
+
+impl MasterVolume {
+    /// Level clamped to 0.0-1.0, in case the options file was edited.
+    pub fn clamped(&self) -> f32 {
+        self.level.clamp(0.0, 1.0)
+    }
+
+    /// Raise or lower the level by one step.
+    pub fn step(&mut self, up: bool) {
+        let step = if up { VOLUME_STEP } else { -VOLUME_STEP };
+        // Round so repeated steps land on whole tenths
+        let level = ((self.clamped() + step) * 10.0).round() / 10.0;
+        self.level = level.clamp(0.0, 1.0);
+    }
+}
+
+/// Mouse look sensitivity and filtering.
+///
+/// Filtering defaults to off (raw 1:1 input); its strengths are tuned in
+/// `PlayerConfig`.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LookOptions {
+    /// Mouse sensitivity multiplier
+    pub sensitivity: f32,
+    /// Invert Y-axis for mouse look
+    pub invert_y: bool,
+    /// Low-pass filter mouse movement over a few frames
+    pub smoothing: bool,
+    /// Increase sensitivity with faster mouse movement
+    pub acceleration: bool,
+}
+
+impl Default for LookOptions {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.5,
+            invert_y: false,
+            smoothing: false,
+            acceleration: false,
+        }
+    }
+}
+
+/// When floating health bars are shown above enemies.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnemyHealthBars {
+    /// Every enemy in view
+    Always,
+    /// Recently damaged enemies and the one under the crosshair
+    #[default]
+    OnHit,
+    Never,
+}
+
+impl EnemyHealthBars {
+    /// The next mode in cycling order.
+    pub fn next(self) -> Self {
+        match self {
+            EnemyHealthBars::Always => EnemyHealthBars::OnHit,
+            EnemyHealthBars::OnHit => EnemyHealthBars::Never,
+            EnemyHealthBars::Never => EnemyHealthBars::Always,
+        }
+    }
+}
+
+/// Screen corner the HUD bars are anchored to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HudCorner {
+    TopLeft,
+    TopRight,
+    #[default]
+    BottomLeft,
+    BottomRight,
+}
+
+impl HudCorner {
+    /// The next corner in cycling order (clockwise).
+    pub fn next(self) -> Self {
+        match self {
+            HudCorner::TopLeft => HudCorner::TopRight,
+            HudCorner::TopRight => HudCorner::BottomRight,
+            HudCorner::BottomRight => HudCorner::BottomLeft,
+            HudCorner::BottomLeft => HudCorner::TopLeft,
+        }
+    }
+}
+
+/// HUD scale presets cycled by the hotkey.
+const HUD_SCALES: [f32; 4] = [1.0, 1.5, 2.0, 3.0];
+
+/// HUD bar placement and size.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HudConfig {
+    pub corner: HudCorner,
+    /// Size multiplier for the bars (e.g. 2.0 on 4K displays)
+    pub scale: f32,
+    /// Show the compass strip at the top of the screen
+    pub compass: bool,
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self {
+            corner: HudCorner::BottomLeft,
+            scale: 1.0,
+            compass: true,
+        }
+    }
+}
+
+impl HudConfig {
+    /// Scale clamped to a usable range, in case the options file was edited.
+    pub fn clamped_scale(&self) -> f32 {
+        self.scale.clamp(0.5, 4.0)
+    }
+
+    /// The next larger scale preset, wrapping back to the smallest.
+    pub fn next_scale(&self) -> f32 {
+        HUD_SCALES
+            .iter()
+            .copied()
+            .find(|&scale| scale > self.scale + 0.01)
+            .unwrap_or(HUD_SCALES[0])
+    }
+}
+
+/// Shape of the HUD crosshair.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrosshairStyle {
+    #[default]
+    Dot,
+    Cross,
+    Circle,
+    None,
+}
+
+impl CrosshairStyle {
+    /// The next style in cycling order.
+    pub fn next(self) -> Self {
+        match self {
+            CrosshairStyle::Dot => CrosshairStyle::Cross,
+            CrosshairStyle::Cross => CrosshairStyle::Circle,
+            CrosshairStyle::Circle => CrosshairStyle::None,
+            CrosshairStyle::None => CrosshairStyle::Dot,
+        }
+    }
+}
+
+/// Crosshair appearance.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrosshairConfig {
+    pub style: CrosshairStyle,
+    /// RGB color (0.0-1.0)
+    pub color: (f32, f32, f32),
+    /// Opacity (0.0-1.0)
+    pub opacity: f32,
+}
+
+impl Default for CrosshairConfig {
+    fn default() -> Self {
+        Self {
+            style: CrosshairStyle::Dot,
+            color: (1.0, 1.0, 1.0),
+            opacity: 0.5,
+        }
+    }
+}
+
+impl CrosshairConfig {
+    /// Crosshair color with opacity applied.
+    pub fn color(&self) -> Color {
+        Color::srgba(self.color.0, self.color.1, self.color.2, self.opacity)
+    }
+}
+
+/// Every live option resource, for systems that change them.
+#[derive(SystemParam)]
+pub struct OptionsMut<'w> {
+    pub reduce_motion: ResMut<'w, ReduceMotion>,
+    pub auto_pickup: ResMut<'w, AutoPickup>,
+    pub crosshair: ResMut<'w, CrosshairConfig>,
+    pub look: ResMut<'w, LookOptions>,
+    pub enemy_health_bars: ResMut<'w, EnemyHealthBars>,
+    pub hud: ResMut<'w, HudConfig>,
+    pub master_volume: ResMut<'w, MasterVolume>,
+    pub pause_on_focus_loss: ResMut<'w, PauseOnFocusLoss>,
+}
+
+/// Toggle or cycle options with their function-key hotkeys, and step the
+/// master volume with -/=.
+pub fn toggle_options_input(keyboard: Res<ButtonInput<KeyCode>>, options: OptionsMut) {
+    let OptionsMut {
+        mut reduce_motion,
+        mut auto_pickup,
+        mut crosshair,
+        mut look,
+        mut enemy_health_bars,
+        mut hud,
+        mut master_volume,
+        mut pause_on_focus_loss,
+    } = options;
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+
+    if keyboard.just_pressed(KeyCode::F1) {
+        *enemy_health_bars = enemy_health_bars.next();
+        info!("Enemy health bars: {:?}", *enemy_health_bars);
+    }
+    if keyboard.just_pressed(KeyCode::F6) {
+        reduce_motion.enabled = !reduce_motion.enabled;
+        info!("Reduce motion: {}", if reduce_motion.enabled { "on" } else { "off" });
+    }
+    if keyboard.just_pressed(KeyCode::F7) {
+        if shift {
+            pause_on_focus_loss.enabled = !pause_on_focus_loss.enabled;
+            info!(
+                "Pause on focus loss: {}",
+                if pause_on_focus_loss.enabled { "on" } else { "off" }
+            );
+        } else {
+            auto_pickup.enabled = !auto_pickup.enabled;
+            info!("Auto-pickup: {}", if auto_pickup.enabled { "on" } else { "off" });
+        }
+    }
+    if keyboard.just_pressed(KeyCode::F8) {
+        crosshair.style = crosshair.style.next();
+        info!("Crosshair: {:?}", crosshair.style);
+    }
+    if keyboard.just_pressed(KeyCode::F9) {
+        look.smoothing = !look.smoothing;
+        info!("Look smoothing: {}", if look.smoothing { "on" } else { "off" });
+    }
+    if keyboard.just_pressed(KeyCode::F10) {
+        look.acceleration = !look.acceleration;
+        info!("Mouse acceleration: {}", if look.acceleration { "on" } else { "off" });
+    }
+    if keyboard.just_pressed(KeyCode::F12) {
+        if shift {
+            hud.scale = hud.next_scale();
+            info!("HUD scale: {}", hud.scale);
+        } else if keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight) {
+            hud.compass = !hud.compass;
+            info!("Compass: {}", if hud.compass { "on" } else { "off" });
+        } else {
+            hud.corner = hud.corner.next();
+            info!("HUD corner: {:?}", hud.corner);
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Minus) || keyboard.just_pressed(KeyCode::Equal) {
+        master_volume.step(keyboard.just_pressed(KeyCode::Equal));
+        info!("Master volume: {:.0}%", master_volume.level * 100.0);
+    }
+}
+
+/// Read access to every live option resource.
+#[derive(SystemParam)]
+pub struct LiveOptions<'w> {
+    reduce_motion: Res<'w, ReduceMotion>,
+    auto_pickup: Res<'w, AutoPickup>,
+    crosshair: Res<'w, CrosshairConfig>,
+    look: Res<'w, LookOptions>,
+    enemy_health_bars: Res<'w, EnemyHealthBars>,
+    hud: Res<'w, HudConfig>,
+    master_volume: Res<'w, MasterVolume>,
+    pause_on_focus_loss: Res<'w, PauseOnFocusLoss>,
+}
+
+impl LiveOptions<'_> {
+    /// Whether any option was modified after its initial insertion.
+    pub fn any_modified(&self) -> bool {
+        modified(&self.reduce_motion)
+            || modified(&self.auto_pickup)
+            || modified(&self.crosshair)
+            || modified(&self.look)
+            || modified(&self.enemy_health_bars)
+            || modified(&self.hud)
+            || modified(&self.master_volume)
+            || modified(&self.pause_on_focus_loss)
+    }
+
+    /// The current options in their on-disk form.
+    pub fn to_saved(&self) -> SavedOptions {
+        SavedOptions {
+            reduce_motion: self.reduce_motion.enabled,
+            auto_pickup: self.auto_pickup.enabled,
+            crosshair: *self.crosshair,
+            look: *self.look,
+            enemy_health_bars: *self.enemy_health_bars,
+            hud: *self.hud,
+            master_volume: self.master_volume.level,
+            pause_on_focus_loss: self.pause_on_focus_loss.enabled,
+        }
+    }
+}
+
+/// Whether a resource was modified after its initial insertion.
+fn modified<T: Resource>(resource: &Res<T>) -> bool {
+    resource.is_changed() && !resource.is_added()
+}
+
+/// Persist options whenever one of them changes.
+pub fn save_options_on_change(options: LiveOptions) {
+    if options.any_modified() {
+        options.to_saved().save();
+    }
+}
@@ -0,0 +1,48 @@
+//! Play-time tracking.
+
+use bevy::prelude::*;
+
+use super::states::GameState;
+
+/// Real time spent in gameplay, excluding menus and pause.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct PlayTime {
+    /// Seconds played since the game was started or loaded
+    pub session: f64,
+    /// Seconds played in total on this save (includes `session`)
+    pub total: f64,
+}
+
+impl PlayTime {
+    /// Start tracking from a saved total (e.g. after loading a save).
+    pub fn restore(total: f64) -> Self {
+        Self {
+            session: 0.0,
+            total,
+        }
+    }
+
+    /// Reset for a new game.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Format seconds as `H:MM:SS`.
+pub fn format_play_time(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60)
+}
+
+/// Set up play-time tracking.
+pub fn setup_play_time_systems(app: &mut App) {
+    app.init_resource::<PlayTime>()
+        .add_systems(Update, tick_play_time.run_if(in_state(GameState::InGame)));
+}
+
+/// Accumulate real (unscaled) time while in gameplay.
+fn tick_play_time(time: Res<Time<Real>>, mut play_time: ResMut<PlayTime>) {
+    let delta = time.delta_secs_f64();
+    play_time.session += delta;
+    play_time.total += delta;
+}
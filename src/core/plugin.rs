@@ -1,8 +1,12 @@
 //! Core plugin that sets up game states, events, and fundamental systems.
 
 use bevy::prelude::*;
+use bevy::window::WindowFocused;
 
 use super::events::*;
+use super::keybindings::*;
+use super::options::*;
+use super::play_time::*;
 use super::states::*;
 use super::tween::*;
 
@@ -16,46 +20,87 @@ pub struct CorePlugin;
 
 impl Plugin for CorePlugin {
     fn build(&self, app: &mut App) {
+        // Play-time tracking
+        setup_play_time_systems(app);
+
+        // Game states, and freezing virtual time while gameplay is paused
+        setup_state_systems(app);
+
+        // Load persisted options before any gameplay system reads them
+        let options = SavedOptions::load();
+
         app
-            // Initialize game states
-            .init_state::<GameState>()
-            .add_sub_state::<PlayState>()
+            // Player options
+            .insert_resource(ReduceMotion {
+                enabled: options.reduce_motion,
+            })
+            .insert_resource(AutoPickup {
+                enabled: options.auto_pickup,
+            })
+            .insert_resource(options.crosshair)
+            .insert_resource(options.look)
+            .insert_resource(options.enemy_health_bars)
+            .insert_resource(options.hud)
+            .insert_resource(MasterVolume {
+                level: options.master_volume,
+            })
+            .insert_resource(PauseOnFocusLoss {
+                enabled: options.pause_on_focus_loss,
+            })
+            .add_systems(Update, (toggle_options_input, save_options_on_change).chain())
+            .init_resource::<KeyBindings>()
 
             // Register global events
             .add_event::<DamageEvent>()
+            .add_event::<DamageDealtEvent>()
             .add_event::<DeathEvent>()
+            .add_event::<EnemyKilledEvent>()
+            .add_event::<AlertEvent>()
             .add_event::<ItemPickupEvent>()
             .add_event::<LevelUpEvent>()
 
-            // Loading state - transition to MainMenu when ready
-            // For now, immediately transition since we have no assets to load
-            .add_systems(OnEnter(GameState::Loading), transition_to_main_menu)
-
-            // Pause/unpause with Escape key
+            // Pause/unpause with the pause key
             .add_systems(
                 Update,
                 handle_pause_input.run_if(in_state(GameState::InGame).or(in_state(GameState::Paused)))
             )
+            // Pause when alt-tabbing; regaining focus leaves the game paused
+            // so the click that refocuses the window doesn't leak into play
+            .add_systems(Update, pause_on_focus_loss.run_if(in_state(GameState::InGame)))
 
             // Smooth transform interpolation (runs for all game states)
             .add_systems(Update, update_smooth_transforms);
     }
 }
 
-/// Immediately transition from Loading to MainMenu.
-/// Later this will wait for assets to load.
-fn transition_to_main_menu(mut next_state: ResMut<NextState<GameState>>) {
-    // TODO: Add actual asset loading checks here
-    next_state.set(GameState::MainMenu);
+/// Set up game states and pausing.
+fn setup_state_systems(app: &mut App) {
+    app.init_state::<GameState>()
+        .add_sub_state::<PlayState>()
+        .add_computed_state::<GameSession>()
+        // Freeze virtual time while paused so animations, physics and
+        // anything else driven by `Time` resume where they left off
+        .add_systems(OnEnter(GameState::Paused), pause_virtual_time)
+        .add_systems(OnExit(GameState::Paused), unpause_virtual_time)
+        // The attribute screen pauses gameplay the same way
+        .add_systems(OnEnter(PlayState::LevelUp), pause_virtual_time)
+        .add_systems(OnExit(PlayState::LevelUp), unpause_virtual_time);
 }
 
-/// Handle Escape key to pause/unpause the game.
+/// Handle the pause key (Escape by default) to pause/unpause the game.
+///
+/// While the attribute screen is open, the pause key closes it instead.
 fn handle_pause_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     current_state: Res<State<GameState>>,
+    play_state: Option<Res<State<PlayState>>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+    if play_state.is_some_and(|state| *state.get() == PlayState::LevelUp) {
+        return;
+    }
+
+    if input.just_pressed(Action::Pause) {
         match current_state.get() {
             GameState::InGame => next_state.set(GameState::Paused),
             GameState::Paused => next_state.set(GameState::InGame),
@@ -63,3 +108,154 @@ fn handle_pause_input(
         }
     }
 }
+
+/// Pause the game when the window loses focus, if enabled.
+///
+/// Leaving `InGame` releases the cursor. The attribute screen already frees
+/// the cursor and pausing from it would discard the screen, so it's left open.
+fn pause_on_focus_loss(
+    mut focus_events: EventReader<WindowFocused>,
+    option: Res<PauseOnFocusLoss>,
+    play_state: Option<Res<State<PlayState>>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let lost_focus = focus_events.read().any(|event| !event.focused);
+    if !lost_focus || !option.enabled {
+        return;
+    }
+    if play_state.is_some_and(|state| *state.get() == PlayState::LevelUp) {
+        return;
+    }
+
+    info!("Window lost focus, pausing");
+    next_state.set(GameState::Paused);
+}
+
+/// Stop virtual time when the game is paused or the attribute screen opens.
+fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// Resume virtual time when leaving the pause menu or attribute screen.
+fn unpause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+    use std::time::Duration;
+
+    /// A player in the middle of a swing while falling.
+    #[derive(Component)]
+    struct MidAction {
+        swing: Timer,
+        vertical_velocity: f32,
+        height: f32,
+    }
+
+    /// Times the level was torn down.
+    #[derive(Resource, Default)]
+    struct Teardowns(u32);
+
+    /// Advance the swing and fall with `Time`, like animations and physics
+    /// do regardless of game state.
+    fn advance(time: Res<Time>, mut query: Query<&mut MidAction>) {
+        for mut action in query.iter_mut() {
+            action.swing.tick(time.delta());
+            action.vertical_velocity -= 9.8 * time.delta_secs();
+            action.height += action.vertical_velocity * time.delta_secs();
+        }
+    }
+
+    fn count_teardown(mut teardowns: ResMut<Teardowns>) {
+        teardowns.0 += 1;
+    }
+
+    fn pause_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((StatesPlugin, TimePlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)))
+            .init_resource::<Teardowns>()
+            .add_systems(Update, advance)
+            .add_systems(OnExit(GameSession), count_teardown);
+        setup_state_systems(&mut app);
+
+        let entity = app
+            .world_mut()
+            .spawn(MidAction {
+                swing: Timer::from_seconds(10.0, TimerMode::Once),
+                vertical_velocity: 0.0,
+                height: 100.0,
+            })
+            .id();
+        (app, entity)
+    }
+
+    fn set_state(app: &mut App, state: GameState) {
+        app.world_mut().resource_mut::<NextState<GameState>>().set(state);
+        app.update();
+    }
+
+    fn snapshot(app: &App, entity: Entity) -> (Duration, f32, f32) {
+        let action = app.world().get::<MidAction>(entity).unwrap();
+        (action.swing.elapsed(), action.vertical_velocity, action.height)
+    }
+
+    #[test]
+    fn pause_freezes_swing_and_fall() {
+        let (mut app, entity) = pause_app();
+        set_state(&mut app, GameState::InGame);
+        app.update();
+        app.update();
+
+        set_state(&mut app, GameState::Paused);
+        let paused = snapshot(&app, entity);
+        assert!(paused.0 > Duration::ZERO, "swing should be underway");
+        assert!(paused.1 < 0.0, "player should be falling");
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(snapshot(&app, entity), paused);
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+    }
+
+    #[test]
+    fn resume_continues_where_pause_left_off() {
+        let (mut app, entity) = pause_app();
+        set_state(&mut app, GameState::InGame);
+        app.update();
+
+        set_state(&mut app, GameState::Paused);
+        let paused = snapshot(&app, entity);
+        app.update();
+
+        set_state(&mut app, GameState::InGame);
+        app.update();
+        let resumed = snapshot(&app, entity);
+
+        // One frame's worth of progress, not a jump or a restart
+        assert_eq!(resumed.0 - paused.0, Duration::from_millis(50));
+        assert!(resumed.1 < paused.1);
+        assert!(resumed.2 < paused.2);
+        assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+    }
+
+    #[test]
+    fn pausing_keeps_the_session() {
+        let (mut app, entity) = pause_app();
+        set_state(&mut app, GameState::InGame);
+        set_state(&mut app, GameState::Paused);
+        assert!(app.world().get_resource::<State<GameSession>>().is_some());
+        set_state(&mut app, GameState::InGame);
+
+        assert_eq!(app.world().resource::<Teardowns>().0, 0);
+        assert!(app.world().get_entity(entity).is_ok());
+
+        set_state(&mut app, GameState::MainMenu);
+        assert_eq!(app.world().resource::<Teardowns>().0, 1);
+    }
+}
@@ -13,7 +13,7 @@ use bevy::prelude::*;
 /// - Move to `MainMenu` when loading completes
 /// - Enter `InGame` when player starts/continues
 /// - `Paused` freezes gameplay but keeps the world visible
-/// - `GameOver` when player dies
+/// - `GameOver` when player dies, until they retry from the last checkpoint
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum GameState {
     /// Initial state - loading assets and data files
@@ -34,6 +34,7 @@ pub enum GameState {
 /// These control what the player can do during active gameplay:
 /// - `Exploring`: Normal movement, combat, and interaction
 /// - `Inventory`: Inventory screen is open, gameplay paused
+/// - `LevelUp`: Attribute screen is open, gameplay paused
 /// - `Dialogue`: Talking to an NPC (future feature)
 #[derive(SubStates, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 #[source(GameState = GameState::InGame)]
@@ -43,6 +44,27 @@ pub enum PlayState {
     Exploring,
     /// Inventory screen is open
     Inventory,
+    /// Attribute allocation screen is open (after a level up)
+    LevelUp,
     /// Dialogue with NPC (future)
     Dialogue,
 }
+
+/// Computed state that exists while a level is loaded: `InGame`, `Paused`
+/// or `GameOver`.
+///
+/// Level setup and teardown hang off this instead of `GameState::InGame`,
+/// so pausing and resuming keep the world, player and HUD exactly as they
+/// were instead of rebuilding them. Dying keeps them too, so retrying
+/// respawns the player at a checkpoint in the same level.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct GameSession;
+
+impl ComputedStates for GameSession {
+    type SourceStates = GameState;
+
+    fn compute(sources: GameState) -> Option<Self> {
+        matches!(sources, GameState::InGame | GameState::Paused | GameState::GameOver)
+            .then_some(GameSession)
+    }
+}
@@ -1,26 +1,105 @@
 //! Enemy AI behavior systems.
 
+use bevy::core::FrameCount;
 use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
 
-use super::components::{AiState, AttackReady, AttackTimer, DeathTimer, Enemy, EnemyStats};
-use crate::combat::Health;
+use super::components::{
+    AiLod, AiState, AttackReady, AttackTimer, DeathTimer, Enemy, EnemyStats, Wander,
+    AI_LOD_WAKE_FACTOR, WANDER_RADIUS,
+};
+use crate::combat::DamageEvent;
+use crate::core::{AlertEvent, EnemyKilledEvent};
 use crate::player::Player;
+use crate::world::DoorPanel;
 
-/// Detect player and transition from Idle to Chasing.
-pub fn ai_detection(
+/// Put distant idle enemies to sleep and wake them when the player approaches.
+///
+/// Dormant enemies only re-check distance on their staggered tick frame.
+pub fn update_ai_lod(
+    frame: Res<FrameCount>,
     player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    mut enemy_query: Query<(&Transform, &EnemyStats, &mut AiState), (With<Enemy>, Without<Player>)>,
+    mut enemy_query: Query<(&Transform, &EnemyStats, &AiState, &mut AiLod), With<Enemy>>,
 ) {
     let Ok(player_transform) = player_query.get_single() else {
         return;
     };
 
-    for (enemy_transform, stats, mut ai_state) in enemy_query.iter_mut() {
+    for (enemy_transform, stats, ai_state, mut lod) in enemy_query.iter_mut() {
+        // Engaged enemies always run full-rate AI
+        if *ai_state != AiState::Idle {
+            lod.active = true;
+            continue;
+        }
+
+        // Dormant enemies only check on their tick frame
+        if !lod.active && !lod.is_tick_frame(frame.0) {
+            continue;
+        }
+
+        let offset = player_transform.translation - enemy_transform.translation;
+        let horizontal_distance = Vec3::new(offset.x, 0.0, offset.z).length();
+        lod.active = horizontal_distance <= stats.detection_range * AI_LOD_WAKE_FACTOR;
+    }
+}
+
+/// An enemy that may notice the player, with its AI LOD if it has one.
+type DetectingEnemyData<'a> = (
+    Entity,
+    &'a Transform,
+    &'a EnemyStats,
+    &'a mut AiState,
+    Option<&'a AiLod>,
+);
+
+/// The player, kept apart from the enemy queries.
+type PlayerNotEnemy = (With<Player>, Without<Enemy>);
+
+/// Detect player and transition from Idle to Chasing.
+///
+/// The player must be within detection range and inside the enemy's vision
+/// cone. Taking damage from the player alerts an idle enemy regardless of
+/// where it faces.
+pub fn ai_detection(
+    mut damage_events: EventReader<DamageEvent>,
+    mut alert_events: EventWriter<AlertEvent>,
+    player_query: Query<(Entity, &Transform), PlayerNotEnemy>,
+    mut enemy_query: Query<DetectingEnemyData, (With<Enemy>, Without<Player>)>,
+) {
+    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+        return;
+    };
+
+    // Player projectiles and thrown items carry the player as their source;
+    // traps and hazards don't give anyone away
+    let hit_enemies: Vec<Entity> = damage_events
+        .read()
+        .filter(|event| event.source == player_entity)
+        .map(|event| event.target)
+        .collect();
+
+    for (entity, enemy_transform, stats, mut ai_state, lod) in enemy_query.iter_mut() {
         // Only check detection when idle
         if *ai_state != AiState::Idle {
             continue;
         }
 
+        // Getting hit by the player always gives them away
+        if hit_enemies.contains(&entity) {
+            *ai_state = AiState::Chasing;
+            alert_events.send(AlertEvent {
+                enemy: entity,
+                position: enemy_transform.translation,
+            });
+            continue;
+        }
+
+        // Skip dormant enemies
+        if lod.is_some_and(|lod| !lod.active) {
+            continue;
+        }
+
         // Use horizontal distance (consistent with ai_chase)
         let player_pos = player_transform.translation;
         let enemy_pos = enemy_transform.translation;
@@ -30,23 +109,238 @@ pub fn ai_detection(
             player_pos.z - enemy_pos.z,
         ).length();
 
-        if horizontal_distance <= stats.detection_range {
+        // Model forward is +Z
+        let facing = enemy_transform.back().as_vec3();
+        if horizontal_distance <= stats.detection_range
+            && stats.in_vision_cone(facing, player_pos - enemy_pos)
+        {
             *ai_state = AiState::Chasing;
+            alert_events.send(AlertEvent {
+                enemy: entity,
+                position: enemy_pos,
+            });
+        }
+    }
+}
+
+/// Fraction of `move_speed` used while wandering.
+const WANDER_SPEED_FACTOR: f32 = 0.4;
+
+/// Attempts at finding a reachable wander destination before pausing again.
+const WANDER_PICK_ATTEMPTS: usize = 4;
+
+/// An enemy that may wander, with its AI LOD if it has one.
+type WanderingEnemyData<'a> = (
+    Entity,
+    &'a mut Transform,
+    &'a EnemyStats,
+    &'a AiState,
+    &'a mut Wander,
+    Option<&'a AiLod>,
+);
+
+/// Stroll idle enemies between random nearby points, pausing at each.
+pub fn ai_wander(
+    time: Res<Time>,
+    rapier_context: Query<&RapierContext>,
+    door_query: Query<(), With<DoorPanel>>,
+    mut enemy_query: Query<WanderingEnemyData, With<Enemy>>,
+) {
+    let context = rapier_context.get_single().ok();
+    let delta = time.delta_secs();
+    let mut rng = rand::thread_rng();
+
+    for (entity, mut transform, stats, ai_state, mut wander, lod) in enemy_query.iter_mut() {
+        // Engaged enemies drop their stroll
+        if *ai_state != AiState::Idle {
+            wander.target = None;
+            continue;
+        }
+
+        // Dormant enemies stay put
+        if lod.is_some_and(|lod| !lod.active) {
+            continue;
+        }
+
+        wander.timer -= delta;
+
+        let Some(target) = wander.target else {
+            if wander.timer > 0.0 {
+                continue;
+            }
+
+            // Pick a reachable point near home
+            let origin = transform.translation;
+            let speed = stats.move_speed * WANDER_SPEED_FACTOR;
+            let destination = (0..WANDER_PICK_ATTEMPTS).find_map(|_| {
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                let radius = rng.gen_range(1.0..WANDER_RADIUS);
+                let candidate = wander.home + Vec3::new(angle.cos(), 0.0, angle.sin()) * radius;
+                let candidate = Vec3::new(candidate.x, origin.y, candidate.z);
+                context
+                    .is_none_or(|context| {
+                        is_reachable(context, entity, origin, candidate, stats.max_drop)
+                    })
+                    .then_some(candidate)
+            });
+
+            match destination {
+                Some(destination) => {
+                    wander.target = Some(destination);
+                    // Give up if blocked for much longer than the walk should take
+                    wander.timer = origin.distance(destination) / speed.max(0.1) * 2.0 + 1.0;
+                }
+                None => wander.timer = rng.gen_range(1.0..3.0),
+            }
+            continue;
+        };
+
+        let offset = Vec3::new(
+            target.x - transform.translation.x,
+            0.0,
+            target.z - transform.translation.z,
+        );
+
+        // Arrived (or gave up): pause before the next stroll
+        if offset.length() < 0.2 || wander.timer <= 0.0 {
+            wander.target = None;
+            wander.timer = rng.gen_range(2.0..5.0);
+            continue;
         }
+
+        turn_toward(&mut transform, offset, stats.turn_rate, delta);
+        let facing = transform.back().as_vec3();
+        let move_direction = Vec3::new(facing.x, 0.0, facing.z).normalize_or_zero();
+        // A door closed since the stroll began: wait for the timer to give up
+        if context.is_some_and(|context| {
+            blocked_by_door(context, entity, transform.translation, move_direction, &door_query)
+        }) {
+            continue;
+        }
+        let step = (stats.move_speed * WANDER_SPEED_FACTOR * delta).min(offset.length());
+        transform.translation += move_direction * step;
+    }
+}
+
+/// Whether a wander destination has a clear line and ground beneath it.
+fn is_reachable(
+    context: &RapierContext,
+    entity: Entity,
+    from: Vec3,
+    to: Vec3,
+    max_drop: f32,
+) -> bool {
+    let filter = QueryFilter::default().exclude_collider(entity);
+    let eye = Vec3::Y * RAY_HEIGHT;
+    let offset = to - from;
+    let distance = offset.length();
+    if distance <= 0.0 {
+        return false;
+    }
+
+    // Wall check
+    if context
+        .cast_ray(from + eye, offset / distance, distance + 0.5, true, filter)
+        .is_some()
+    {
+        return false;
+    }
+
+    // Ground check (don't stroll into pits)
+    has_ground(context, entity, to, max_drop)
+}
+
+/// Height above an enemy's origin that obstacle and ground rays start from.
+const RAY_HEIGHT: f32 = 0.5;
+
+/// How far ahead of a chasing enemy the ledge check probes for ground.
+const LEDGE_PROBE_DISTANCE: f32 = 0.6;
+
+/// How far ahead of a moving enemy the door check probes.
+const DOOR_PROBE_DISTANCE: f32 = 0.6;
+
+/// Whether a closed door is right in front of an enemy at `position`.
+///
+/// Open doors have their collider disabled, so only closed ones are hit.
+fn blocked_by_door(
+    context: &RapierContext,
+    entity: Entity,
+    position: Vec3,
+    direction: Vec3,
+    doors: &Query<(), With<DoorPanel>>,
+) -> bool {
+    let predicate = |collider: Entity| doors.contains(collider);
+    let filter = QueryFilter::default()
+        .exclude_collider(entity)
+        .predicate(&predicate);
+    direction != Vec3::ZERO
+        && context
+            .cast_ray(position + Vec3::Y * RAY_HEIGHT, direction, DOOR_PROBE_DISTANCE, true, filter)
+            .is_some()
+}
+
+/// Whether there is ground within `max_drop` below `position`.
+fn has_ground(context: &RapierContext, entity: Entity, position: Vec3, max_drop: f32) -> bool {
+    let filter = QueryFilter::default().exclude_collider(entity);
+    context
+        .cast_ray(position + Vec3::Y * RAY_HEIGHT, Vec3::NEG_Y, RAY_HEIGHT + max_drop, true, filter)
+        .is_some()
+}
+
+/// Adjust a movement direction so an enemy at `position` doesn't walk off a ledge.
+///
+/// If there's no ground ahead, steer along the edge toward `goal` (whichever
+/// perpendicular direction has ground). Returns zero when both are blocked.
+fn avoid_ledges(
+    context: &RapierContext,
+    entity: Entity,
+    position: Vec3,
+    direction: Vec3,
+    goal: Vec3,
+    max_drop: f32,
+) -> Vec3 {
+    let safe = |dir: Vec3| {
+        has_ground(context, entity, position + dir * LEDGE_PROBE_DISTANCE, max_drop)
+    };
+
+    if direction == Vec3::ZERO || safe(direction) {
+        return direction;
     }
+
+    let left = Vec3::new(-direction.z, 0.0, direction.x);
+    let right = -left;
+    let (first, second) = if left.dot(goal) >= right.dot(goal) {
+        (left, right)
+    } else {
+        (right, left)
+    };
+
+    [first, second]
+        .into_iter()
+        .find(|&dir| safe(dir))
+        .unwrap_or(Vec3::ZERO)
 }
 
+/// An enemy that may chase the player.
+type ChasingEnemyData<'a> = (Entity, &'a mut Transform, &'a EnemyStats, &'a mut AiState);
+
 /// Chase player and transition to Attacking when in range.
+///
+/// Enemies stop at ledges deeper than their `max_drop`, sliding along the
+/// edge instead of walking off, and at closed doors.
 pub fn ai_chase(
     time: Res<Time>,
+    rapier_context: Query<&RapierContext>,
+    door_query: Query<(), With<DoorPanel>>,
     player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    mut enemy_query: Query<(&mut Transform, &EnemyStats, &mut AiState), (With<Enemy>, Without<Player>)>,
+    mut enemy_query: Query<ChasingEnemyData, (With<Enemy>, Without<Player>)>,
 ) {
     let Ok(player_transform) = player_query.get_single() else {
         return;
     };
+    let context = rapier_context.get_single().ok();
 
-    for (mut enemy_transform, stats, mut ai_state) in enemy_query.iter_mut() {
+    for (entity, mut enemy_transform, stats, mut ai_state) in enemy_query.iter_mut() {
         // Only move when chasing
         if *ai_state != AiState::Chasing {
             continue;
@@ -65,7 +359,7 @@ pub fn ai_chase(
         let distance = direction.length();
 
         // Check if in attack range
-        if distance <= stats.attack_range {
+        if stats.should_attack(false, distance) {
             *ai_state = AiState::Attacking;
             continue;
         }
@@ -78,15 +372,25 @@ pub fn ai_chase(
 
         // Move toward player
         if distance > 0.1 {
-            let move_direction = direction.normalize();
+            // Turn toward the player, then walk where the model actually faces
+            turn_toward(&mut enemy_transform, direction, stats.turn_rate, time.delta_secs());
+            let facing = enemy_transform.back().as_vec3();
+            let mut move_direction = Vec3::new(facing.x, 0.0, facing.z).normalize_or_zero();
+            if let Some(context) = context {
+                move_direction = avoid_ledges(
+                    context,
+                    entity,
+                    enemy_pos,
+                    move_direction,
+                    direction,
+                    stats.max_drop,
+                );
+                if blocked_by_door(context, entity, enemy_pos, move_direction, &door_query) {
+                    move_direction = Vec3::ZERO;
+                }
+            }
             let movement = move_direction * stats.move_speed * time.delta_secs();
             enemy_transform.translation += movement;
-
-            // Face the player (rotate around Y axis)
-            let look_target = Vec3::new(player_pos.x, enemy_transform.translation.y, player_pos.z);
-            enemy_transform.look_at(look_target, Vec3::Y);
-            // Rotate 180° because model's forward is +Z, not -Z
-            enemy_transform.rotate_y(std::f32::consts::PI);
         }
     }
 }
@@ -113,10 +417,8 @@ pub fn ai_attack(
 
         // Face the player (rotate around Y axis)
         let player_pos = player_transform.translation;
-        let look_target = Vec3::new(player_pos.x, enemy_transform.translation.y, player_pos.z);
-        enemy_transform.look_at(look_target, Vec3::Y);
-        // Rotate 180° because model's forward is +Z, not -Z
-        enemy_transform.rotate_y(std::f32::consts::PI);
+        let to_player = player_pos - enemy_transform.translation;
+        turn_toward(&mut enemy_transform, to_player, stats.turn_rate, time.delta_secs());
 
         // Tick the attack timer
         attack_timer.0.tick(time.delta());
@@ -141,8 +443,8 @@ pub fn ai_attack(
                 player_pos.z - enemy_pos.z,
             ).length();
 
-            // If player moved out of attack range, go back to chasing
-            if horizontal_distance > stats.attack_range {
+            // If player moved well out of attack range, go back to chasing
+            if !stats.should_attack(true, horizontal_distance) {
                 *ai_state = AiState::Chasing;
             }
             // Otherwise stay in Attacking state (attack again)
@@ -150,18 +452,35 @@ pub fn ai_attack(
     }
 }
 
+/// Rotate an enemy around Y toward `direction` by at most `turn_rate` degrees per second.
+///
+/// The enemy model's forward is +Z, so the target yaw points +Z along `direction`.
+fn turn_toward(transform: &mut Transform, direction: Vec3, turn_rate: f32, delta: f32) {
+    if direction.x == 0.0 && direction.z == 0.0 {
+        return;
+    }
+
+    let target = Quat::from_rotation_y(direction.x.atan2(direction.z));
+    let angle = transform.rotation.angle_between(target);
+    let max_step = turn_rate.to_radians() * delta;
+
+    if angle <= max_step {
+        transform.rotation = target;
+    } else {
+        transform.rotation = transform.rotation.slerp(target, max_step / angle);
+    }
+}
+
 /// Handle enemy death transition.
 pub fn handle_enemy_death(
     mut commands: Commands,
-    mut enemy_query: Query<
-        (Entity, &Health, &mut AiState),
-        (With<Enemy>, Without<DeathTimer>),
-    >,
+    mut killed_events: EventReader<EnemyKilledEvent>,
+    mut enemy_query: Query<&mut AiState, (With<Enemy>, Without<DeathTimer>)>,
 ) {
-    for (entity, health, mut ai_state) in enemy_query.iter_mut() {
-        if health.is_dead() && *ai_state != AiState::Dying {
+    for event in killed_events.read() {
+        if let Ok(mut ai_state) = enemy_query.get_mut(event.enemy) {
             *ai_state = AiState::Dying;
-            commands.entity(entity).insert(DeathTimer::default());
+            commands.entity(event.enemy).insert(DeathTimer::default());
         }
     }
 }
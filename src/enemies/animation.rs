@@ -3,9 +3,13 @@
 use bevy::prelude::*;
 use bevy::animation::{AnimationClip, AnimationPlayer, RepeatAnimation, graph::AnimationNodeIndex};
 
-use super::components::{AiState, AttackReady, Enemy, EnemyType, EnemyStats};
+use super::components::{AiState, AttackReady, Enemy, EnemyAttacks, EnemyType, EnemyStats, Wander};
 use super::data::{AnimationConfig, EnemyRegistry};
 use crate::combat::DamageEvent;
+use crate::player::Player;
+
+/// Seconds an enemy holds its combat stance after aborting a wind-up.
+const ATTACK_CANCEL_RECOVERY: f32 = 0.25;
 
 /// Visual animation state (separate from AI state for animation control).
 #[derive(Component, Default, Clone, Copy, PartialEq, Debug)]
@@ -26,7 +30,8 @@ pub struct EnemyAnimations {
     pub idle: AnimationNodeIndex,
     pub walk: AnimationNodeIndex,
     pub combat_idle: AnimationNodeIndex,
-    pub attack: AnimationNodeIndex,
+    /// One node per attack, in `EnemyAttacks` order.
+    pub attacks: Vec<AnimationNodeIndex>,
     pub hurt: Option<AnimationNodeIndex>,
     pub death: AnimationNodeIndex,
 }
@@ -53,8 +58,12 @@ pub struct PreviousAnimationState(pub AnimationState);
 /// Tracks attack animation progress for hit detection.
 #[derive(Component)]
 pub struct AttackAnimationProgress {
+    /// Index of the selected attack in `EnemyAttacks`
+    pub attack_index: usize,
     pub hit_fired: bool,
     pub hit_frame: f32,
+    pub damage: f32,
+    pub range: f32,
 }
 
 /// Event sent when enemy attack animation reaches its hit frame.
@@ -62,6 +71,8 @@ pub struct AttackAnimationProgress {
 pub struct AttackHitEvent {
     pub attacker: Entity,
     pub damage: f32,
+    /// Reach of the attack that fired this hit
+    pub range: f32,
 }
 
 /// Finds AnimationPlayer in scene hierarchy and builds AnimationGraph.
@@ -123,7 +134,7 @@ pub fn setup_enemy_animations(
                     idle: idle_node,
                     walk: node_indices.1,
                     combat_idle: node_indices.2,
-                    attack: node_indices.3,
+                    attacks: node_indices.3,
                     hurt: node_indices.4,
                     death: node_indices.5,
                 },
@@ -162,12 +173,22 @@ fn find_animation_player_entity(
     None
 }
 
+/// Graph nodes of an enemy's clips: idle, walk, combat idle, attacks, hurt and death.
+type AnimationNodes = (
+    AnimationNodeIndex,
+    AnimationNodeIndex,
+    AnimationNodeIndex,
+    Vec<AnimationNodeIndex>,
+    Option<AnimationNodeIndex>,
+    AnimationNodeIndex,
+);
+
 /// Build animation graph from config.
 fn build_animation_graph(
     asset_server: &AssetServer,
     model_base: &str,
     config: &AnimationConfig,
-) -> (AnimationGraph, (AnimationNodeIndex, AnimationNodeIndex, AnimationNodeIndex, AnimationNodeIndex, Option<AnimationNodeIndex>, AnimationNodeIndex)) {
+) -> (AnimationGraph, AnimationNodes) {
     let mut graph = AnimationGraph::new();
 
     // Load animation clips
@@ -175,14 +196,22 @@ fn build_animation_graph(
     let walk_clip: Handle<AnimationClip> = asset_server.load(format!("{}#Animation{}", model_base, config.indices.walk));
     let combat_idle_idx = config.indices.combat_idle.unwrap_or(config.indices.idle);
     let combat_idle_clip: Handle<AnimationClip> = asset_server.load(format!("{}#Animation{}", model_base, combat_idle_idx));
-    let attack_clip: Handle<AnimationClip> = asset_server.load(format!("{}#Animation{}", model_base, config.indices.attack));
     let death_clip: Handle<AnimationClip> = asset_server.load(format!("{}#Animation{}", model_base, config.indices.death));
 
     // Add nodes to graph
     let idle_node = graph.add_clip(idle_clip, 1.0, graph.root);
     let walk_node = graph.add_clip(walk_clip, 1.0, graph.root);
     let combat_idle_node = graph.add_clip(combat_idle_clip, 1.0, graph.root);
-    let attack_node = graph.add_clip(attack_clip, 1.0, graph.root);
+
+    // One node per attack
+    let attack_nodes = config
+        .attack_clips()
+        .into_iter()
+        .map(|idx| {
+            let attack_clip: Handle<AnimationClip> = asset_server.load(format!("{}#Animation{}", model_base, idx));
+            graph.add_clip(attack_clip, 1.0, graph.root)
+        })
+        .collect();
     let death_node = graph.add_clip(death_clip, 1.0, graph.root);
 
     // Hurt is optional
@@ -191,28 +220,29 @@ fn build_animation_graph(
         graph.add_clip(hurt_clip, 1.0, graph.root)
     });
 
-    (graph, (idle_node, walk_node, combat_idle_node, attack_node, hurt_node, death_node))
+    (graph, (idle_node, walk_node, combat_idle_node, attack_nodes, hurt_node, death_node))
 }
 
 /// Maps AiState + context to AnimationState.
 pub fn sync_animation_state(
     mut query: Query<
-        (&AiState, &mut AnimationState, &EnemyStats, &Transform),
+        (&AiState, &mut AnimationState, &EnemyStats, &Transform, Option<&Wander>),
         (With<Enemy>, With<EnemyAnimations>, Without<OneShotTimer>),
     >,
-    player_query: Query<&Transform, (With<crate::player::Player>, Without<Enemy>)>,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
 ) {
     let Ok(player_transform) = player_query.get_single() else {
         return;
     };
 
-    for (ai_state, mut anim_state, stats, enemy_transform) in query.iter_mut() {
+    for (ai_state, mut anim_state, stats, enemy_transform, wander) in query.iter_mut() {
         // Don't change animation state if dying
         if *anim_state == AnimationState::Dying {
             continue;
         }
 
         let new_state = match ai_state {
+            AiState::Idle if wander.is_some_and(Wander::is_walking) => AnimationState::Walking,
             AiState::Idle => AnimationState::Idle,
             AiState::Chasing => AnimationState::Walking,
             AiState::Attacking => {
@@ -223,7 +253,7 @@ pub fn sync_animation_state(
                     0.0,
                     enemy_transform.translation.z - player_transform.translation.z,
                 ).length();
-                if distance <= stats.attack_range {
+                if distance <= stats.attack_exit_range() {
                     // The actual attack animation is triggered separately
                     AnimationState::CombatIdle
                 } else {
@@ -240,14 +270,20 @@ pub fn sync_animation_state(
 }
 
 /// Listens to DamageEvent and triggers hurt animation.
+///
+/// Getting hit mid-attack interrupts it: if the hit frame hasn't been reached
+/// yet, the blow is cancelled. Attacks whose hit already fired are unaffected.
 pub fn trigger_hurt_animation(
     mut commands: Commands,
     mut damage_events: EventReader<DamageEvent>,
-    mut query: Query<(Entity, &mut AnimationState, &EnemyType), With<Enemy>>,
+    mut query: Query<
+        (Entity, &mut AnimationState, &EnemyType, Option<&AttackAnimationProgress>),
+        With<Enemy>,
+    >,
     registry: Res<EnemyRegistry>,
 ) {
     for event in damage_events.read() {
-        if let Ok((entity, mut anim_state, enemy_type)) = query.get_mut(event.target) {
+        if let Ok((entity, mut anim_state, enemy_type, attack_progress)) = query.get_mut(event.target) {
             // Don't interrupt dying
             if *anim_state == AnimationState::Dying {
                 continue;
@@ -268,6 +304,14 @@ pub fn trigger_hurt_animation(
 
             *anim_state = AnimationState::Hurt;
 
+            // Cancel the attack so the hurt timer doesn't drive its hit frame
+            if let Some(progress) = attack_progress {
+                if !progress.hit_fired {
+                    debug!("Enemy {:?} attack interrupted", entity);
+                }
+                commands.entity(entity).remove::<AttackAnimationProgress>();
+            }
+
             commands.entity(entity).insert(OneShotTimer {
                 timer: Timer::from_seconds(hurt_duration, TimerMode::Once),
                 return_to,
@@ -276,16 +320,65 @@ pub fn trigger_hurt_animation(
     }
 }
 
+/// Aborts attack wind-ups when the player backs out of the attack's reach.
+///
+/// Only attacks that haven't reached their hit frame are cancelled; the enemy
+/// briefly recovers in its combat stance and goes back to chasing.
+pub fn cancel_out_of_range_attacks(
+    mut commands: Commands,
+    mut query: Query<
+        (Entity, &mut AnimationState, &mut AiState, &AttackAnimationProgress, &Transform),
+        With<Enemy>,
+    >,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for (entity, mut anim_state, mut ai_state, progress, enemy_transform) in query.iter_mut() {
+        if progress.hit_fired
+            || *anim_state != AnimationState::Attacking
+            || *ai_state == AiState::Dying
+        {
+            continue;
+        }
+
+        // Same distance check the hit itself uses
+        let distance = enemy_transform.translation.distance(player_transform.translation);
+        if distance <= progress.range {
+            continue;
+        }
+
+        debug!("Enemy {:?} attack cancelled, player out of range", entity);
+        *anim_state = AnimationState::CombatIdle;
+        *ai_state = AiState::Chasing;
+        commands
+            .entity(entity)
+            .remove::<AttackAnimationProgress>()
+            .insert(OneShotTimer {
+                timer: Timer::from_seconds(ATTACK_CANCEL_RECOVERY, TimerMode::Once),
+                return_to: AnimationState::Walking,
+            });
+    }
+}
+
 /// Triggers attack animation when AI enters attack state with cooldown ready.
+///
+/// Picks one of the enemy's attacks based on distance to the player.
 pub fn trigger_attack_animation(
     mut commands: Commands,
     mut query: Query<
-        (Entity, &AiState, &mut AnimationState, &EnemyType),
+        (Entity, &AiState, &mut AnimationState, &EnemyAttacks, &Transform),
         (With<Enemy>, With<EnemyAnimations>, With<AttackReady>, Without<OneShotTimer>),
     >,
-    registry: Res<EnemyRegistry>,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
 ) {
-    for (entity, ai_state, mut anim_state, enemy_type) in query.iter_mut() {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for (entity, ai_state, mut anim_state, attacks, enemy_transform) in query.iter_mut() {
         // Only trigger attack animation when:
         // 1. AI is in attacking state
         // 2. Not already attacking or hurt
@@ -301,30 +394,34 @@ pub fn trigger_attack_animation(
             continue;
         }
 
-        // Get attack hit frame from config
-        let hit_frame = registry.get(&enemy_type.0)
-            .and_then(|def| def.animations.as_ref())
-            .map(|cfg| cfg.attack_hit_frame)
-            .unwrap_or(0.5);
+        // Choose an attack (horizontal distance, consistent with AI systems)
+        let distance = Vec3::new(
+            enemy_transform.translation.x - player_transform.translation.x,
+            0.0,
+            enemy_transform.translation.z - player_transform.translation.z,
+        ).length();
+        let attack_index = attacks.select(distance);
+        let Some(attack) = attacks.0.get(attack_index) else {
+            commands.entity(entity).remove::<AttackReady>();
+            continue;
+        };
 
         *anim_state = AnimationState::Attacking;
 
-        // Use attack cooldown as animation duration estimate
-        let attack_duration = registry.get(&enemy_type.0)
-            .map(|def| def.attack_cooldown * 0.6) // Animation is shorter than full cooldown
-            .unwrap_or(0.6);
-
         // Remove AttackReady marker and add animation components
         commands.entity(entity)
             .remove::<AttackReady>()
             .insert((
                 OneShotTimer {
-                    timer: Timer::from_seconds(attack_duration, TimerMode::Once),
+                    timer: Timer::from_seconds(attack.duration, TimerMode::Once),
                     return_to: AnimationState::CombatIdle,
                 },
                 AttackAnimationProgress {
+                    attack_index,
                     hit_fired: false,
-                    hit_frame,
+                    hit_frame: attack.hit_frame,
+                    damage: attack.damage,
+                    range: attack.range,
                 },
             ));
     }
@@ -333,12 +430,18 @@ pub fn trigger_attack_animation(
 /// Applies AnimationState changes to AnimationPlayer.
 pub fn play_animations(
     mut query: Query<
-        (&AnimationState, &PreviousAnimationState, &AnimationLink, &EnemyAnimations),
+        (
+            &AnimationState,
+            &PreviousAnimationState,
+            &AnimationLink,
+            &EnemyAnimations,
+            Option<&AttackAnimationProgress>,
+        ),
         Changed<AnimationState>,
     >,
     mut animation_players: Query<&mut AnimationPlayer>,
 ) {
-    for (anim_state, prev_state, link, animations) in query.iter_mut() {
+    for (anim_state, prev_state, link, animations, attack_progress) in query.iter_mut() {
         // Only play if state changed
         if *anim_state == prev_state.0 {
             continue;
@@ -352,7 +455,13 @@ pub fn play_animations(
             AnimationState::Idle => (animations.idle, true),
             AnimationState::Walking => (animations.walk, true),
             AnimationState::CombatIdle => (animations.combat_idle, true),
-            AnimationState::Attacking => (animations.attack, false),
+            AnimationState::Attacking => {
+                let index = attack_progress.map_or(0, |progress| progress.attack_index);
+                let Some(&attack_node) = animations.attacks.get(index).or(animations.attacks.first()) else {
+                    continue; // No attack animation, skip
+                };
+                (attack_node, false)
+            }
             AnimationState::Hurt => {
                 if let Some(hurt_node) = animations.hurt {
                     (hurt_node, false)
@@ -403,10 +512,10 @@ pub fn update_oneshot_timers(
 
 /// Fires AttackHitEvent when attack animation reaches configured hit frame.
 pub fn detect_attack_hit(
-    mut query: Query<(Entity, &mut AttackAnimationProgress, &OneShotTimer, &EnemyStats)>,
+    mut query: Query<(Entity, &mut AttackAnimationProgress, &OneShotTimer)>,
     mut attack_hit_events: EventWriter<AttackHitEvent>,
 ) {
-    for (entity, mut progress, oneshot, stats) in query.iter_mut() {
+    for (entity, mut progress, oneshot) in query.iter_mut() {
         if progress.hit_fired {
             continue;
         }
@@ -418,7 +527,8 @@ pub fn detect_attack_hit(
             progress.hit_fired = true;
             attack_hit_events.send(AttackHitEvent {
                 attacker: entity,
-                damage: stats.damage,
+                damage: progress.damage,
+                range: progress.range,
             });
         }
     }
@@ -0,0 +1,103 @@
+//! Line-of-sight occlusion for enemy-sourced sounds.
+//!
+//! An `AudioOcclusion` on an enemy tracks whether level geometry blocks the
+//! line from the player's camera to it, so a sound emitter on the enemy can
+//! apply its `volume()` and `low_pass_hz()` to muffle enemies behind walls.
+//! Emitters opt in by inserting the component; enemies don't play sounds of
+//! their own yet, so none do and the checks stay idle. Ray casts run at a
+//! reduced rate and the result fades in and out so sounds don't snap when
+//! passing a doorway.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::player::PlayerCamera;
+
+/// Seconds between occlusion ray casts.
+const CHECK_INTERVAL: f32 = 0.1;
+
+/// Enemies further away than this aren't checked (too far to hear clearly).
+const MAX_CHECK_DISTANCE: f32 = 40.0;
+
+/// Height above the enemy's origin (at its feet) that sound comes from.
+const SOURCE_HEIGHT: f32 = 1.0;
+
+/// Volume multiplier when fully occluded.
+const OCCLUDED_VOLUME: f32 = 0.35;
+
+/// Low-pass cutoff in Hz when fully occluded and when in the clear.
+const OCCLUDED_LOW_PASS_HZ: f32 = 900.0;
+const OPEN_LOW_PASS_HZ: f32 = 20000.0;
+
+/// How fast `amount` moves toward its target, per second.
+const FADE_SPEED: f32 = 6.0;
+
+/// Occlusion of an enemy's sounds from the player's point of view.
+#[derive(Component, Default)]
+pub struct AudioOcclusion {
+    /// Whether the last check found geometry between camera and enemy
+    pub occluded: bool,
+    /// Smoothed occlusion (0.0 = clear, 1.0 = fully occluded)
+    pub amount: f32,
+}
+
+impl AudioOcclusion {
+    /// Volume multiplier for the enemy's sounds.
+    pub fn volume(&self) -> f32 {
+        1.0 + (OCCLUDED_VOLUME - 1.0) * self.amount
+    }
+
+    /// Low-pass filter cutoff in Hz for the enemy's sounds.
+    pub fn low_pass_hz(&self) -> f32 {
+        OPEN_LOW_PASS_HZ + (OCCLUDED_LOW_PASS_HZ - OPEN_LOW_PASS_HZ) * self.amount
+    }
+}
+
+/// Ray cast from the camera to each nearby tracked enemy to see if walls are
+/// in the way.
+///
+/// Only fixed colliders (level geometry) occlude; other enemies and the
+/// player don't.
+pub fn check_audio_occlusion(
+    time: Res<Time>,
+    mut since_check: Local<f32>,
+    rapier_context: Query<&RapierContext>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    mut enemy_query: Query<(&GlobalTransform, &mut AudioOcclusion)>,
+) {
+    *since_check += time.delta_secs();
+    if *since_check < CHECK_INTERVAL {
+        return;
+    }
+    *since_check = 0.0;
+
+    let (Ok(context), Ok(camera_transform)) =
+        (rapier_context.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
+    let listener = camera_transform.translation();
+    let filter = QueryFilter::only_fixed().exclude_sensors();
+
+    for (transform, mut occlusion) in enemy_query.iter_mut() {
+        let offset = transform.translation() + Vec3::Y * SOURCE_HEIGHT - listener;
+        let distance = offset.length();
+        if distance > MAX_CHECK_DISTANCE || distance <= f32::EPSILON {
+            continue;
+        }
+
+        occlusion.occluded = context
+            .cast_ray(listener, offset / distance, distance, true, filter)
+            .is_some();
+    }
+}
+
+/// Fade each enemy's occlusion amount toward its latest check.
+pub fn fade_audio_occlusion(time: Res<Time>, mut query: Query<&mut AudioOcclusion>) {
+    let step = FADE_SPEED * time.delta_secs();
+    for mut occlusion in query.iter_mut() {
+        let target = if occlusion.occluded { 1.0 } else { 0.0 };
+        let amount = occlusion.amount;
+        occlusion.amount = amount + (target - amount).clamp(-step, step);
+    }
+}
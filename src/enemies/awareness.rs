@@ -0,0 +1,228 @@
+//! Floating awareness indicators over enemies.
+//!
+//! A "!" pops up when an enemy spots the player and starts chasing, and a
+//! "?" when it loses track of them. Indicators are camera-facing billboards
+//! built from a few unlit quads, follow their enemy, and fade out shortly
+//! after appearing.
+
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::*;
+
+use super::components::{AiState, Enemy};
+use crate::player::PlayerCamera;
+use crate::world::LevelGeometry;
+
+/// Height of the indicator above the enemy's origin.
+const INDICATOR_HEIGHT: f32 = 2.4;
+
+/// Seconds over which an indicator fades out at the end of its lifetime.
+const FADE_DURATION: f32 = 0.4;
+
+/// Tracks AI state transitions for an enemy's awareness indicator.
+#[derive(Component, Default)]
+pub struct Awareness {
+    /// AI state seen on the previous update
+    pub last_state: AiState,
+    /// Currently displayed indicator, if any
+    pub indicator: Option<Entity>,
+}
+
+/// Which glyph an indicator shows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AwarenessKind {
+    /// Enemy spotted the player ("!")
+    Alerted,
+    /// Enemy lost the player ("?")
+    Searching,
+}
+
+impl AwarenessKind {
+    /// Total seconds the indicator stays visible, including the fade.
+    fn lifetime(self) -> f32 {
+        match self {
+            // Fades soon after the enemy fully engages
+            AwarenessKind::Alerted => 1.0,
+            AwarenessKind::Searching => 2.5,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            AwarenessKind::Alerted => Color::srgb(1.0, 0.25, 0.15),
+            AwarenessKind::Searching => Color::srgb(1.0, 0.85, 0.3),
+        }
+    }
+
+    /// Glyph pieces as (center, size) rectangles in the billboard's local space.
+    fn glyph(self) -> &'static [(Vec2, Vec2)] {
+        const ALERTED: &[(Vec2, Vec2)] = &[
+            // Bar
+            (Vec2::new(0.0, 0.1), Vec2::new(0.08, 0.3)),
+            // Dot
+            (Vec2::new(0.0, -0.15), Vec2::new(0.08, 0.08)),
+        ];
+        const SEARCHING: &[(Vec2, Vec2)] = &[
+            // Top of the hook
+            (Vec2::new(0.0, 0.25), Vec2::new(0.24, 0.07)),
+            // Right side of the hook
+            (Vec2::new(0.085, 0.165), Vec2::new(0.07, 0.17)),
+            // Middle bar
+            (Vec2::new(0.035, 0.08), Vec2::new(0.17, 0.07)),
+            // Stem
+            (Vec2::new(0.0, 0.015), Vec2::new(0.07, 0.1)),
+            // Dot
+            (Vec2::new(0.0, -0.12), Vec2::new(0.07, 0.07)),
+        ];
+
+        match self {
+            AwarenessKind::Alerted => ALERTED,
+            AwarenessKind::Searching => SEARCHING,
+        }
+    }
+}
+
+/// A floating indicator following an enemy.
+#[derive(Component)]
+pub struct AwarenessIndicator {
+    /// Enemy this indicator belongs to
+    pub enemy: Entity,
+    /// Seconds until the indicator disappears
+    pub remaining: f32,
+    /// Material shared by all glyph pieces (faded via alpha)
+    pub material: Handle<StandardMaterial>,
+}
+
+/// Enemies whose AI state changed this frame.
+type AiStateChanged = (With<Enemy>, Changed<AiState>);
+
+/// Spawn or replace indicators when an enemy's AI state changes.
+pub fn update_awareness(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut enemy_query: Query<(Entity, &AiState, &mut Awareness), AiStateChanged>,
+) {
+    for (enemy, ai_state, mut awareness) in enemy_query.iter_mut() {
+        let kind = match (&awareness.last_state, ai_state) {
+            (AiState::Idle, AiState::Chasing) => Some(AwarenessKind::Alerted),
+            (AiState::Chasing, AiState::Idle) => Some(AwarenessKind::Searching),
+            _ => None,
+        };
+        awareness.last_state = ai_state.clone();
+
+        // Dying enemies drop their indicator immediately
+        if *ai_state == AiState::Dying {
+            despawn_indicator(&mut commands, &mut awareness);
+            continue;
+        }
+
+        let Some(kind) = kind else {
+            continue;
+        };
+
+        despawn_indicator(&mut commands, &mut awareness);
+
+        awareness.indicator = Some(spawn_indicator(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            enemy,
+            kind,
+        ));
+    }
+}
+
+/// Remove an enemy's current indicator (it may already have expired).
+fn despawn_indicator(commands: &mut Commands, awareness: &mut Awareness) {
+    if let Some(indicator) = awareness.indicator.take() {
+        if let Some(entity_commands) = commands.get_entity(indicator) {
+            entity_commands.despawn_recursive();
+        }
+    }
+}
+
+/// Spawn an indicator billboard for an enemy.
+fn spawn_indicator(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    enemy: Entity,
+    kind: AwarenessKind,
+) -> Entity {
+    let material = materials.add(StandardMaterial {
+        base_color: kind.color(),
+        unlit: true,
+        fog_enabled: false,
+        alpha_mode: AlphaMode::Blend,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    });
+
+    commands
+        .spawn((
+            AwarenessIndicator {
+                enemy,
+                remaining: kind.lifetime(),
+                material: material.clone(),
+            },
+            Transform::default(),
+            Visibility::Hidden, // Shown once positioned above the enemy
+            LevelGeometry,
+        ))
+        .with_children(|parent| {
+            for (center, size) in kind.glyph() {
+                parent.spawn((
+                    Mesh3d(meshes.add(Rectangle::new(size.x, size.y))),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_xyz(center.x, center.y, 0.0),
+                    NotShadowCaster,
+                ));
+            }
+        })
+        .id()
+}
+
+/// Keep indicators above their enemy, facing the camera, and fade them out.
+pub fn update_awareness_indicators(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    enemy_query: Query<&GlobalTransform, With<Enemy>>,
+    mut indicator_query: Query<(Entity, &mut AwarenessIndicator, &mut Transform, &mut Visibility)>,
+) {
+    let camera_pos = camera_query.get_single().ok().map(|t| t.translation());
+
+    for (entity, mut indicator, mut transform, mut visibility) in indicator_query.iter_mut() {
+        indicator.remaining -= time.delta_secs();
+
+        // Clean up with the enemy or once the lifetime ends
+        let Ok(enemy_transform) = enemy_query.get(indicator.enemy) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        if indicator.remaining <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let position = enemy_transform.translation() + Vec3::Y * INDICATOR_HEIGHT;
+        transform.translation = position;
+
+        // Billboard: the quads face +Z, so point -Z away from the camera
+        if let Some(camera_pos) = camera_pos {
+            let away = position - camera_pos;
+            if away.length_squared() > 0.0001 {
+                transform.look_to(away, Vec3::Y);
+            }
+        }
+        *visibility = Visibility::Visible;
+
+        if indicator.remaining < FADE_DURATION {
+            if let Some(material) = materials.get_mut(&indicator.material) {
+                material.base_color.set_alpha(indicator.remaining / FADE_DURATION);
+            }
+        }
+    }
+}
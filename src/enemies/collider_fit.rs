@@ -0,0 +1,87 @@
+//! Automatic collider sizing from loaded enemy models.
+//!
+//! Enemies without an explicit `collider` in their definition spawn with a
+//! default capsule and the `NeedsColliderFit` marker. Once the model's meshes
+//! have loaded, the capsule is refitted to the model's bounding box.
+
+use bevy::prelude::*;
+use bevy::render::mesh::MeshAabb;
+use bevy_rapier3d::prelude::*;
+
+use super::components::Enemy;
+
+/// Marker for enemies whose collider should be fitted to their model once it loads.
+#[derive(Component)]
+pub struct NeedsColliderFit;
+
+/// Enemies still waiting for a fitted collider.
+type PendingColliderFit = (With<Enemy>, With<NeedsColliderFit>);
+
+/// Fit a capsule collider to each pending enemy's model bounds.
+///
+/// Bounds are computed in the enemy's local space, so the definition's
+/// `scale` is applied by the physics engine just like explicit colliders.
+pub fn fit_enemy_colliders(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    enemy_query: Query<(Entity, &GlobalTransform), PendingColliderFit>,
+    children_query: Query<&Children>,
+    mesh_query: Query<(&Mesh3d, &GlobalTransform)>,
+) {
+    for (enemy_entity, enemy_global) in enemy_query.iter() {
+        let world_to_local = enemy_global.affine().inverse();
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        let mut found_mesh = false;
+        let mut all_loaded = true;
+
+        for descendant in children_query.iter_descendants(enemy_entity) {
+            let Ok((mesh, mesh_global)) = mesh_query.get(descendant) else {
+                continue;
+            };
+            found_mesh = true;
+
+            let Some(aabb) = meshes.get(&mesh.0).and_then(|mesh| mesh.compute_aabb()) else {
+                all_loaded = false;
+                break;
+            };
+
+            let center = Vec3::from(aabb.center);
+            let half_extents = Vec3::from(aabb.half_extents);
+            for x in [-1.0, 1.0] {
+                for y in [-1.0, 1.0] {
+                    for z in [-1.0, 1.0] {
+                        let corner = center + half_extents * Vec3::new(x, y, z);
+                        let local = world_to_local.transform_point3(mesh_global.transform_point(corner));
+                        min = min.min(local);
+                        max = max.max(local);
+                    }
+                }
+            }
+        }
+
+        // Scene not spawned or meshes still loading - try again next frame
+        if !found_mesh || !all_loaded {
+            continue;
+        }
+
+        let size = max - min;
+        let center = (min + max) / 2.0;
+        let radius = ((size.x + size.z) / 4.0).max(0.05);
+        let half_height = (size.y / 2.0 - radius).max(0.0);
+
+        commands
+            .entity(enemy_entity)
+            .remove::<NeedsColliderFit>()
+            .insert(Collider::compound(vec![(
+                center,
+                Quat::IDENTITY,
+                Collider::capsule_y(half_height, radius),
+            )]));
+
+        info!(
+            "Fitted collider for enemy {:?}: half_height {:.2}, radius {:.2}",
+            enemy_entity, half_height, radius
+        );
+    }
+}
@@ -10,6 +10,16 @@ pub struct Enemy;
 #[derive(Component, Clone)]
 pub struct EnemyType(pub String);
 
+/// Where a level-placed enemy spawned: floor layer index and grid tile.
+///
+/// Stays the same every time the level is built, so it identifies the enemy
+/// across save and load where `Entity` ids don't.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpawnPoint {
+    pub floor: usize,
+    pub tile: (i32, i32),
+}
+
 /// AI state machine for enemy behavior.
 #[derive(Component, Default, PartialEq, Clone, Debug)]
 pub enum AiState {
@@ -24,6 +34,72 @@ pub enum AiState {
     Dying,
 }
 
+/// Frames between AI updates for dormant (distant, idle) enemies.
+pub const AI_LOD_INTERVAL: u32 = 8;
+
+/// Dormant enemies wake when the player is within this multiple of their detection range.
+pub const AI_LOD_WAKE_FACTOR: f32 = 2.0;
+
+/// Distance-based AI level of detail.
+///
+/// Idle enemies far beyond their detection range go dormant and only re-check
+/// the player distance every `AI_LOD_INTERVAL` frames. Engaged enemies always
+/// run at full rate.
+#[derive(Component)]
+pub struct AiLod {
+    /// Frame offset so dormant enemies don't all update on the same frame
+    pub phase: u32,
+    /// Whether the enemy is running full-rate AI
+    pub active: bool,
+}
+
+impl Default for AiLod {
+    fn default() -> Self {
+        Self {
+            phase: rand::random::<u32>() % AI_LOD_INTERVAL,
+            active: true,
+        }
+    }
+}
+
+impl AiLod {
+    /// Whether a dormant enemy gets its reduced-rate check on this frame.
+    pub fn is_tick_frame(&self, frame: u32) -> bool {
+        frame.wrapping_add(self.phase).is_multiple_of(AI_LOD_INTERVAL)
+    }
+}
+
+/// Radius around the home position that wandering enemies stay within.
+pub const WANDER_RADIUS: f32 = 4.0;
+
+/// Idle wandering: stroll to a nearby point, pause, repeat.
+///
+/// Only present on enemies whose definition enables `wander`.
+#[derive(Component)]
+pub struct Wander {
+    /// Point the enemy wanders around (its spawn position)
+    pub home: Vec3,
+    /// Current stroll destination, if walking
+    pub target: Option<Vec3>,
+    /// Pause remaining when stopped, or time left to reach the target when walking
+    pub timer: f32,
+}
+
+impl Wander {
+    pub fn new(home: Vec3) -> Self {
+        Self {
+            home,
+            target: None,
+            timer: rand::random::<f32>() * 3.0,
+        }
+    }
+
+    /// Whether the enemy is currently strolling.
+    pub fn is_walking(&self) -> bool {
+        self.target.is_some()
+    }
+}
+
 /// Enemy stats loaded from RON data files.
 #[derive(Component, Clone)]
 pub struct EnemyStats {
@@ -33,6 +109,65 @@ pub struct EnemyStats {
     pub detection_range: f32,
     pub attack_range: f32,
     pub attack_cooldown: f32,
+    /// Full width in degrees of the arc in front of the enemy that attacks can hit
+    pub attack_arc: f32,
+    /// How fast the enemy turns to face its target, in degrees per second
+    pub turn_rate: f32,
+    /// Full width in degrees of the cone in front of the enemy it can see the player in
+    pub vision_angle: f32,
+    /// Extra distance beyond `attack_range` before an attacking enemy resumes chasing
+    pub attack_range_buffer: f32,
+    /// Deepest drop the enemy will walk down; anything deeper is treated as a ledge
+    pub max_drop: f32,
+}
+
+impl EnemyStats {
+    /// Whether a target in `direction` (from the enemy) is inside the attack arc.
+    ///
+    /// `facing` is the direction the enemy model faces. Only the horizontal
+    /// components are compared.
+    pub fn in_attack_arc(&self, facing: Vec3, direction: Vec3) -> bool {
+        in_arc(self.attack_arc, facing, direction)
+    }
+
+    /// Distance at which an attacking enemy gives up and chases again.
+    ///
+    /// Larger than `attack_range` so a player hovering at the edge doesn't
+    /// make the enemy flicker between chasing and attacking.
+    pub fn attack_exit_range(&self) -> f32 {
+        self.attack_range + self.attack_range_buffer
+    }
+
+    /// Whether an enemy should be attacking a target `distance` away, given
+    /// whether it already is: it starts within `attack_range` and only stops
+    /// beyond `attack_exit_range`.
+    pub fn should_attack(&self, attacking: bool, distance: f32) -> bool {
+        if attacking {
+            distance <= self.attack_exit_range()
+        } else {
+            distance <= self.attack_range
+        }
+    }
+
+    /// Whether a target in `direction` (from the enemy) is inside the vision cone.
+    pub fn in_vision_cone(&self, facing: Vec3, direction: Vec3) -> bool {
+        in_arc(self.vision_angle, facing, direction)
+    }
+}
+
+/// Horizontal check of `direction` against an arc of `arc` degrees around `facing`.
+fn in_arc(arc: f32, facing: Vec3, direction: Vec3) -> bool {
+    if arc >= 360.0 {
+        return true;
+    }
+    let facing = Vec3::new(facing.x, 0.0, facing.z).normalize_or_zero();
+    let direction = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+    if direction == Vec3::ZERO {
+        // Target is on top of the enemy
+        return true;
+    }
+    let half_arc = (arc * 0.5).to_radians();
+    facing.dot(direction) >= half_arc.cos()
 }
 
 impl Default for EnemyStats {
@@ -44,7 +179,55 @@ impl Default for EnemyStats {
             detection_range: 8.0,
             attack_range: 2.0,
             attack_cooldown: 1.5,
+            attack_arc: 120.0,
+            turn_rate: 360.0,
+            vision_angle: 360.0,
+            attack_range_buffer: 0.5,
+            max_drop: 1.0,
+        }
+    }
+}
+
+/// Resolved parameters for one of an enemy's attacks.
+#[derive(Clone, Debug)]
+pub struct AttackProfile {
+    pub damage: f32,
+    pub range: f32,
+    /// 0.0-1.0, fraction of the animation at which damage applies
+    pub hit_frame: f32,
+    /// Attack animation length in seconds
+    pub duration: f32,
+}
+
+/// All attacks an enemy can perform, in the same order as its attack animations.
+#[derive(Component, Clone)]
+pub struct EnemyAttacks(pub Vec<AttackProfile>);
+
+impl EnemyAttacks {
+    /// Pick an attack for the given distance to the target.
+    ///
+    /// Chooses randomly among attacks that can reach; falls back to the
+    /// longest-reaching attack if none can.
+    pub fn select(&self, distance: f32) -> usize {
+        let in_range: Vec<usize> = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, attack)| distance <= attack.range)
+            .map(|(index, _)| index)
+            .collect();
+
+        if in_range.is_empty() {
+            return self
+                .0
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.range.total_cmp(&b.range))
+                .map(|(index, _)| index)
+                .unwrap_or(0);
         }
+
+        in_range[rand::random::<usize>() % in_range.len()]
     }
 }
 
@@ -72,3 +255,81 @@ impl Default for DeathTimer {
 /// Added by AI when attack timer finishes, removed by animation system after triggering.
 #[derive(Component)]
 pub struct AttackReady;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Offset from an enemy at the origin to a player standing at `x, z`.
+    fn to_player(x: f32, z: f32) -> Vec3 {
+        Vec3::new(x, 1.0, z)
+    }
+
+    #[test]
+    fn player_behind_is_outside_attack_arc() {
+        let stats = EnemyStats::default();
+        // Enemy models face +Z, as in the hit check
+        let facing = Transform::default().back().as_vec3();
+
+        assert!(stats.in_attack_arc(facing, to_player(0.0, 1.5)));
+        assert!(!stats.in_attack_arc(facing, to_player(0.0, -1.5)));
+        assert!(!stats.in_attack_arc(facing, to_player(-1.0, -1.0)));
+    }
+
+    #[test]
+    fn attack_arc_edges() {
+        let stats = EnemyStats {
+            attack_arc: 120.0,
+            ..default()
+        };
+        let facing = Vec3::Z;
+        let at_angle = |degrees: f32| {
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            Vec3::new(sin, 0.0, cos)
+        };
+
+        assert!(stats.in_attack_arc(facing, at_angle(55.0)));
+        assert!(stats.in_attack_arc(facing, at_angle(-55.0)));
+        assert!(!stats.in_attack_arc(facing, at_angle(65.0)));
+        assert!(!stats.in_attack_arc(facing, at_angle(90.0)));
+        // Straight above or below counts as on top of the enemy
+        assert!(stats.in_attack_arc(facing, Vec3::Y));
+    }
+
+    #[test]
+    fn attack_state_does_not_thrash_at_range_edge() {
+        let stats = EnemyStats {
+            attack_range: 2.0,
+            attack_range_buffer: 0.5,
+            ..default()
+        };
+
+        // Player hovering either side of attack_range, within the buffer
+        let mut attacking = false;
+        let mut changes = 0;
+        for step in 0..40 {
+            let distance = if step % 2 == 0 { 1.9 } else { 2.3 };
+            let next = stats.should_attack(attacking, distance);
+            if next != attacking {
+                changes += 1;
+            }
+            attacking = next;
+        }
+        assert!(attacking);
+        assert_eq!(changes, 1, "should start attacking once and stay");
+
+        // Backing off past the buffer resumes the chase
+        assert!(!stats.should_attack(true, 2.6));
+        // Approaching from outside doesn't attack until in range
+        assert!(!stats.should_attack(false, 2.3));
+    }
+
+    #[test]
+    fn full_circle_arc_hits_behind() {
+        let stats = EnemyStats {
+            attack_arc: 360.0,
+            ..default()
+        };
+        assert!(stats.in_attack_arc(Vec3::Z, Vec3::NEG_Z));
+    }
+}
@@ -6,7 +6,10 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-use super::components::EnemyStats;
+use super::components::{AttackProfile, EnemyAttacks, EnemyStats};
+use crate::combat::HitFx;
+use crate::inventory::PickupKind;
+use crate::world::DataLoadError;
 
 /// Animation clip indices for an enemy type.
 #[derive(Deserialize, Clone, Debug, Default)]
@@ -19,12 +22,42 @@ pub struct AnimationIndices {
     pub death: u32,
 }
 
+/// A single attack an enemy can perform (e.g. a slash or a lunge).
+///
+/// Unset fields fall back to the enemy's top-level values.
+#[derive(Deserialize, Clone, Debug)]
+pub struct AttackDef {
+    pub animation: u32,
+    #[serde(default)]
+    pub damage: Option<f32>,
+    #[serde(default)]
+    pub range: Option<f32>,
+    #[serde(default)]
+    pub hit_frame: Option<f32>, // 0.0-1.0, when damage applies
+    #[serde(default)]
+    pub duration: Option<f32>,  // seconds
+}
+
 /// Animation configuration for an enemy type.
 #[derive(Deserialize, Clone, Debug, Default)]
 pub struct AnimationConfig {
     pub indices: AnimationIndices,
     pub attack_hit_frame: f32, // 0.0-1.0, when damage applies
     pub hurt_duration: f32,    // seconds
+    /// Optional list of attacks. When empty, `indices.attack` is the only attack.
+    #[serde(default)]
+    pub attacks: Vec<AttackDef>,
+}
+
+impl AnimationConfig {
+    /// Animation clip index for each attack, in attack order.
+    pub fn attack_clips(&self) -> Vec<u32> {
+        if self.attacks.is_empty() {
+            vec![self.indices.attack]
+        } else {
+            self.attacks.iter().map(|attack| attack.animation).collect()
+        }
+    }
 }
 
 /// Collider configuration for an enemy type.
@@ -43,6 +76,39 @@ impl Default for ColliderConfig {
     }
 }
 
+fn default_attack_arc() -> f32 {
+    120.0
+}
+
+fn default_turn_rate() -> f32 {
+    360.0
+}
+
+fn default_vision_angle() -> f32 {
+    360.0
+}
+
+fn default_attack_range_buffer() -> f32 {
+    0.5
+}
+
+fn default_max_drop() -> f32 {
+    1.0
+}
+
+fn default_drop_chance() -> f32 {
+    1.0
+}
+
+/// A possible loot drop when the enemy dies.
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootDrop {
+    pub item: PickupKind,
+    /// Probability of dropping (0.0-1.0)
+    #[serde(default = "default_drop_chance")]
+    pub chance: f32,
+}
+
 /// Enemy definition loaded from RON file.
 #[derive(Deserialize, Clone, Debug)]
 pub struct EnemyDefinition {
@@ -52,27 +118,109 @@ pub struct EnemyDefinition {
     pub move_speed: f32,
     pub detection_range: f32,
     pub attack_range: f32,
+    /// Extra distance beyond the attack range before the enemy resumes chasing
+    #[serde(default = "default_attack_range_buffer")]
+    pub attack_range_buffer: f32,
     pub attack_cooldown: f32,
+    /// Width in degrees of the frontal arc attacks can hit
+    #[serde(default = "default_attack_arc")]
+    pub attack_arc: f32,
+    /// Turn speed in degrees per second (high values snap to face the player)
+    #[serde(default = "default_turn_rate")]
+    pub turn_rate: f32,
+    /// Width in degrees of the forward cone the enemy can spot the player in
+    #[serde(default = "default_vision_angle")]
+    pub vision_angle: f32,
+    /// Deepest drop the enemy will walk down instead of stopping at the ledge
+    #[serde(default = "default_max_drop")]
+    pub max_drop: f32,
     pub model_path: String,
     pub scale: f32,
+    /// Explicit collider. When omitted, the collider is fitted to the model bounds.
     #[serde(default)]
     pub collider: Option<ColliderConfig>,
     #[serde(default)]
     pub animations: Option<AnimationConfig>,
+    #[serde(default)]
+    pub drops: Vec<LootDrop>,
+    /// Idle enemies stroll around their spawn point until they detect the player.
+    #[serde(default)]
+    pub wander: bool,
+    /// Particles emitted when hit
+    #[serde(default)]
+    pub hit_fx: HitFx,
+    /// Fraction of knockback ignored (0.0 = full knockback, 1.0 = immovable)
+    #[serde(default)]
+    pub knockback_resistance: f32,
+    /// Experience awarded to the player for the kill
+    #[serde(default)]
+    pub experience: u32,
 }
 
 impl EnemyDefinition {
     /// Convert to EnemyStats component.
+    ///
+    /// The attack range is widened to the longest attack so the AI engages
+    /// from a distance where at least one attack can land.
     pub fn to_stats(&self) -> EnemyStats {
+        let attack_range = self
+            .to_attacks()
+            .0
+            .iter()
+            .map(|attack| attack.range)
+            .fold(self.attack_range, f32::max);
+
         EnemyStats {
             max_health: self.max_health,
             damage: self.damage,
             move_speed: self.move_speed,
             detection_range: self.detection_range,
-            attack_range: self.attack_range,
+            attack_range,
             attack_cooldown: self.attack_cooldown,
+            attack_arc: self.attack_arc,
+            turn_rate: self.turn_rate,
+            vision_angle: self.vision_angle,
+            attack_range_buffer: self.attack_range_buffer,
+            max_drop: self.max_drop,
         }
     }
+
+    /// Convert to EnemyAttacks component.
+    ///
+    /// Enemies without an attack list get a single attack built from their
+    /// top-level damage, range and hit frame.
+    pub fn to_attacks(&self) -> EnemyAttacks {
+        let default_hit_frame = self
+            .animations
+            .as_ref()
+            .map(|cfg| cfg.attack_hit_frame)
+            .unwrap_or(0.5);
+        // Animation is shorter than full cooldown
+        let default_duration = self.attack_cooldown * 0.6;
+
+        let default_attack = AttackProfile {
+            damage: self.damage,
+            range: self.attack_range,
+            hit_frame: default_hit_frame,
+            duration: default_duration,
+        };
+
+        let attacks = match &self.animations {
+            Some(cfg) if !cfg.attacks.is_empty() => cfg
+                .attacks
+                .iter()
+                .map(|def| AttackProfile {
+                    damage: def.damage.unwrap_or(self.damage),
+                    range: def.range.unwrap_or(self.attack_range),
+                    hit_frame: def.hit_frame.unwrap_or(default_hit_frame),
+                    duration: def.duration.unwrap_or(default_duration),
+                })
+                .collect(),
+            _ => vec![default_attack],
+        };
+
+        EnemyAttacks(attacks)
+    }
 }
 
 /// Resource holding all loaded enemy definitions.
@@ -88,18 +236,33 @@ impl EnemyRegistry {
     }
 }
 
+/// Directory holding enemy definition files.
+pub const ENEMIES_DIR: &str = "assets/data/enemies";
+
 /// Load all enemy definitions from the assets/data/enemies/ directory.
 pub fn load_enemy_definitions(mut registry: ResMut<EnemyRegistry>) {
-    let enemies_dir = Path::new("assets/data/enemies");
+    for e in read_enemy_definitions(Path::new(ENEMIES_DIR), &mut registry) {
+        error!("Skipping enemy definition: {}", e);
+    }
+}
 
+/// Read every enemy definition in `enemies_dir` into the registry.
+///
+/// The file stem is the enemy type id. Files that fail to load are skipped,
+/// and an error for each is returned so callers can log or report them.
+pub fn read_enemy_definitions(
+    enemies_dir: &Path,
+    registry: &mut EnemyRegistry,
+) -> Vec<DataLoadError> {
+    let mut errors = Vec::new();
     if !enemies_dir.exists() {
         warn!("Enemy definitions directory not found: {:?}", enemies_dir);
-        return;
+        return errors;
     }
 
     let Ok(entries) = fs::read_dir(enemies_dir) else {
         warn!("Failed to read enemy definitions directory");
-        return;
+        return errors;
     };
 
     for entry in entries.flatten() {
@@ -118,13 +281,15 @@ pub fn load_enemy_definitions(mut registry: ResMut<EnemyRegistry>) {
                         info!("Loaded enemy definition: {} ({})", definition.name, enemy_type);
                         registry.definitions.insert(enemy_type, definition);
                     }
-                    Err(e) => {
-                        error!("Failed to parse enemy definition {:?}: {}", path, e);
-                    }
+                    Err(e) => errors.push(DataLoadError::ParseError {
+                        path: path.to_string_lossy().to_string(),
+                        details: e.to_string(),
+                    }),
                 },
-                Err(e) => {
-                    error!("Failed to read enemy definition {:?}: {}", path, e);
-                }
+                Err(e) => errors.push(DataLoadError::ReadError {
+                    path: path.to_string_lossy().to_string(),
+                    details: e.to_string(),
+                }),
             }
         }
     }
@@ -133,4 +298,5 @@ pub fn load_enemy_definitions(mut registry: ResMut<EnemyRegistry>) {
         "Loaded {} enemy definitions",
         registry.definitions.len()
     );
+    errors
 }
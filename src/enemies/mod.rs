@@ -2,13 +2,18 @@
 
 pub mod animation;
 mod ai;
+pub mod audio_occlusion;
+pub mod awareness;
+pub mod collider_fit;
 mod components;
 pub mod data;
 mod plugin;
 mod spawning;
+pub mod traps;
 
 pub use animation::AttackHitEvent;
 pub use components::*;
 pub use data::EnemyRegistry;
+pub use traps::TrapRegistry;
 pub use plugin::EnemyPlugin;
 // SpawnZone is deprecated - use monster grid in level files instead
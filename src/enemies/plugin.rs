@@ -4,8 +4,12 @@ use bevy::prelude::*;
 
 use super::ai;
 use super::animation;
+use super::audio_occlusion;
+use super::awareness;
+use super::collider_fit;
 use super::data::{load_enemy_definitions, EnemyRegistry};
-use crate::core::GameState;
+use super::traps::{self, load_trap_definitions, TrapRegistry};
+use crate::core::{GameState, PlayState};
 
 /// Enemy plugin - handles enemy spawning, AI, death, and animations.
 pub struct EnemyPlugin;
@@ -14,22 +18,69 @@ impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<EnemyRegistry>()
+            .init_resource::<TrapRegistry>()
             // Register animation events
             .add_event::<animation::AttackHitEvent>()
             // Load enemy definitions at startup (before level loading needs them)
-            .add_systems(Startup, load_enemy_definitions)
-            // AI systems run during gameplay
+            .add_systems(Startup, (load_enemy_definitions, load_trap_definitions))
+            // AI systems run during gameplay, and stop while a menu like the
+            // attribute screen is open
             .add_systems(
                 Update,
                 (
+                    ai::update_ai_lod,
                     ai::ai_detection,
+                    ai::ai_wander,
                     ai::ai_chase,
                     ai::ai_attack,
                     ai::handle_enemy_death,
                     ai::despawn_dead_enemies,
                 )
                     .chain()
-                    .run_if(in_state(GameState::InGame)),
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(in_state(PlayState::Exploring)),
+            )
+            // Traps fire on their own timers, independent of the chase AI
+            .add_systems(
+                Update,
+                (
+                    traps::trap_attack,
+                    traps::spin_blades,
+                    traps::update_projectiles,
+                    traps::destroy_traps,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(in_state(PlayState::Exploring)),
+            )
+            // Awareness indicators react to AI state changes
+            .add_systems(
+                Update,
+                (awareness::update_awareness, awareness::update_awareness_indicators)
+                    .chain()
+                    .after(ai::despawn_dead_enemies)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(in_state(PlayState::Exploring)),
+            )
+            // Muffle sounds from enemies behind walls, once any emitter
+            // tracks occlusion
+            .add_systems(
+                Update,
+                (
+                    audio_occlusion::check_audio_occlusion,
+                    audio_occlusion::fade_audio_occlusion,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(in_state(PlayState::Exploring))
+                    .run_if(any_with_component::<audio_occlusion::AudioOcclusion>),
+            )
+            // Fit colliders to models once their scenes load
+            .add_systems(
+                Update,
+                collider_fit::fit_enemy_colliders
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(in_state(PlayState::Exploring)),
             )
             // Animation systems run after AI systems
             .add_systems(
@@ -39,6 +90,7 @@ impl Plugin for EnemyPlugin {
                     animation::sync_animation_state,
                     animation::trigger_attack_animation,
                     animation::trigger_hurt_animation,
+                    animation::cancel_out_of_range_attacks,
                     animation::trigger_death_animation,
                     animation::play_animations,
                     animation::update_previous_animation_state,
@@ -47,7 +99,8 @@ impl Plugin for EnemyPlugin {
                 )
                     .chain()
                     .after(ai::ai_attack)
-                    .run_if(in_state(GameState::InGame)),
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(in_state(PlayState::Exploring)),
             );
     }
 }
@@ -0,0 +1,427 @@
+//! Stationary traps - spinning blades and dart launchers.
+//!
+//! Traps are placed from the level's monster grid like enemies and carry the
+//! `Enemy` marker so player attacks can target them, but they never move or
+//! run the chase/attack AI. Each trap fires on its own timer and deals damage
+//! through the regular `DamageEvent` pipeline. Traps marked `hurts_enemies`
+//! also damage enemies, so knocking an enemy into a blade can finish it off.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::components::{Enemy, EnemyStats};
+use crate::combat::{sweep_projectile, DamageEvent, Dead, Element, Health, HitFx};
+use crate::player::Player;
+use crate::world::{DataLoadError, LevelGeometry};
+
+/// How long a dart flies before despawning.
+const DART_LIFETIME: f32 = 3.0;
+
+/// Collision radius of a dart.
+const DART_RADIUS: f32 = 0.05;
+
+/// Blade spin speed in radians per second.
+const BLADE_SPIN_SPEED: f32 = 6.0;
+
+/// Thickness of a blade's sweeping bar.
+const BLADE_THICKNESS: f32 = 0.05;
+
+/// Largest height difference between a blade and a target it still hits:
+/// the blade's thickness plus the player's 1.6 unit height, so a blade
+/// never reaches a floor above or below.
+const BLADE_VERTICAL_REACH: f32 = BLADE_THICKNESS + 1.6;
+
+fn default_projectile_speed() -> f32 {
+    12.0
+}
+
+/// Kind of trap behaviour.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrapKind {
+    /// Spinning blade damaging everything within `range`.
+    Blade,
+    /// Launcher firing darts at the player when within `range`.
+    Dart,
+}
+
+/// Trap definition loaded from RON file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TrapDefinition {
+    pub name: String,
+    pub kind: TrapKind,
+    pub damage: f32,
+    /// Seconds between activations
+    pub fire_rate: f32,
+    /// Blade damage radius, or dart firing range
+    pub range: f32,
+    /// Traps with health can be destroyed; `None` makes them indestructible.
+    #[serde(default)]
+    pub max_health: Option<f32>,
+    #[serde(default = "default_projectile_speed")]
+    pub projectile_speed: f32,
+    /// Whether the trap also damages enemies, not just the player
+    #[serde(default)]
+    pub hurts_enemies: bool,
+}
+
+/// Resource holding all loaded trap definitions.
+#[derive(Resource, Default)]
+pub struct TrapRegistry {
+    pub definitions: HashMap<String, TrapDefinition>,
+}
+
+impl TrapRegistry {
+    /// Get a trap definition by type name.
+    pub fn get(&self, trap_type: &str) -> Option<&TrapDefinition> {
+        self.definitions.get(trap_type)
+    }
+}
+
+/// Directory holding trap definition files.
+pub const TRAPS_DIR: &str = "assets/data/traps";
+
+/// Load all trap definitions from the assets/data/traps/ directory.
+pub fn load_trap_definitions(mut registry: ResMut<TrapRegistry>) {
+    for e in read_trap_definitions(Path::new(TRAPS_DIR), &mut registry) {
+        error!("Skipping trap definition: {}", e);
+    }
+}
+
+/// Read every trap definition in `traps_dir` into the registry.
+///
+/// The file stem is the trap type id. Files that fail to load are skipped,
+/// and an error for each is returned so callers can log or report them.
+pub fn read_trap_definitions(
+    traps_dir: &Path,
+    registry: &mut TrapRegistry,
+) -> Vec<DataLoadError> {
+    let mut errors = Vec::new();
+    if !traps_dir.exists() {
+        warn!("Trap definitions directory not found: {:?}", traps_dir);
+        return errors;
+    }
+
+    let Ok(entries) = fs::read_dir(traps_dir) else {
+        warn!("Failed to read trap definitions directory");
+        return errors;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.extension().is_some_and(|ext| ext == "ron") {
+            let trap_type = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown")
+                .to_string();
+
+            match fs::read_to_string(&path) {
+                Ok(contents) => match ron::from_str::<TrapDefinition>(&contents) {
+                    Ok(definition) => {
+                        info!("Loaded trap definition: {} ({})", definition.name, trap_type);
+                        registry.definitions.insert(trap_type, definition);
+                    }
+                    Err(e) => errors.push(DataLoadError::ParseError {
+                        path: path.to_string_lossy().to_string(),
+                        details: e.to_string(),
+                    }),
+                },
+                Err(e) => errors.push(DataLoadError::ReadError {
+                    path: path.to_string_lossy().to_string(),
+                    details: e.to_string(),
+                }),
+            }
+        }
+    }
+
+    info!("Loaded {} trap definitions", registry.definitions.len());
+    errors
+}
+
+/// A stationary trap.
+#[derive(Component)]
+pub struct Trap {
+    pub kind: TrapKind,
+    pub damage: f32,
+    pub range: f32,
+    pub projectile_speed: f32,
+    pub hurts_enemies: bool,
+    /// Time between activations
+    pub timer: Timer,
+    /// Material of fired darts
+    pub dart_material: Handle<StandardMaterial>,
+}
+
+/// A dart fired by a trap.
+#[derive(Component)]
+pub struct Projectile {
+    /// Entity that fired the projectile (ignored by its collision checks)
+    pub source: Entity,
+    pub damage: f32,
+    pub velocity: Vec3,
+    /// Seconds before the projectile despawns
+    pub lifetime: f32,
+    /// Whether enemies in the projectile's path are damaged too
+    pub hurts_enemies: bool,
+}
+
+/// Spawn a trap at the given position.
+pub fn spawn_trap(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    definition: &TrapDefinition,
+    position: Vec3,
+) -> Entity {
+    let trap = Trap {
+        kind: definition.kind,
+        damage: definition.damage,
+        range: definition.range,
+        projectile_speed: definition.projectile_speed,
+        hurts_enemies: definition.hurts_enemies,
+        timer: Timer::from_seconds(definition.fire_rate, TimerMode::Repeating),
+        dart_material: material.clone(),
+    };
+
+    let mut entity = match definition.kind {
+        TrapKind::Blade => commands.spawn((
+            Enemy,
+            trap,
+            // Blade sweeps the damage radius, hub is the solid part
+            Mesh3d(meshes.add(Cuboid::new(definition.range * 2.0, BLADE_THICKNESS, 0.2))),
+            MeshMaterial3d(material),
+            Transform::from_translation(position + Vec3::Y * 0.5),
+            Collider::cylinder(0.25, 0.25),
+            LevelGeometry,
+        )),
+        TrapKind::Dart => commands.spawn((
+            Enemy,
+            trap,
+            Mesh3d(meshes.add(Cuboid::new(0.4, 0.4, 0.4))),
+            MeshMaterial3d(material),
+            Transform::from_translation(position + Vec3::Y * 1.0),
+            Collider::cuboid(0.2, 0.2, 0.2),
+            LevelGeometry,
+        )),
+    };
+
+    // Traps are metal: hits always throw sparks
+    entity.insert(HitFx::Sparks);
+
+    if let Some(max_health) = definition.max_health {
+        entity.insert(Health::new(max_health));
+    }
+
+    entity.id()
+}
+
+/// Enemies a trap can still hurt.
+type LivingEnemy = (With<EnemyStats>, Without<Dead>);
+
+/// Tick trap timers and fire when ready.
+pub fn trap_attack(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut trap_query: Query<(Entity, &GlobalTransform, &mut Trap), Without<Dead>>,
+    player_query: Query<(Entity, &GlobalTransform), With<Player>>,
+    enemy_query: Query<(Entity, &GlobalTransform), LivingEnemy>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation();
+
+    for (trap_entity, trap_transform, mut trap) in trap_query.iter_mut() {
+        if !trap.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let trap_pos = trap_transform.translation();
+        let offset = player_pos - trap_pos;
+
+        match trap.kind {
+            TrapKind::Blade => {
+                let hurts_enemies = trap.hurts_enemies;
+                let player_target = std::iter::once((player_entity, player_pos));
+                let enemy_targets = enemy_query
+                    .iter()
+                    .filter(|_| hurts_enemies)
+                    .map(|(entity, transform)| (entity, transform.translation()));
+
+                for (target, target_pos) in player_target.chain(enemy_targets) {
+                    let target_offset = target_pos - trap_pos;
+                    let horizontal = Vec3::new(target_offset.x, 0.0, target_offset.z);
+                    if horizontal.length() <= trap.range
+                        && target_offset.y.abs() <= BLADE_VERTICAL_REACH
+                    {
+                        damage_events.send(DamageEvent {
+                            target,
+                            source: trap_entity,
+                            amount: trap.damage,
+                            element: Element::Physical,
+                            knockback: horizontal.normalize_or_zero() * 2.0,
+                            was_crit: false,
+                            melee: false,
+                        });
+                    }
+                }
+            }
+            TrapKind::Dart => {
+                if offset.length() > trap.range {
+                    continue;
+                }
+                let direction = offset.normalize_or_zero();
+                commands.spawn((
+                    Projectile {
+                        source: trap_entity,
+                        damage: trap.damage,
+                        velocity: direction * trap.projectile_speed,
+                        lifetime: DART_LIFETIME,
+                        hurts_enemies: trap.hurts_enemies,
+                    },
+                    Mesh3d(meshes.add(Cuboid::new(0.04, 0.04, 0.35))),
+                    MeshMaterial3d(trap.dart_material.clone()),
+                    Transform::from_translation(trap_pos).looking_to(direction, Vec3::Y),
+                    LevelGeometry,
+                ));
+            }
+        }
+    }
+}
+
+/// Spin blade traps.
+pub fn spin_blades(time: Res<Time>, mut query: Query<(&Trap, &mut Transform), Without<Dead>>) {
+    for (trap, mut transform) in query.iter_mut() {
+        if trap.kind == TrapKind::Blade {
+            transform.rotate_y(BLADE_SPIN_SPEED * time.delta_secs());
+        }
+    }
+}
+
+/// Move projectiles, damaging the player (and enemies, if allowed) and
+/// stopping at the first collider hit.
+pub fn update_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    rapier_context: Query<&RapierContext>,
+    mut projectile_query: Query<(Entity, &mut Projectile, &mut Transform)>,
+    player_query: Query<Entity, With<Player>>,
+    enemy_query: Query<(), (With<EnemyStats>, Without<Dead>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let context = rapier_context.get_single().ok();
+    let player_entity = player_query.get_single().ok();
+    let delta = time.delta_secs();
+
+    for (entity, mut projectile, mut transform) in projectile_query.iter_mut() {
+        projectile.lifetime -= delta;
+        if projectile.lifetime <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let step = projectile.velocity * delta;
+        if step == Vec3::ZERO {
+            continue;
+        }
+
+        let hit = context.and_then(|context| {
+            sweep_projectile(context, transform.translation, step, DART_RADIUS, projectile.source)
+        });
+
+        // Any other collider (walls, props, a wall the dart spawned inside) stops it
+        if let Some(hit) = hit {
+            let hit_entity = hit.entity;
+            let hits_enemy = projectile.hurts_enemies && enemy_query.contains(hit_entity);
+            if Some(hit_entity) == player_entity || hits_enemy {
+                damage_events.send(DamageEvent {
+                    target: hit_entity,
+                    source: projectile.source,
+                    amount: projectile.damage,
+                    element: Element::Physical,
+                    knockback: step.normalize_or_zero() * 2.0,
+                    was_crit: false,
+                    melee: false,
+                });
+            }
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation += step;
+    }
+}
+
+/// Despawn destructible traps once destroyed.
+pub fn destroy_traps(mut commands: Commands, query: Query<Entity, (With<Trap>, Added<Dead>)>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+    use std::time::Duration;
+
+    /// An app running only `trap_attack`, with a blade at the origin and the
+    /// player at `player_pos`.
+    fn blade_app(player_pos: Vec3) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)))
+            .init_resource::<Assets<Mesh>>()
+            .add_event::<DamageEvent>()
+            .add_systems(Update, trap_attack);
+
+        app.world_mut().spawn((
+            Trap {
+                kind: TrapKind::Blade,
+                damage: 10.0,
+                range: 1.5,
+                projectile_speed: 0.0,
+                hurts_enemies: false,
+                timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+                dart_material: Handle::default(),
+            },
+            GlobalTransform::default(),
+        ));
+        let player = app
+            .world_mut()
+            .spawn((Player, GlobalTransform::from_translation(player_pos)))
+            .id();
+        (app, player)
+    }
+
+    /// Run `frames` updates and count the hits on `target`.
+    fn hits_over(app: &mut App, target: Entity, frames: u32) -> usize {
+        let mut cursor = app.world().resource::<Events<DamageEvent>>().get_cursor();
+        let mut hits = 0;
+        for _ in 0..frames {
+            app.update();
+            let events = app.world().resource::<Events<DamageEvent>>();
+            hits += cursor.read(events).filter(|event| event.target == target).count();
+        }
+        hits
+    }
+
+    #[test]
+    fn blade_hits_player_beside_it() {
+        let (mut app, player) = blade_app(Vec3::new(1.0, 0.3, 0.0));
+        assert!(hits_over(&mut app, player, 6) > 0);
+    }
+
+    #[test]
+    fn blade_misses_player_on_floor_above() {
+        let (mut app, player) = blade_app(Vec3::new(0.0, 3.3, 0.0));
+        assert_eq!(hits_over(&mut app, player, 6), 0);
+    }
+}
@@ -0,0 +1,96 @@
+//! Inventory-related components.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// What a pickup gives the player when collected.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub enum PickupKind {
+    /// Restores the given amount of health
+    Health(f32),
+    /// Adds one throwable to the inventory
+    Throwable(ThrowableKind),
+    /// Equips the weapon with this id from the `WeaponRegistry`
+    Weapon(String),
+}
+
+impl PickupKind {
+    /// Display name used in interaction prompts.
+    ///
+    /// Weapons return their id; use `WeaponRegistry::display_name` for the
+    /// weapon's proper name.
+    pub fn display_name(&self) -> &str {
+        match self {
+            PickupKind::Health(_) => "Health Potion",
+            PickupKind::Throwable(kind) => kind.display_name(),
+            PickupKind::Weapon(id) => id,
+        }
+    }
+
+    /// Base color of the pickup's placeholder mesh.
+    pub fn color(&self) -> Color {
+        match self {
+            PickupKind::Health(_) => Color::srgb(0.8, 0.15, 0.15),
+            PickupKind::Throwable(kind) => kind.color(),
+            PickupKind::Weapon(_) => Color::srgb(0.75, 0.75, 0.8),
+        }
+    }
+}
+
+/// Kind of item that can be thrown with the secondary action.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ThrowableKind {
+    Rock,
+    Firebomb,
+}
+
+impl ThrowableKind {
+    /// All throwables, in the order they are picked when throwing.
+    pub const ALL: [ThrowableKind; 2] = [ThrowableKind::Rock, ThrowableKind::Firebomb];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ThrowableKind::Rock => "Rock",
+            ThrowableKind::Firebomb => "Firebomb",
+        }
+    }
+
+    /// Color of the pickup and thrown projectile.
+    pub fn color(&self) -> Color {
+        match self {
+            ThrowableKind::Rock => Color::srgb(0.45, 0.43, 0.4),
+            ThrowableKind::Firebomb => Color::srgb(0.9, 0.4, 0.1),
+        }
+    }
+}
+
+/// Items carried by the player.
+#[derive(Component, Default, Debug)]
+pub struct Inventory {
+    /// Number of each throwable carried
+    pub throwables: HashMap<ThrowableKind, u32>,
+}
+
+impl Inventory {
+    pub fn add_throwable(&mut self, kind: ThrowableKind) {
+        *self.throwables.entry(kind).or_insert(0) += 1;
+    }
+
+    /// Remove one throwable, taking the first kind in `ThrowableKind::ALL` that is carried.
+    pub fn take_throwable(&mut self) -> Option<ThrowableKind> {
+        let kind = ThrowableKind::ALL
+            .into_iter()
+            .find(|kind| self.throwables.get(kind).is_some_and(|&count| count > 0))?;
+        if let Some(count) = self.throwables.get_mut(&kind) {
+            *count -= 1;
+        }
+        Some(kind)
+    }
+}
+
+/// An item lying in the world that can be collected.
+#[derive(Component, Clone)]
+pub struct Pickup {
+    pub kind: PickupKind,
+}
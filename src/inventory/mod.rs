@@ -0,0 +1,10 @@
+//! Inventory module - items, pickups, loot drops, and throwables.
+
+mod components;
+mod pickups;
+mod plugin;
+mod throwing;
+
+pub use components::*;
+pub use plugin::InventoryPlugin;
+pub use throwing::{ThrowableDefinition, ThrowableRegistry, Thrown};
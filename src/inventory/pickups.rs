@@ -0,0 +1,193 @@
+//! Loot drops and pickup collection.
+//!
+//! Pickups are always spawned as `Interactable`s. With auto-pickup enabled,
+//! walking over a pickup simulates an interaction, so both paths share the
+//! same collection logic.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use super::components::{Inventory, Pickup, PickupKind};
+use crate::combat::{EquipWeaponEvent, Health, WeaponRegistry};
+use crate::core::{AutoPickup, EnemyKilledEvent, GameState, ItemPickupEvent, PlayState};
+use crate::enemies::EnemyRegistry;
+use crate::player::Player;
+use crate::world::{InteractEvent, Interactable, LevelGeometry};
+
+/// Horizontal distance at which auto-pickup collects an item.
+const AUTO_PICKUP_RADIUS: f32 = 1.0;
+
+/// Interaction range for manually looting a pickup.
+const PICKUP_INTERACT_RANGE: f32 = 2.5;
+
+/// Setup pickup systems.
+pub fn setup_pickup_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            spawn_loot_drops,
+            auto_pickup,
+            pickup_on_interact,
+            apply_pickups,
+        )
+            .chain()
+            .run_if(in_state(GameState::InGame))
+            .run_if(in_state(PlayState::Exploring)),
+    );
+}
+
+/// Spawn a pickup in the world.
+pub fn spawn_pickup(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    weapons: &WeaponRegistry,
+    kind: PickupKind,
+    position: Vec3,
+) -> Entity {
+    let name = match &kind {
+        PickupKind::Weapon(id) => weapons.display_name(id),
+        other => other.display_name(),
+    };
+    let prompt = format!("pick up {}", name);
+    let color = kind.color();
+
+    commands
+        .spawn((
+            Pickup { kind },
+            Interactable::new(prompt, PICKUP_INTERACT_RANGE),
+            Mesh3d(meshes.add(Cuboid::new(0.25, 0.35, 0.25))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: color,
+                perceptual_roughness: 0.6,
+                ..default()
+            })),
+            Transform::from_translation(position),
+            LevelGeometry, // Cleaned up with the level
+        ))
+        .id()
+}
+
+/// Roll loot tables for enemies that died this frame.
+fn spawn_loot_drops(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut killed_events: EventReader<EnemyKilledEvent>,
+    registry: Res<EnemyRegistry>,
+    weapons: Res<WeaponRegistry>,
+) {
+    for event in killed_events.read() {
+        let Some(definition) = registry.get(&event.enemy_type) else {
+            continue;
+        };
+
+        for drop in &definition.drops {
+            if rand::random::<f32>() >= drop.chance {
+                continue;
+            }
+
+            // Scatter drops slightly so multiple items don't overlap
+            let scatter = Vec3::new(
+                rand::random::<f32>() - 0.5,
+                0.0,
+                rand::random::<f32>() - 0.5,
+            ) * 0.6;
+            let position = event.position + scatter + Vec3::Y * 0.3;
+
+            spawn_pickup(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &weapons,
+                drop.item.clone(),
+                position,
+            );
+        }
+    }
+}
+
+/// Simulate an interaction with any pickup the player walks over.
+fn auto_pickup(
+    auto_pickup: Res<AutoPickup>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    pickup_query: Query<(Entity, &Transform), With<Pickup>>,
+    mut interact_events: EventWriter<InteractEvent>,
+) {
+    if !auto_pickup.enabled {
+        return;
+    }
+
+    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+        return;
+    };
+
+    for (pickup_entity, pickup_transform) in pickup_query.iter() {
+        let offset = pickup_transform.translation - player_transform.translation;
+        let horizontal_distance = Vec3::new(offset.x, 0.0, offset.z).length();
+
+        if horizontal_distance <= AUTO_PICKUP_RADIUS && offset.y.abs() < 1.5 {
+            interact_events.send(InteractEvent {
+                interactor: player_entity,
+                target: pickup_entity,
+            });
+        }
+    }
+}
+
+/// Turn interactions with pickups into ItemPickupEvents.
+fn pickup_on_interact(
+    mut interact_events: EventReader<InteractEvent>,
+    pickup_query: Query<(), With<Pickup>>,
+    mut pickup_events: EventWriter<ItemPickupEvent>,
+) {
+    // Manual and auto pickup may target the same item in one frame
+    let mut collected = HashSet::new();
+
+    for event in interact_events.read() {
+        if pickup_query.get(event.target).is_ok() && collected.insert(event.target) {
+            pickup_events.send(ItemPickupEvent {
+                item: event.target,
+                player: event.interactor,
+            });
+        }
+    }
+}
+
+/// Apply pickup effects and remove collected items.
+fn apply_pickups(
+    mut commands: Commands,
+    mut pickup_events: EventReader<ItemPickupEvent>,
+    pickup_query: Query<&Pickup>,
+    mut health_query: Query<&mut Health>,
+    mut inventory_query: Query<&mut Inventory>,
+    mut equip_events: EventWriter<EquipWeaponEvent>,
+) {
+    for event in pickup_events.read() {
+        let Ok(pickup) = pickup_query.get(event.item) else {
+            continue;
+        };
+
+        match &pickup.kind {
+            PickupKind::Health(amount) => {
+                if let Ok(mut health) = health_query.get_mut(event.player) {
+                    health.heal(*amount);
+                }
+            }
+            PickupKind::Throwable(kind) => {
+                if let Ok(mut inventory) = inventory_query.get_mut(event.player) {
+                    inventory.add_throwable(*kind);
+                }
+            }
+            PickupKind::Weapon(weapon_id) => {
+                equip_events.send(EquipWeaponEvent {
+                    entity: event.player,
+                    weapon_id: weapon_id.clone(),
+                });
+            }
+        }
+
+        info!("Picked up {}", pickup.kind.display_name());
+        commands.entity(event.item).despawn_recursive();
+    }
+}
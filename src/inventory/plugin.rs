@@ -0,0 +1,19 @@
+//! Inventory plugin - pickups, loot drops and throwables.
+
+use bevy::prelude::*;
+
+use super::pickups;
+use super::throwing;
+
+/// Inventory plugin - handles loot drops, item pickups and throwables.
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        // Setup pickup systems
+        pickups::setup_pickup_systems(app);
+
+        // Setup throwing systems
+        throwing::setup_throwing_systems(app);
+    }
+}
@@ -0,0 +1,252 @@
+//! Throwable items - the player's secondary attack.
+//!
+//! Pressing the throw key consumes one throwable from the inventory and
+//! launches it from the camera. Thrown items arc under gravity and deal their
+//! damage on impact: directly to whatever they hit, or to every enemy within
+//! their blast radius with damage fading toward the edge. Per-item tuning is
+//! loaded from assets/data/throwables/.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::components::{Inventory, ThrowableKind};
+use crate::combat::{sweep_projectile, AreaDamage, DamageEvent, Element, Falloff, Health};
+use crate::core::{Action, ActionInput, GameState, PlayState};
+use crate::player::{Player, PlayerCamera};
+use crate::world::LevelGeometry;
+
+/// Seconds before a thrown item that never hits anything despawns.
+const THROWN_LIFETIME: f32 = 5.0;
+
+/// Distance in front of the camera thrown items start from.
+const THROW_SPAWN_OFFSET: f32 = 0.5;
+
+/// Collision radius of a thrown item.
+const THROWN_RADIUS: f32 = 0.1;
+
+fn default_gravity() -> f32 {
+    9.8
+}
+
+fn default_throw_lift() -> f32 {
+    0.15
+}
+
+/// Throwable definition loaded from RON file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ThrowableDefinition {
+    pub kind: ThrowableKind,
+    pub damage: f32,
+    #[serde(default)]
+    pub element: Element,
+    /// Launch speed in units per second
+    pub throw_force: f32,
+    /// Upward tilt added to the aim direction
+    #[serde(default = "default_throw_lift")]
+    pub throw_lift: f32,
+    /// Blast radius on impact (0.0 = only the entity hit)
+    #[serde(default)]
+    pub radius: f32,
+    #[serde(default = "default_gravity")]
+    pub gravity: f32,
+}
+
+/// Resource holding all loaded throwable definitions.
+#[derive(Resource, Default)]
+pub struct ThrowableRegistry {
+    pub definitions: HashMap<ThrowableKind, ThrowableDefinition>,
+}
+
+impl ThrowableRegistry {
+    pub fn get(&self, kind: ThrowableKind) -> Option<&ThrowableDefinition> {
+        self.definitions.get(&kind)
+    }
+}
+
+/// A thrown item in flight.
+#[derive(Component)]
+pub struct Thrown {
+    /// Entity that threw the item (ignored by its collision checks)
+    pub source: Entity,
+    pub damage: f32,
+    pub element: Element,
+    pub radius: f32,
+    pub gravity: f32,
+    pub velocity: Vec3,
+    /// Seconds before the item despawns
+    pub lifetime: f32,
+}
+
+/// Setup throwing systems.
+pub fn setup_throwing_systems(app: &mut App) {
+    app.init_resource::<ThrowableRegistry>()
+        .add_systems(Startup, load_throwable_definitions)
+        .add_systems(
+            Update,
+            (throw_input, update_thrown)
+                .chain()
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PlayState::Exploring)),
+        );
+}
+
+/// Load all throwable definitions from the assets/data/throwables/ directory.
+fn load_throwable_definitions(mut registry: ResMut<ThrowableRegistry>) {
+    let throwables_dir = Path::new("assets/data/throwables");
+
+    if !throwables_dir.exists() {
+        warn!("Throwable definitions directory not found: {:?}", throwables_dir);
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(throwables_dir) else {
+        warn!("Failed to read throwable definitions directory");
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.extension().is_some_and(|ext| ext == "ron") {
+            match fs::read_to_string(&path) {
+                Ok(contents) => match ron::from_str::<ThrowableDefinition>(&contents) {
+                    Ok(definition) => {
+                        info!("Loaded throwable definition: {:?}", definition.kind);
+                        registry.definitions.insert(definition.kind, definition);
+                    }
+                    Err(e) => {
+                        error!("Failed to parse throwable definition {:?}: {}", path, e);
+                    }
+                },
+                Err(e) => {
+                    error!("Failed to read throwable definition {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    info!("Loaded {} throwable definitions", registry.definitions.len());
+}
+
+/// Throw the next carried throwable along the camera's view direction.
+fn throw_input(
+    mut commands: Commands,
+    input: ActionInput,
+    registry: Res<ThrowableRegistry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut player_query: Query<(Entity, &mut Inventory), With<Player>>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+) {
+    if !input.just_pressed(Action::Throw) {
+        return;
+    }
+
+    let Ok((player_entity, mut inventory)) = player_query.get_single_mut() else {
+        return;
+    };
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    let Some(kind) = inventory.take_throwable() else {
+        info!("Nothing to throw");
+        return;
+    };
+    let Some(definition) = registry.get(kind) else {
+        warn!("No throwable definition for {:?}", kind);
+        inventory.add_throwable(kind);
+        return;
+    };
+
+    let aim = camera_transform.forward().as_vec3();
+    let direction = (aim + Vec3::Y * definition.throw_lift).normalize_or_zero();
+    let origin = camera_transform.translation() + aim * THROW_SPAWN_OFFSET;
+
+    commands.spawn((
+        Thrown {
+            source: player_entity,
+            damage: definition.damage,
+            element: definition.element,
+            radius: definition.radius,
+            gravity: definition.gravity,
+            velocity: direction * definition.throw_force,
+            lifetime: THROWN_LIFETIME,
+        },
+        Mesh3d(meshes.add(Sphere::new(0.08))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: kind.color(),
+            perceptual_roughness: 0.8,
+            ..default()
+        })),
+        Transform::from_translation(origin),
+        LevelGeometry,
+    ));
+}
+
+/// Move thrown items along their arc and resolve impacts.
+fn update_thrown(
+    mut commands: Commands,
+    time: Res<Time>,
+    rapier_context: Query<&RapierContext>,
+    mut thrown_query: Query<(Entity, &mut Thrown, &mut Transform)>,
+    target_query: Query<(), With<Health>>,
+    mut area_damage: AreaDamage,
+) {
+    let context = rapier_context.get_single().ok();
+    let delta = time.delta_secs();
+
+    for (entity, mut thrown, mut transform) in thrown_query.iter_mut() {
+        thrown.lifetime -= delta;
+        if thrown.lifetime <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        thrown.velocity.y -= thrown.gravity * delta;
+        let step = thrown.velocity * delta;
+        if step == Vec3::ZERO {
+            continue;
+        }
+
+        let hit = context.and_then(|context| {
+            sweep_projectile(context, transform.translation, step, THROWN_RADIUS, thrown.source)
+        });
+
+        let Some(hit) = hit else {
+            transform.translation += step;
+            continue;
+        };
+
+        let hit_entity = hit.entity;
+        let impact = hit.position;
+        let knockback = Vec3::new(step.x, 0.0, step.z).normalize_or_zero();
+
+        if thrown.radius > 0.0 {
+            area_damage.apply_aoe_damage(
+                impact,
+                thrown.radius,
+                thrown.damage,
+                thrown.element,
+                thrown.source,
+                Falloff::Linear,
+            );
+        } else if target_query.contains(hit_entity) {
+            area_damage.damage_events.send(DamageEvent {
+                target: hit_entity,
+                source: thrown.source,
+                amount: thrown.damage,
+                element: thrown.element,
+                knockback,
+                was_crit: false,
+                melee: false,
+            });
+        }
+
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -18,20 +18,20 @@
 //! - **UI**: Menus, HUD, inventory screen
 //! - **Persistence**: Save/load system
 
+pub mod audio;
 pub mod combat;
 pub mod core;
 pub mod enemies;
+pub mod inventory;
+pub mod persistence;
 pub mod player;
+pub mod progression;
 pub mod rendering;
 pub mod ui;
 pub mod world;
 
 // These modules will be implemented in later phases:
 // pub mod magic;
-// pub mod inventory;
-// pub mod progression;
-// pub mod audio;
-// pub mod persistence;
 
 use bevy::prelude::*;
 
@@ -56,6 +56,18 @@ impl Plugin for LunacidPlugin {
             // World systems
             .add_plugins(world::WorldPlugin)
 
+            // Inventory systems
+            .add_plugins(inventory::InventoryPlugin)
+
+            // Progression systems
+            .add_plugins(progression::ProgressionPlugin)
+
+            // Save/load systems
+            .add_plugins(persistence::PersistencePlugin)
+
+            // Audio systems
+            .add_plugins(audio::AudioPlugin)
+
             // Rendering systems
             .add_plugins(rendering::RenderingPlugin)
 
@@ -6,7 +6,29 @@
 //! - WASD: Move
 //! - Mouse: Look around
 //! - Shift: Sprint
+//! - Left Alt: Dodge roll
+//! - Left click: Attack (hold for a heavy attack)
+//! - Right click: Block
 //! - Escape: Pause/Unpause
+//! - F1: Cycle enemy health bars (always / on hit / never)
+//! - F2/F3/F4: Toggle film grain / scanlines / vignette
+//! - F5: Toggle ambient occlusion
+//! - E: Interact
+//! - G: Throw item
+//! - C: Open/close attribute screen (also opens on level up)
+//! - Tab: Toggle minimap
+//! - Insert/Delete: Quick save / quick load
+//! - F6: Toggle reduce motion
+//! - F7: Toggle auto-pickup
+//! - Shift+F7: Toggle pause on focus loss
+//! - F8: Cycle crosshair style
+//! - F9: Toggle look smoothing
+//! - F10: Toggle mouse acceleration
+//! - F11 + Arrows/PageUp/PageDown: Tune weapon offset / eye height (debug builds)
+//! - F12 / Shift+F12: Cycle HUD corner / HUD scale
+//! - Ctrl+F12: Toggle compass
+//! - - / =: Lower / raise master volume
+//! - Home/End: Toggle god mode / one-shot kills (`cheats` feature only)
 
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
@@ -0,0 +1,23 @@
+//! Error types for save files.
+
+use thiserror::Error;
+
+/// Errors that can occur when saving or loading the game.
+#[derive(Debug, Error)]
+pub enum SaveError {
+    /// Save file could not be read or written.
+    #[error("Save file I/O failed for '{path}': {details}")]
+    Io { path: String, details: String },
+
+    /// Save file contents could not be parsed.
+    #[error("Failed to parse save file: {0}")]
+    Parse(String),
+
+    /// Save data could not be serialized.
+    #[error("Failed to serialize save data: {0}")]
+    Serialize(String),
+
+    /// Save was written by a newer version of the game.
+    #[error("Save version {found} is newer than supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+}
@@ -0,0 +1,185 @@
+//! Save file migrations.
+//!
+//! Each migration upgrades the raw RON value of a save by one version,
+//! typically filling in defaults for fields added in that version. Saves
+//! written before versioning was introduced are treated as version 1.
+
+use ron::{Map, Value};
+
+use super::error::SaveError;
+use super::save::SAVE_VERSION;
+
+/// Upgrades the top-level save map from one version to the next.
+type Migration = fn(&mut Map);
+
+/// Migration chain: `MIGRATIONS[i]` upgrades version `i + 1` to `i + 2`.
+///
+/// Append a migration here whenever `SAVE_VERSION` is bumped.
+const MIGRATIONS: &[Migration] = &[v1_to_v2, v2_to_v3, v3_to_v4];
+
+/// Bring a raw save value from `version` up to `SAVE_VERSION`.
+pub fn migrate(value: Value, version: u32) -> Result<Value, SaveError> {
+    if version > SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion {
+            found: version,
+            supported: SAVE_VERSION,
+        });
+    }
+
+    let Value::Map(mut map) = value else {
+        return Err(SaveError::Parse("save root is not a struct".to_string()));
+    };
+
+    for from in version.max(1)..SAVE_VERSION {
+        let migration = MIGRATIONS[(from - 1) as usize];
+        migration(&mut map);
+    }
+
+    Ok(Value::Map(map))
+}
+
+/// Version 2 added play time tracking.
+fn v1_to_v2(map: &mut Map) {
+    insert_default(map, "play_time", Value::Number(0.0.into()));
+}
+
+/// Version 3 added defeated enemies. Stamina and the equipped weapon were
+/// added to the player too; `PlayerSave` defaults those to full stamina
+/// and the starter weapon.
+fn v2_to_v3(map: &mut Map) {
+    insert_default(map, "defeated_enemies", Value::Seq(Vec::new()));
+}
+
+/// Version 4 made the player's position, health and stamina optional, so
+/// saves missing them keep the spawned player's values.
+fn v3_to_v4(map: &mut Map) {
+    let player_key = Value::String("player".to_string());
+    let Some((_, Value::Map(player))) = map.iter_mut().find(|(key, _)| **key == player_key)
+    else {
+        return;
+    };
+    for name in ["position", "health", "max_health", "stamina"] {
+        let key = Value::String(name.to_string());
+        if let Some((_, value)) = player.iter_mut().find(|(existing, _)| **existing == key) {
+            if !matches!(value, Value::Option(_)) {
+                *value = Value::Option(Some(Box::new(value.clone())));
+            }
+        }
+    }
+}
+
+/// Insert a field only if the older save doesn't already have it.
+fn insert_default(map: &mut Map, name: &str, value: Value) {
+    let key = Value::String(name.to_string());
+    if !map.iter().any(|(existing, _)| *existing == key) {
+        map.insert(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::save::{PlayerSave, SaveData};
+    use super::*;
+
+    /// A save written before versioning, play time or defeated enemies.
+    const V1_SAVE: &str = r#"(
+        level: "compact_quarters",
+        player: (
+            position: (4.0, 1.0, 6.0),
+            health: 80.0,
+            max_health: 100.0,
+        ),
+    )"#;
+
+    #[test]
+    fn v1_save_gets_defaults() {
+        let data = SaveData::from_ron_str(V1_SAVE).unwrap();
+
+        assert_eq!(data.version, SAVE_VERSION);
+        assert_eq!(data.level, "compact_quarters");
+        assert_eq!(data.play_time, 0.0);
+        assert!(data.defeated_enemies.is_empty());
+        assert_eq!(data.player.position, Some((4.0, 1.0, 6.0)));
+        assert_eq!(data.player.health, Some(80.0));
+        assert_eq!(data.player.weapon, None);
+        // Filled up to the player's maximum on load
+        assert_eq!(data.player.stamina, None);
+    }
+
+    #[test]
+    fn v1_save_without_player_leaves_player_unset() {
+        let data = SaveData::from_ron_str(r#"(level: "compact_quarters")"#).unwrap();
+
+        assert_eq!(data.version, SAVE_VERSION);
+        assert_eq!(data.player.position, None);
+        assert_eq!(data.player.health, None);
+        assert_eq!(data.player.max_health, None);
+        assert_eq!(data.player.stamina, None);
+        assert_eq!(data.player.weapon, None);
+    }
+
+    #[test]
+    fn migration_keeps_existing_fields() {
+        let contents = r#"(
+            version: 2,
+            level: "ruined_temple",
+            play_time: 125.5,
+        )"#;
+        let data = SaveData::from_ron_str(contents).unwrap();
+
+        assert_eq!(data.play_time, 125.5);
+        assert!(data.defeated_enemies.is_empty());
+    }
+
+    #[test]
+    fn v3_player_values_become_optional() {
+        let contents = r#"(
+            version: 3,
+            level: "ruined_temple",
+            player: (position: (1.0, 2.0, 3.0), health: 40.0, max_health: 90.0, stamina: 12.0),
+            play_time: 10.0,
+            defeated_enemies: [],
+        )"#;
+        let data = SaveData::from_ron_str(contents).unwrap();
+
+        assert_eq!(data.player.position, Some((1.0, 2.0, 3.0)));
+        assert_eq!(data.player.health, Some(40.0));
+        assert_eq!(data.player.max_health, Some(90.0));
+        assert_eq!(data.player.stamina, Some(12.0));
+    }
+
+    #[test]
+    fn saved_stamina_round_trips() {
+        let player = PlayerSave {
+            stamina: Some(42.5),
+            ..PlayerSave::default()
+        };
+        let contents = SaveData::new("compact_quarters", player, 0.0, Vec::new())
+            .to_ron_string()
+            .unwrap();
+        let data = SaveData::from_ron_str(&contents).unwrap();
+
+        assert_eq!(data.player.stamina, Some(42.5));
+    }
+
+    #[test]
+    fn future_version_is_refused() {
+        let contents = format!(
+            r#"(version: {}, level: "compact_quarters", play_time: 0.0, defeated_enemies: [])"#,
+            SAVE_VERSION + 1
+        );
+
+        match SaveData::from_ron_str(&contents) {
+            Err(SaveError::UnsupportedVersion { found, supported }) => {
+                assert_eq!(found, SAVE_VERSION + 1);
+                assert_eq!(supported, SAVE_VERSION);
+            }
+            other => panic!("expected UnsupportedVersion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn every_version_has_a_migration() {
+        assert_eq!(MIGRATIONS.len(), (SAVE_VERSION - 1) as usize);
+    }
+}
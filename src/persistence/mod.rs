@@ -0,0 +1,14 @@
+//! Persistence module - save file format, saving and loading.
+//!
+//! Save files are versioned RON. Loading an older save runs it through a
+//! chain of migrations before deserializing, so adding fields doesn't break
+//! existing saves.
+
+mod error;
+mod migration;
+mod plugin;
+mod save;
+
+pub use error::SaveError;
+pub use plugin::{PendingLoad, PersistencePlugin};
+pub use save::*;
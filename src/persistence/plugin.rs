@@ -0,0 +1,282 @@
+//! Persistence plugin - quick-save, quick-load and continuing from a save.
+//!
+//! Saving captures the player, the current level and which of its enemies
+//! are dead. Loading rebuilds the saved level from scratch and then applies
+//! the save on top of it through `PendingLoad`: the player is moved and
+//! restored, and enemies whose spawn point is recorded as defeated are
+//! removed again.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use super::save::{load_game, save_game, DefeatedEnemySave, PlayerSave, SaveData};
+use crate::combat::{EquipWeaponEvent, Health, Stamina, Weapon};
+use crate::core::{
+    Action, ActionInput, EnemyKilledEvent, GameSession, GameState, PlayState, PlayTime,
+};
+use crate::enemies::{Enemy, SpawnPoint};
+use crate::player::{Attributes, Experience, MovementState, Player};
+use crate::world::{CurrentLevel, LevelRegistry, LevelTransition};
+
+/// Persistence plugin - handles saving and loading games.
+pub struct PersistencePlugin;
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DefeatedEnemies>()
+            .add_systems(
+                Update,
+                (quick_save, quick_load)
+                    .chain()
+                    .run_if(in_state(PlayState::Exploring)),
+            )
+            // After the level is built, which happens in Update or on
+            // entering the session
+            .add_systems(
+                PostUpdate,
+                (
+                    track_defeated_enemies,
+                    apply_pending_load
+                        .run_if(resource_exists::<PendingLoad>)
+                        .run_if(not(resource_exists::<LevelTransition>)),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameSession), reset_defeated_enemies);
+    }
+}
+
+/// A loaded save waiting to be applied once its level has been built.
+#[derive(Resource)]
+pub struct PendingLoad(pub SaveData);
+
+/// Spawn points of enemies killed in the current level.
+#[derive(Resource, Default)]
+struct DefeatedEnemies(HashSet<SpawnPoint>);
+
+/// Record killed enemies, starting over whenever the level is rebuilt.
+fn track_defeated_enemies(
+    mut killed_events: EventReader<EnemyKilledEvent>,
+    current_level: Res<CurrentLevel>,
+    mut defeated: ResMut<DefeatedEnemies>,
+    spawn_query: Query<&SpawnPoint>,
+    spawned_query: Query<&SpawnPoint, Added<SpawnPoint>>,
+) {
+    if current_level.is_changed() {
+        defeated.0.clear();
+    }
+    // Enemies brought back by a checkpoint respawn are alive again
+    for spawn_point in spawned_query.iter() {
+        defeated.0.remove(spawn_point);
+    }
+
+    for event in killed_events.read() {
+        if let Ok(spawn_point) = spawn_query.get(event.enemy) {
+            defeated.0.insert(*spawn_point);
+        }
+    }
+}
+
+/// Forget killed enemies when the game session ends.
+fn reset_defeated_enemies(mut defeated: ResMut<DefeatedEnemies>) {
+    defeated.0.clear();
+}
+
+/// Everything a save records about the player.
+type SavedPlayerData<'a> = (
+    &'a Transform,
+    &'a Health,
+    &'a Stamina,
+    &'a Attributes,
+    &'a Experience,
+    &'a Weapon,
+);
+
+/// Save the game with the quick-save key.
+fn quick_save(
+    input: ActionInput,
+    current_level: Res<CurrentLevel>,
+    play_time: Res<PlayTime>,
+    defeated: Res<DefeatedEnemies>,
+    player_query: Query<SavedPlayerData, With<Player>>,
+) {
+    if !input.just_pressed(Action::QuickSave) {
+        return;
+    }
+    let Ok((transform, health, stamina, attributes, experience, weapon)) =
+        player_query.get_single()
+    else {
+        return;
+    };
+
+    let position = transform.translation;
+    let player = PlayerSave {
+        position: Some((position.x, position.y, position.z)),
+        health: Some(health.current),
+        max_health: Some(health.maximum),
+        stamina: Some(stamina.current),
+        weapon: weapon.id.clone(),
+        experience: *experience,
+        attributes: *attributes,
+    };
+    let defeated_enemies = defeated
+        .0
+        .iter()
+        .map(|spawn_point| DefeatedEnemySave {
+            floor: spawn_point.floor,
+            tile: spawn_point.tile,
+        })
+        .collect();
+
+    let data = SaveData::new(&current_level.name, player, play_time.total, defeated_enemies);
+    if let Err(e) = save_game(&data) {
+        error!("Quick save failed: {}", e);
+    }
+}
+
+/// Load the save with the quick-load key, rebuilding its level.
+fn quick_load(
+    mut commands: Commands,
+    input: ActionInput,
+    level_registry: Res<LevelRegistry>,
+    mut play_time: ResMut<PlayTime>,
+) {
+    if !input.just_pressed(Action::QuickLoad) {
+        return;
+    }
+    let data = match load_game() {
+        Ok(data) => data,
+        Err(e) => {
+            warn!("Quick load failed: {}", e);
+            return;
+        }
+    };
+
+    // The arrival tile is overridden by the saved position once built
+    let target_spawn = level_registry
+        .get(&data.level)
+        .map_or((0, 0), |level| level.player_start);
+    commands.insert_resource(LevelTransition {
+        target_level: data.level.clone(),
+        target_spawn,
+    });
+    *play_time = PlayTime::restore(data.play_time);
+    commands.insert_resource(PendingLoad(data));
+}
+
+/// Everything a loaded save restores on the player.
+type LoadedPlayerData<'a> = (
+    Entity,
+    &'a mut Transform,
+    &'a mut MovementState,
+    &'a mut Health,
+    &'a mut Stamina,
+    &'a mut Attributes,
+    &'a mut Experience,
+);
+
+/// Apply a loaded save to the freshly built level and player.
+fn apply_pending_load(
+    mut commands: Commands,
+    pending: Res<PendingLoad>,
+    mut defeated: ResMut<DefeatedEnemies>,
+    mut equip_events: EventWriter<EquipWeaponEvent>,
+    mut player_query: Query<LoadedPlayerData, With<Player>>,
+    enemy_query: Query<(Entity, &SpawnPoint), With<Enemy>>,
+) {
+    // The player is spawned with the level; wait for it
+    let Ok((
+        player,
+        mut transform,
+        mut movement,
+        mut health,
+        mut stamina,
+        mut attributes,
+        mut experience,
+    )) = player_query.get_single_mut()
+    else {
+        return;
+    };
+    let data = &pending.0;
+    let saved = &data.player;
+
+    // Anything the save lacks keeps the value the player spawned with
+    if let Some((x, y, z)) = saved.position {
+        transform.translation = Vec3::new(x, y, z);
+    }
+    movement.vertical_velocity = 0.0;
+    if let Some(max_health) = saved.max_health.filter(|max_health| *max_health > 0.0) {
+        health.maximum = max_health;
+    }
+    if let Some(current) = saved.health.filter(|current| *current > 0.0) {
+        health.current = current.min(health.maximum);
+    }
+    stamina.current = saved
+        .stamina
+        .map_or(stamina.maximum, |current| current.min(stamina.maximum));
+    *attributes = saved.attributes;
+    *experience = saved.experience;
+    if let Some(weapon_id) = &saved.weapon {
+        equip_events.send(EquipWeaponEvent {
+            entity: player,
+            weapon_id: weapon_id.clone(),
+        });
+    }
+
+    defeated.0 = data
+        .defeated_enemies
+        .iter()
+        .map(|enemy| SpawnPoint {
+            floor: enemy.floor,
+            tile: enemy.tile,
+        })
+        .collect();
+    for (entity, spawn_point) in enemy_query.iter() {
+        if defeated.0.contains(spawn_point) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    info!("Loaded save in level '{}'", data.level);
+    commands.remove_resource::<PendingLoad>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_without_player_keeps_spawned_player() {
+        let mut app = App::new();
+        app.init_resource::<DefeatedEnemies>()
+            .add_event::<EquipWeaponEvent>()
+            .add_systems(Update, apply_pending_load);
+
+        let spawn = Vec3::new(3.0, 1.0, 5.0);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_translation(spawn),
+                MovementState::default(),
+                Health::new(100.0),
+                Stamina::default(),
+                Attributes::default(),
+                Experience::default(),
+            ))
+            .id();
+        let data = SaveData::from_ron_str(r#"(level: "compact_quarters")"#).unwrap();
+        app.insert_resource(PendingLoad(data));
+
+        app.update();
+
+        let world = app.world();
+        assert!(!world.contains_resource::<PendingLoad>());
+        assert_eq!(world.get::<Transform>(player).unwrap().translation, spawn);
+        let health = world.get::<Health>(player).unwrap();
+        assert_eq!(health.maximum, 100.0);
+        assert_eq!(health.current, 100.0);
+        assert!(!health.is_dead());
+    }
+}
@@ -0,0 +1,152 @@
+//! Save file format and disk I/O.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::error::SaveError;
+use crate::player::{Attributes, Experience};
+use super::migration::migrate;
+
+/// Path of the save file (relative to the working directory).
+pub const SAVE_PATH: &str = "savegame.ron";
+
+/// Current save format version. Bump this and add a migration when the
+/// format changes.
+pub const SAVE_VERSION: u32 = 4;
+
+fn legacy_version() -> u32 {
+    1
+}
+
+/// Saved player state.
+///
+/// Fields missing from the save keep the freshly spawned player's values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerSave {
+    pub position: Option<(f32, f32, f32)>,
+    pub health: Option<f32>,
+    pub max_health: Option<f32>,
+    /// Current stamina (added in version 3, `None` = full)
+    pub stamina: Option<f32>,
+    /// Weapon registry id of the equipped weapon (`None` = starter weapon)
+    pub weapon: Option<String>,
+    /// Level, experience and unallocated attribute points
+    pub experience: Experience,
+    /// Allocated attributes
+    pub attributes: Attributes,
+}
+
+/// A killed level-placed enemy, by where it spawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DefeatedEnemySave {
+    /// Floor layer index
+    pub floor: usize,
+    /// Spawn tile (x, z)
+    pub tile: (i32, i32),
+}
+
+/// Root of the save file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    /// Format version, used to pick migrations on load
+    #[serde(default = "legacy_version")]
+    pub version: u32,
+    /// Name of the level the player is in
+    pub level: String,
+    #[serde(default)]
+    pub player: PlayerSave,
+    /// Total seconds played on this save (added in version 2)
+    pub play_time: f64,
+    /// Enemies of `level` that stay dead on load (added in version 3)
+    pub defeated_enemies: Vec<DefeatedEnemySave>,
+}
+
+impl SaveData {
+    /// Create save data stamped with the current format version.
+    pub fn new(
+        level: impl Into<String>,
+        player: PlayerSave,
+        play_time: f64,
+        defeated_enemies: Vec<DefeatedEnemySave>,
+    ) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            level: level.into(),
+            player,
+            play_time,
+            defeated_enemies,
+        }
+    }
+
+    /// Parse save data from RON, migrating older versions.
+    ///
+    /// Saves from newer, unknown versions are refused rather than loaded
+    /// with missing or misread fields.
+    pub fn from_ron_str(contents: &str) -> Result<Self, SaveError> {
+        /// Reads just the version, ignoring all other fields.
+        #[derive(Deserialize)]
+        struct VersionProbe {
+            #[serde(default = "legacy_version")]
+            version: u32,
+        }
+
+        let probe: VersionProbe =
+            ron::from_str(contents).map_err(|e| SaveError::Parse(e.to_string()))?;
+        let value: ron::Value =
+            ron::from_str(contents).map_err(|e| SaveError::Parse(e.to_string()))?;
+
+        let migrated = migrate(value, probe.version)?;
+        let mut data: SaveData = migrated
+            .into_rust()
+            .map_err(|e| SaveError::Parse(e.to_string()))?;
+
+        if probe.version < SAVE_VERSION {
+            info!("Migrated save from version {} to {}", probe.version, SAVE_VERSION);
+        }
+        data.version = SAVE_VERSION;
+
+        Ok(data)
+    }
+
+    /// Serialize to pretty-printed RON.
+    pub fn to_ron_string(&self) -> Result<String, SaveError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| SaveError::Serialize(e.to_string()))
+    }
+}
+
+/// Write the save file to disk.
+pub fn save_game(data: &SaveData) -> Result<(), SaveError> {
+    let contents = data.to_ron_string()?;
+    fs::write(SAVE_PATH, contents).map_err(|e| SaveError::Io {
+        path: SAVE_PATH.to_string(),
+        details: e.to_string(),
+    })?;
+    info!("Saved game to {}", SAVE_PATH);
+    Ok(())
+}
+
+/// Whether a save file exists to continue from.
+pub fn save_exists() -> bool {
+    Path::new(SAVE_PATH).exists()
+}
+
+/// Load the save file from disk, migrating it to the current version.
+pub fn load_game() -> Result<SaveData, SaveError> {
+    let contents = fs::read_to_string(SAVE_PATH).map_err(|e| SaveError::Io {
+        path: SAVE_PATH.to_string(),
+        details: e.to_string(),
+    })?;
+
+    match SaveData::from_ron_str(&contents) {
+        Ok(data) => Ok(data),
+        Err(e @ SaveError::UnsupportedVersion { .. }) => {
+            warn!("Refusing to load {}: {}", SAVE_PATH, e);
+            Err(e)
+        }
+        Err(e) => Err(e),
+    }
+}
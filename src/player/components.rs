@@ -1,6 +1,7 @@
 //! Player-related components.
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Marker component for the player entity.
 #[derive(Component)]
@@ -28,8 +29,28 @@ impl Default for PlayerStats {
     }
 }
 
+/// Attribute value every multiplier is measured from.
+const BASE_ATTRIBUTE: f32 = 10.0;
+
+/// Melee damage bonus per strength point above the base.
+const STRENGTH_DAMAGE: f32 = 0.05;
+
+/// Maximum mana per magic point above the base.
+const MAGIC_MANA: f32 = 5.0;
+
+/// Attack speed bonus per dexterity point above the base.
+const DEXTERITY_ATTACK_SPEED: f32 = 0.03;
+
+/// Movement speed bonus per speed point above the base.
+const SPEED_MOVE_SPEED: f32 = 0.02;
+
+/// Damage reduction per defense point above the base, and its cap.
+const DEFENSE_REDUCTION: f32 = 0.02;
+const MAX_DEFENSE_REDUCTION: f32 = 0.5;
+
 /// Character attributes that affect gameplay.
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Attributes {
     /// Affects melee damage
     pub strength: u32,
@@ -55,6 +76,85 @@ impl Default for Attributes {
     }
 }
 
+impl Attributes {
+    /// Points above the base value (negative below it).
+    fn above_base(value: u32) -> f32 {
+        value as f32 - BASE_ATTRIBUTE
+    }
+
+    /// Multiplier on melee weapon damage.
+    pub fn damage_multiplier(&self) -> f32 {
+        (1.0 + Self::above_base(self.strength) * STRENGTH_DAMAGE).max(0.1)
+    }
+
+    /// Maximum mana for a character with `base` mana at base magic.
+    pub fn max_mana(&self, base: f32) -> f32 {
+        (base + Self::above_base(self.magic) * MAGIC_MANA).max(0.0)
+    }
+
+    /// Multiplier on attack speed (swing duration and cooldown are divided by it).
+    pub fn attack_speed_multiplier(&self) -> f32 {
+        (1.0 + Self::above_base(self.dexterity) * DEXTERITY_ATTACK_SPEED).max(0.1)
+    }
+
+    /// Multiplier on movement speed.
+    pub fn move_speed_multiplier(&self) -> f32 {
+        (1.0 + Self::above_base(self.speed) * SPEED_MOVE_SPEED).max(0.1)
+    }
+
+    /// Fraction of incoming damage blocked (0.0 to `MAX_DEFENSE_REDUCTION`).
+    pub fn damage_reduction(&self) -> f32 {
+        (Self::above_base(self.defense) * DEFENSE_REDUCTION).clamp(0.0, MAX_DEFENSE_REDUCTION)
+    }
+}
+
+/// Attribute points granted per level gained.
+pub const POINTS_PER_LEVEL: u32 = 3;
+
+/// Experience needed per level: reaching level `n + 1` takes `n * XP_PER_LEVEL`.
+const XP_PER_LEVEL: u32 = 100;
+
+/// Player level, experience and unspent attribute points.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Experience {
+    pub level: u32,
+    /// Experience gained toward the next level
+    pub current: u32,
+    /// Attribute points earned but not yet allocated
+    pub unspent_points: u32,
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            current: 0,
+            unspent_points: 0,
+        }
+    }
+}
+
+impl Experience {
+    /// Experience needed to go from the current level to the next.
+    pub fn to_next_level(&self) -> u32 {
+        self.level.max(1) * XP_PER_LEVEL
+    }
+
+    /// Add experience, returning how many levels were gained.
+    pub fn gain(&mut self, amount: u32) -> u32 {
+        self.current += amount;
+        let mut gained = 0;
+        while self.current >= self.to_next_level() {
+            self.current -= self.to_next_level();
+            self.level += 1;
+            self.unspent_points += POINTS_PER_LEVEL;
+            gained += 1;
+        }
+        gained
+    }
+}
+
 /// Tracks player movement state for physics.
 #[derive(Component)]
 pub struct MovementState {
@@ -71,32 +171,98 @@ impl Default for MovementState {
     }
 }
 
+/// An active dodge roll, moving the player in a fixed direction.
+///
+/// Inserted when a roll starts and removed once `remaining` runs out. The
+/// player's invincibility frames cover the roll.
+#[derive(Component)]
+pub struct DodgeRoll {
+    /// Horizontal world-space direction of the roll (normalized)
+    pub direction: Vec3,
+    /// Seconds of the roll left
+    pub remaining: f32,
+}
+
 /// Configuration for the first-person camera controller.
 #[derive(Resource)]
 pub struct PlayerConfig {
-    /// Mouse sensitivity multiplier
-    pub mouse_sensitivity: f32,
-    /// Invert Y-axis for mouse look
-    pub invert_y: bool,
+    /// Time constant in seconds of the look smoothing filter (when enabled in options)
+    pub look_smoothing: f32,
+    /// Extra sensitivity per 1000 px/s of mouse speed (when acceleration is enabled)
+    pub mouse_acceleration: f32,
     /// Base movement speed in units per second
     pub move_speed: f32,
     /// Sprint speed multiplier
     pub sprint_multiplier: f32,
+    /// Stamina drained per second while sprinting
+    pub sprint_drain_rate: f32,
+    /// Allow sprinting while attacking or blocking
+    pub sprint_in_combat: bool,
+    /// Movement speed multiplier while blocking (1.0 = no slowdown)
+    pub block_move_multiplier: f32,
     /// Jump velocity
     pub jump_force: f32,
+    /// Speed of a dodge roll in units per second
+    pub dodge_speed: f32,
+    /// Duration of a dodge roll in seconds (invincible throughout)
+    pub dodge_duration: f32,
+    /// Stamina cost of a dodge roll
+    pub dodge_stamina_cost: f32,
     /// Gravity acceleration
     pub gravity: f32,
+    /// Out-of-combat health regeneration per second (0.0 = disabled)
+    pub health_regen_rate: f32,
+    /// Seconds without taking damage before health starts regenerating
+    pub health_regen_delay: f32,
+    /// Maximum step height the character controller climbs automatically
+    pub autostep_height: f32,
+    /// Minimum landing width required on top of a step
+    pub autostep_min_width: f32,
+    /// Steepest slope the player can walk up, in degrees
+    pub max_slope_climb_angle: f32,
+    /// Slopes steeper than this (in degrees) make the player slide
+    pub min_slope_slide_angle: f32,
+    /// Distance to snap down to the ground on slopes/stairs (0.0 = disabled)
+    pub snap_to_ground: f32,
+    /// Camera height above the player's origin
+    pub eye_height: f32,
+    /// Height of the walking head bob in units (0.0 = disabled)
+    pub headbob_amount: f32,
+    /// Head bob cycles per second at walking speed
+    pub headbob_speed: f32,
+    /// Resting position of the weapon viewmodel relative to the camera
+    pub weapon_offset: Vec3,
+    /// Scene for first-person arms holding the weapon (`None` = weapon only)
+    pub arms_model_path: Option<String>,
 }
 
 impl Default for PlayerConfig {
     fn default() -> Self {
         Self {
-            mouse_sensitivity: 1.5,
-            invert_y: false,
+            look_smoothing: 0.03,
+            mouse_acceleration: 0.25,
             move_speed: 5.0,
             sprint_multiplier: 1.5,
+            sprint_drain_rate: 10.0,
+            sprint_in_combat: false,
+            block_move_multiplier: 0.6,
             jump_force: 6.0,
+            dodge_speed: 12.0,
+            dodge_duration: 0.3,
+            dodge_stamina_cost: 25.0,
             gravity: 15.0,
+            health_regen_rate: 0.0,
+            health_regen_delay: 5.0,
+            autostep_height: 0.4,
+            autostep_min_width: 0.3,
+            max_slope_climb_angle: 45.0,
+            min_slope_slide_angle: 30.0,
+            snap_to_ground: 0.5,
+            eye_height: 0.4,
+            headbob_amount: 0.03,
+            headbob_speed: 1.8,
+            weapon_offset: Vec3::new(0.3, -0.2, -0.5),
+            arms_model_path: None,
         }
     }
 }
@@ -5,5 +5,8 @@ mod movement;
 mod plugin;
 
 pub use components::*;
-pub use movement::{spawn_player, PlayerCamera, WeaponCamera};
+pub use movement::{
+    player_movement, spawn_player, CameraBaseTransform, HeadBob, PlayerCamera, WeaponCamera,
+    WeaponLight,
+};
 pub use plugin::PlayerPlugin;
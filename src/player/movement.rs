@@ -5,14 +5,21 @@ use bevy::core_pipeline::core_3d::Camera3dDepthLoadOp;
 use bevy::input::mouse::MouseMotion;
 use bevy::pbr::FogFalloff;
 use bevy::render::camera::ClearColorConfig;
-use bevy::render::view::RenderLayers;
+use bevy::time::Real;
 use bevy::window::{CursorGrabMode, PrimaryWindow};
 use bevy_rapier3d::prelude::*;
+use std::f32::consts::TAU;
 
 use super::components::*;
-use crate::combat::{create_starter_weapon, CombatState, Health, Resistances, Stamina};
-use crate::core::{GameState, PlayState};
-use crate::rendering::{PostProcessSettings, VisualConfig};
+use crate::combat::{
+    CombatState, Health, HealthRegen, Knockback, Resistances, Stamina, Weapon,
+};
+use crate::core::{Action, ActionInput, GameState, LookOptions, PlayState, ReduceMotion};
+use crate::inventory::Inventory;
+use crate::rendering::{
+    single_camera_layers, viewmodel_layers, world_layers, PostProcessSettings, VisualConfig,
+    WorldCamera,
+};
 
 /// Marker component for the player's camera.
 #[derive(Component)]
@@ -27,25 +34,83 @@ impl Default for PlayerCamera {
     }
 }
 
+/// The player camera's local transform without screen shake.
+///
+/// Look and eye height systems keep this up to date alongside the camera's
+/// `Transform`. Screen shake rebuilds the `Transform` from it every frame, so
+/// shake offsets never accumulate into the view or disturb the pitch.
+#[derive(Component, Clone, Copy, Default)]
+pub struct CameraBaseTransform(pub Transform);
+
+/// Walking head bob of the player camera.
+///
+/// `update_head_bob` advances it from the player's horizontal speed and
+/// screen shake adds `offset` on top of `CameraBaseTransform`, so bob never
+/// touches the look rotation.
+#[derive(Component, Default)]
+pub struct HeadBob {
+    /// Position in the bob cycle in radians
+    pub phase: f32,
+    /// Current offset from the base camera position
+    pub offset: Vec3,
+}
+
 /// Marker for the weapon-only camera (renders viewmodel on separate layer).
 #[derive(Component)]
 pub struct WeaponCamera;
 
+/// Marker for the light that only illuminates the weapon viewmodel.
+#[derive(Component)]
+pub struct WeaponLight;
+
+/// Upper bound on the mouse acceleration sensitivity multiplier.
+const MAX_ACCELERATION: f32 = 2.0;
+
+/// Eye height / weapon offset change per second while tuning the view.
+const VIEW_TUNING_SPEED: f32 = 0.25;
+
+/// Horizontal speeds below this count as standing still for head bob.
+const HEAD_BOB_MIN_SPEED: f32 = 0.1;
+
+/// How fast head bob eases back to neutral when standing still, per second.
+const HEAD_BOB_RETURN_SPEED: f32 = 8.0;
+
+/// Upper bound on the head bob amplitude multiplier from speed (sprinting).
+const HEAD_BOB_MAX_SCALE: f32 = 1.5;
+
+/// Near plane of the main camera when it also draws the viewmodel, close
+/// enough for the shrunken weapon (see `combat::viewmodel`).
+const SINGLE_CAMERA_NEAR_PLANE: f32 = 0.01;
+
 /// Set up player movement systems.
 pub fn setup_movement_systems(app: &mut App) {
     app
         .init_resource::<PlayerConfig>()
         .add_systems(OnEnter(GameState::InGame), grab_cursor)
         .add_systems(OnExit(GameState::InGame), release_cursor)
+        // The attribute screen needs the cursor for its buttons
+        .add_systems(OnEnter(PlayState::LevelUp), release_cursor)
+        .add_systems(OnExit(PlayState::LevelUp), grab_cursor)
         .add_systems(
             Update,
             (
                 mouse_look,
-                player_movement,
+                (start_dodge_roll, player_movement, update_dodge_roll).chain(),
+                update_head_bob,
             )
             .run_if(in_state(GameState::InGame))
             .run_if(in_state(PlayState::Exploring))
+        )
+        .add_systems(
+            Update,
+            apply_eye_height
+                .run_if(in_state(GameState::InGame))
+                .run_if(resource_changed::<PlayerConfig>),
         );
+
+    // Live view tuning is a development aid only
+    #[cfg(debug_assertions)]
+    app.add_systems(Update, tune_view_input.run_if(in_state(GameState::InGame)));
 }
 
 /// Grab and hide cursor when entering gameplay.
@@ -64,15 +129,26 @@ fn release_cursor(mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
     }
 }
 
+/// The camera's transform, its unshaken base and its pitch state.
+type LookCameraData<'a> = (
+    &'a mut Transform,
+    &'a mut CameraBaseTransform,
+    &'a mut PlayerCamera,
+);
+
 /// Handle mouse movement for looking around.
 ///
 /// Rotates the player entity horizontally (yaw) and the camera vertically (pitch).
 /// The camera is a child of the player, so horizontal rotation affects both.
+/// Uses real time, so hit stop doesn't freeze smoothing or drop input.
 pub fn mouse_look(
     mut mouse_motion: EventReader<MouseMotion>,
+    time: Res<Time<Real>>,
     config: Res<PlayerConfig>,
+    look: Res<LookOptions>,
+    mut smoothed: Local<Vec2>,
     mut player_query: Query<&mut Transform, With<Player>>,
-    mut camera_query: Query<(&mut Transform, &mut PlayerCamera), (With<Camera3d>, Without<Player>)>,
+    mut camera_query: Query<LookCameraData, (With<Camera3d>, Without<Player>)>,
 ) {
     // Accumulate mouse movement
     let mut delta = Vec2::ZERO;
@@ -80,6 +156,26 @@ pub fn mouse_look(
         delta += event.delta;
     }
 
+    let dt = time.delta_secs();
+
+    // Acceleration: faster flicks turn further
+    if look.acceleration && dt > 0.0 {
+        let speed = delta.length() / dt;
+        delta *= (1.0 + config.mouse_acceleration * speed / 1000.0).min(MAX_ACCELERATION);
+    }
+
+    // Smoothing: exponential low-pass over the per-frame delta
+    if look.smoothing && config.look_smoothing > 0.0 {
+        let alpha = 1.0 - (-dt / config.look_smoothing).exp();
+        *smoothed = smoothed.lerp(delta, alpha);
+        if smoothed.length_squared() < 0.0001 {
+            *smoothed = Vec2::ZERO;
+        }
+        delta = *smoothed;
+    } else {
+        *smoothed = Vec2::ZERO;
+    }
+
     if delta == Vec2::ZERO {
         return;
     }
@@ -88,12 +184,12 @@ pub fn mouse_look(
     let Ok(mut player_transform) = player_query.get_single_mut() else {
         return;
     };
-    let Ok((mut camera_transform, mut camera)) = camera_query.get_single_mut() else {
+    let Ok((mut camera_transform, mut base, mut camera)) = camera_query.get_single_mut() else {
         return;
     };
 
-    let sensitivity = config.mouse_sensitivity * 0.001;
-    let y_invert = if config.invert_y { -1.0 } else { 1.0 };
+    let sensitivity = look.sensitivity * 0.001;
+    let y_invert = if look.invert_y { -1.0 } else { 1.0 };
 
     // Rotate player horizontally (yaw)
     player_transform.rotate_y(-delta.x * sensitivity);
@@ -102,14 +198,15 @@ pub fn mouse_look(
     camera.pitch -= delta.y * sensitivity * y_invert;
     camera.pitch = camera.pitch.clamp(-1.4, 1.4); // About 80 degrees
 
-    camera_transform.rotation = Quat::from_rotation_x(camera.pitch);
+    base.0.rotation = Quat::from_rotation_x(camera.pitch);
+    camera_transform.rotation = base.0.rotation;
 }
 
-/// Handle WASD movement and jumping.
+/// Handle movement and jumping.
 ///
 /// Uses Rapier's KinematicCharacterController for collision detection.
 pub fn player_movement(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     time: Res<Time>,
     config: Res<PlayerConfig>,
     rapier_context: Query<&RapierContext>,
@@ -118,9 +215,25 @@ pub fn player_movement(
         &Transform,
         &mut MovementState,
         &mut KinematicCharacterController,
+        Option<&Knockback>,
+        Option<&CombatState>,
+        Option<&Attributes>,
+        Option<&DodgeRoll>,
+        Option<&mut Stamina>,
     ), With<Player>>,
 ) {
-    let Ok((player_entity, transform, mut movement_state, mut controller)) = player_query.get_single_mut() else {
+    let Ok((
+        player_entity,
+        transform,
+        mut movement_state,
+        mut controller,
+        knockback,
+        combat,
+        attributes,
+        dodge,
+        stamina,
+    )) = player_query.get_single_mut()
+    else {
         return;
     };
 
@@ -150,7 +263,7 @@ pub fn player_movement(
         if movement_state.vertical_velocity < 0.0 {
             movement_state.vertical_velocity = 0.0;
         }
-        if keyboard.just_pressed(KeyCode::Space) {
+        if input.just_pressed(Action::Jump) {
             movement_state.vertical_velocity = config.jump_force;
         }
     } else {
@@ -158,60 +271,211 @@ pub fn player_movement(
         movement_state.vertical_velocity -= config.gravity * time.delta_secs();
     }
 
-    // Build input direction from WASD
+    let movement = input_direction(&input, transform);
+
+    // Apply sprint if shift is held (not mid-swing or while blocking, unless allowed)
+    let is_attacking = combat.is_some_and(|combat| combat.is_attacking);
+    let is_blocking = combat.is_some_and(|combat| combat.is_blocking);
+    let can_sprint = (config.sprint_in_combat || !(is_attacking || is_blocking))
+        && movement != Vec3::ZERO
+        && dodge.is_none();
+    let mut is_sprinting = input.pressed(Action::Sprint) && can_sprint;
+
+    // Sprinting drains stamina like blocking does, and stops once it's gone
+    if is_sprinting {
+        if let Some(mut stamina) = stamina {
+            if stamina.current > 0.0 {
+                let drain = config.sprint_drain_rate * time.delta_secs();
+                stamina.current = (stamina.current - drain).max(0.0);
+                stamina.regen_timer = stamina.regen_delay;
+            } else {
+                is_sprinting = false;
+            }
+        }
+    }
+
+    let mut speed = if is_sprinting {
+        config.move_speed * config.sprint_multiplier
+    } else {
+        config.move_speed
+    };
+    if is_blocking {
+        speed *= config.block_move_multiplier;
+    }
+    speed *= attributes.map_or(1.0, Attributes::move_speed_multiplier);
+
+    // Calculate final translation; a dodge roll overrides walking
+    let mut horizontal = match dodge {
+        Some(dodge) => dodge.direction * config.dodge_speed * time.delta_secs(),
+        None => movement * speed * time.delta_secs(),
+    };
+
+    // Knockback goes through the controller so Rapier stops it at walls
+    if let Some(knockback) = knockback {
+        horizontal += knockback.velocity * time.delta_secs();
+    }
+    let vertical = Vec3::new(0.0, movement_state.vertical_velocity * time.delta_secs(), 0.0);
+
+    controller.translation = Some(horizontal + vertical);
+}
+
+/// Bob the camera while the player walks.
+///
+/// The cycle advances with horizontal speed, so sprinting bobs faster and
+/// harder, and eases back to neutral when standing still or airborne.
+/// Disabled by `headbob_amount` 0 or reduce motion.
+fn update_head_bob(
+    time: Res<Time>,
+    config: Res<PlayerConfig>,
+    reduce_motion: Res<ReduceMotion>,
+    player_query: Query<(&MovementState, Option<&KinematicCharacterControllerOutput>), With<Player>>,
+    mut camera_query: Query<&mut HeadBob, With<PlayerCamera>>,
+) {
+    let Ok((movement_state, output)) = player_query.get_single() else {
+        return;
+    };
+    let Ok(mut bob) = camera_query.get_single_mut() else {
+        return;
+    };
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    let speed = output.map_or(0.0, |output| {
+        output.effective_translation.with_y(0.0).length() / delta
+    });
+    let enabled = config.headbob_amount > 0.0 && !reduce_motion.enabled;
+
+    if !enabled || !movement_state.is_grounded || speed < HEAD_BOB_MIN_SPEED {
+        let ease = (HEAD_BOB_RETURN_SPEED * delta).min(1.0);
+        bob.offset = bob.offset.lerp(Vec3::ZERO, ease);
+        if bob.offset.length_squared() < 1e-8 {
+            bob.offset = Vec3::ZERO;
+            bob.phase = 0.0;
+        }
+        return;
+    }
+
+    let speed_scale = (speed / config.move_speed.max(0.01)).min(HEAD_BOB_MAX_SCALE);
+    bob.phase = (bob.phase + TAU * config.headbob_speed * speed_scale * delta) % TAU;
+
+    // Two vertical dips per side-to-side sway, one per footstep
+    let amount = config.headbob_amount * speed_scale;
+    bob.offset = Vec3::new(
+        bob.phase.cos() * amount * 0.5,
+        -(bob.phase.sin().abs()) * amount,
+        0.0,
+    );
+}
+
+/// Horizontal world-space movement direction from the movement actions,
+/// relative to where the player is facing. Zero when none are held.
+fn input_direction(input: &ActionInput, transform: &Transform) -> Vec3 {
     let mut direction = Vec3::ZERO;
-    if keyboard.pressed(KeyCode::KeyW) {
+    if input.pressed(Action::MoveForward) {
         direction.z -= 1.0;
     }
-    if keyboard.pressed(KeyCode::KeyS) {
+    if input.pressed(Action::MoveBack) {
         direction.z += 1.0;
     }
-    if keyboard.pressed(KeyCode::KeyA) {
+    if input.pressed(Action::MoveLeft) {
         direction.x -= 1.0;
     }
-    if keyboard.pressed(KeyCode::KeyD) {
+    if input.pressed(Action::MoveRight) {
         direction.x += 1.0;
     }
 
     // Normalize to prevent faster diagonal movement
-    if direction != Vec3::ZERO {
-        direction = direction.normalize();
-    }
+    let direction = direction.normalize_or_zero();
 
     // Rotate direction to face where player is looking (only horizontal)
     let yaw = transform.rotation.to_euler(EulerRot::YXZ).0;
-    let rotation = Quat::from_rotation_y(yaw);
-    let movement = rotation * direction;
+    Quat::from_rotation_y(yaw) * direction
+}
 
-    // Apply sprint if shift is held
-    let speed = if keyboard.pressed(KeyCode::ShiftLeft) {
-        config.move_speed * config.sprint_multiplier
-    } else {
-        config.move_speed
+/// The player's side of a dodge roll about to start.
+type DodgerData<'a> = (Entity, &'a Transform, &'a mut Stamina, &'a mut CombatState);
+
+/// Start a dodge roll when the dodge action is pressed.
+///
+/// Rolls in the held movement direction, or backward with no input. Costs
+/// stamina and grants invincibility frames for the roll's duration. Ignored
+/// while already rolling, mid-swing, or without enough stamina.
+fn start_dodge_roll(
+    mut commands: Commands,
+    input: ActionInput,
+    config: Res<PlayerConfig>,
+    mut player_query: Query<DodgerData, (With<Player>, Without<DodgeRoll>)>,
+) {
+    if !input.just_pressed(Action::Dodge) {
+        return;
+    }
+    let Ok((entity, transform, mut stamina, mut combat)) = player_query.get_single_mut() else {
+        return;
     };
+    if combat.is_attacking || !stamina.use_stamina(config.dodge_stamina_cost) {
+        return;
+    }
 
-    // Calculate final translation
-    let horizontal = movement * speed * time.delta_secs();
-    let vertical = Vec3::new(0.0, movement_state.vertical_velocity * time.delta_secs(), 0.0);
+    let mut direction = input_direction(&input, transform);
+    if direction == Vec3::ZERO {
+        let yaw = transform.rotation.to_euler(EulerRot::YXZ).0;
+        direction = Quat::from_rotation_y(yaw) * Vec3::Z;
+    }
 
-    controller.translation = Some(horizontal + vertical);
+    // Rolling cancels a charging attack
+    combat.is_charging = false;
+    combat.charge_time = 0.0;
+    combat.i_frames = combat.i_frames.max(config.dodge_duration);
+
+    commands.entity(entity).insert(DodgeRoll {
+        direction,
+        remaining: config.dodge_duration,
+    });
+}
+
+/// Count down active dodge rolls and end them when they run out.
+fn update_dodge_roll(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DodgeRoll)>,
+) {
+    for (entity, mut dodge) in query.iter_mut() {
+        dodge.remaining -= time.delta_secs();
+        if dodge.remaining <= 0.0 {
+            commands.entity(entity).remove::<DodgeRoll>();
+        }
+    }
 }
 
-/// Spawn the player entity with camera.
-pub fn spawn_player(commands: &mut Commands, position: Vec3, visual_config: &VisualConfig) -> Entity {
+/// Spawn the player entity with camera, wielding `weapon`.
+pub fn spawn_player(
+    commands: &mut Commands,
+    position: Vec3,
+    config: &PlayerConfig,
+    visual_config: &VisualConfig,
+    weapon: Weapon,
+) -> Entity {
     // Spawn player body
     let player = commands
         .spawn((
             Player,
             PlayerStats::default(),
             Attributes::default(),
+            Experience::default(),
             MovementState::default(),
+            Inventory::default(),
             // Combat components
-            Health::new(100.0),
-            Stamina::default(),
-            CombatState::default(),
-            Resistances::default(),
-            create_starter_weapon(),
+            (
+                Health::new(100.0),
+                HealthRegen::default(),
+                Knockback::default(),
+                Stamina::default(),
+                CombatState::default(),
+                Resistances::default(),
+                weapon,
+            ),
             // Transform
             Transform::from_translation(position),
             GlobalTransform::default(),
@@ -223,15 +487,16 @@ pub fn spawn_player(commands: &mut Commands, position: Vec3, visual_config: &Vis
                 offset: CharacterLength::Absolute(0.01),
                 // Enable automatic stair climbing
                 autostep: Some(CharacterAutostep {
-                    max_height: CharacterLength::Absolute(0.4),  // ~40cm step height
-                    min_width: CharacterLength::Absolute(0.3),   // Minimum landing space
+                    max_height: CharacterLength::Absolute(config.autostep_height),
+                    min_width: CharacterLength::Absolute(config.autostep_min_width),
                     include_dynamic_bodies: false,
                 }),
                 // Slope handling
-                max_slope_climb_angle: 45_f32.to_radians(),
-                min_slope_slide_angle: 30_f32.to_radians(),
+                max_slope_climb_angle: config.max_slope_climb_angle.to_radians(),
+                min_slope_slide_angle: config.min_slope_slide_angle.to_radians(),
                 // Snap to ground when going down slopes/stairs
-                snap_to_ground: Some(CharacterLength::Absolute(0.5)),
+                snap_to_ground: (config.snap_to_ground > 0.0)
+                    .then_some(CharacterLength::Absolute(config.snap_to_ground)),
                 ..default()
             },
         ))
@@ -246,71 +511,229 @@ pub fn spawn_player(commands: &mut Commands, position: Vec3, visual_config: &Vis
 
     // Spawn camera as child of player
     commands.entity(player).with_children(|parent| {
-        parent
+        let mut camera = parent.spawn((
+            Camera3d::default(),
+            Camera {
+                // Clear color from config
+                clear_color: ClearColorConfig::Custom(Color::srgb(
+                    visual_config.clear_color.0,
+                    visual_config.clear_color.1,
+                    visual_config.clear_color.2,
+                )),
+                ..default()
+            },
+            // Atmospheric fog from config
+            DistanceFog {
+                color: Color::srgba(
+                    visual_config.fog_color.0,
+                    visual_config.fog_color.1,
+                    visual_config.fog_color.2,
+                    1.0,
+                ),
+                falloff: fog_falloff,
+                directional_light_color: Color::NONE,
+                directional_light_exponent: 8.0,
+            },
+            // Horror post-processing from config
+            PostProcessSettings::from_config(visual_config),
+            PlayerCamera::default(),
+            WorldCamera,
+            // Position camera at "eye level" relative to player
+            Transform::from_xyz(0.0, config.eye_height, 0.0),
+            CameraBaseTransform(Transform::from_xyz(0.0, config.eye_height, 0.0)),
+            HeadBob::default(),
+            // Main camera renders the world layer
+            world_layers(),
+        ));
+
+        if visual_config.single_camera_viewmodel {
+            // Compatibility mode: the main camera also draws the viewmodel,
+            // sharing the world's depth buffer. No weapon light, since this
+            // camera would apply it to the world too
+            camera.insert((
+                single_camera_layers(),
+                Projection::from(PerspectiveProjection {
+                    near: SINGLE_CAMERA_NEAR_PLANE,
+                    ..default()
+                }),
+            ));
+            return;
+        }
+
+        camera.with_children(|camera_parent| {
+            // Weapon camera renders the viewmodel layer
+            camera_parent
+                .spawn((
+                    WeaponCamera,
+                    Camera3d {
+                        depth_load_op: Camera3dDepthLoadOp::Clear(0.0),
+                        ..default()
+                    },
+                    Camera {
+                        order: 1,
+                        clear_color: ClearColorConfig::None,
+                        ..default()
+                    },
+                    Transform::default(),
+                    viewmodel_layers(),
+                ))
+                .with_children(|weapon_camera| {
+                    weapon_camera.spawn(weapon_light());
+                });
+        });
+    });
+
+    player
+}
+
+/// Dedicated light for the weapon viewmodel. Confined to the viewmodel layer,
+/// so it never lights world geometry.
+fn weapon_light() -> impl Bundle {
+    (
+        WeaponLight,
+        PointLight {
+            color: Color::srgb(1.0, 0.9, 0.8),
+            intensity: 100000.0,
+            range: 10.0,
+            shadows_enabled: false,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.5, 0.5),
+        viewmodel_layers(),
+    )
+}
+
+/// Move the camera when the configured eye height changes.
+fn apply_eye_height(
+    config: Res<PlayerConfig>,
+    mut camera_query: Query<(&mut Transform, &mut CameraBaseTransform), With<PlayerCamera>>,
+) {
+    for (mut transform, mut base) in camera_query.iter_mut() {
+        if base.0.translation.y != config.eye_height {
+            base.0.translation.y = config.eye_height;
+            transform.translation.y = config.eye_height;
+        }
+    }
+}
+
+/// Tune the view while holding F11: arrow keys move the weapon, PageUp and
+/// PageDown change the eye height. Values are logged on release so they can
+/// be copied into `PlayerConfig`.
+#[cfg(debug_assertions)]
+fn tune_view_input(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut config: ResMut<PlayerConfig>,
+) {
+    if keyboard.just_released(KeyCode::F11) {
+        info!(
+            "View tuning: eye_height {:.2}, weapon_offset {:.2}",
+            config.eye_height, config.weapon_offset
+        );
+        return;
+    }
+    if !keyboard.pressed(KeyCode::F11) {
+        return;
+    }
+
+    let axis = |negative: KeyCode, positive: KeyCode| {
+        keyboard.pressed(positive) as i8 as f32 - keyboard.pressed(negative) as i8 as f32
+    };
+    let step = VIEW_TUNING_SPEED * time.delta_secs();
+    let weapon_delta = Vec3::new(
+        axis(KeyCode::ArrowLeft, KeyCode::ArrowRight),
+        axis(KeyCode::ArrowDown, KeyCode::ArrowUp),
+        0.0,
+    ) * step;
+    let eye_delta = axis(KeyCode::PageDown, KeyCode::PageUp) * step;
+
+    // Only touch the config when something changes, so change detection stays quiet
+    if weapon_delta != Vec3::ZERO {
+        config.weapon_offset += weapon_delta;
+    }
+    if eye_delta != 0.0 {
+        config.eye_height += eye_delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::KeyBindings;
+    use bevy::scene::ScenePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    /// The wall's face toward the player, on the X axis.
+    const WALL_FACE: f32 = 2.0;
+
+    /// Player capsule radius, matching `spawn_player`.
+    const PLAYER_RADIUS: f32 = 0.3;
+
+    /// Physics with a floor, a wall ahead of the player on +X, and a player
+    /// standing at the origin.
+    fn knockback_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            AssetPlugin::default(),
+            ScenePlugin,
+            RapierPhysicsPlugin::<NoUserData>::default(),
+        ))
+        .init_asset::<Mesh>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(1.0 / 60.0)))
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<ButtonInput<MouseButton>>()
+        .init_resource::<KeyBindings>()
+        .init_resource::<PlayerConfig>()
+        .add_systems(Update, player_movement);
+
+        app.world_mut().spawn((
+            RigidBody::Fixed,
+            Collider::cuboid(10.0, 0.5, 10.0),
+            Transform::from_xyz(0.0, -0.5, 0.0),
+        ));
+        app.world_mut().spawn((
+            RigidBody::Fixed,
+            Collider::cuboid(0.5, 2.0, 5.0),
+            Transform::from_xyz(WALL_FACE + 0.5, 1.0, 0.0),
+        ));
+        let player = app
+            .world_mut()
             .spawn((
-                Camera3d::default(),
-                Camera {
-                    // Clear color from config
-                    clear_color: ClearColorConfig::Custom(Color::srgb(
-                        visual_config.clear_color.0,
-                        visual_config.clear_color.1,
-                        visual_config.clear_color.2,
-                    )),
+                Player,
+                MovementState::default(),
+                Knockback::default(),
+                Transform::from_xyz(0.0, 0.85, 0.0),
+                RigidBody::KinematicPositionBased,
+                Collider::capsule_y(0.5, PLAYER_RADIUS),
+                KinematicCharacterController {
+                    offset: CharacterLength::Absolute(0.01),
                     ..default()
                 },
-                // Atmospheric fog from config
-                DistanceFog {
-                    color: Color::srgba(
-                        visual_config.fog_color.0,
-                        visual_config.fog_color.1,
-                        visual_config.fog_color.2,
-                        1.0,
-                    ),
-                    falloff: fog_falloff,
-                    directional_light_color: Color::NONE,
-                    directional_light_exponent: 8.0,
-                },
-                // Horror post-processing from config
-                PostProcessSettings::from_config(visual_config),
-                PlayerCamera::default(),
-                // Position camera at "eye level" relative to player
-                Transform::from_xyz(0.0, 0.4, 0.0),
-                // Main camera renders world on layer 0
-                RenderLayers::layer(0),
             ))
-            .with_children(|camera_parent| {
-                // Weapon camera renders viewmodel on layer 1
-                camera_parent
-                    .spawn((
-                        WeaponCamera,
-                        Camera3d {
-                            depth_load_op: Camera3dDepthLoadOp::Clear(0.0),
-                            ..default()
-                        },
-                        Camera {
-                            order: 1,
-                            clear_color: ClearColorConfig::None,
-                            ..default()
-                        },
-                        Transform::default(),
-                        RenderLayers::layer(1),
-                    ))
-                    .with_children(|weapon_camera| {
-                        // Dedicated light for weapon viewmodel (no shadows from world geometry)
-                        weapon_camera.spawn((
-                            PointLight {
-                                color: Color::srgb(1.0, 0.9, 0.8),
-                                intensity: 100000.0,
-                                range: 10.0,
-                                shadows_enabled: false,
-                                ..default()
-                            },
-                            Transform::from_xyz(0.0, 0.5, 0.5),
-                            RenderLayers::layer(1),
-                        ));
-                    });
-            });
-    });
+            .id();
+        (app, player)
+    }
 
-    player
+    #[test]
+    fn knockback_into_wall_stops_at_its_face() {
+        let (mut app, player) = knockback_app();
+        app.update();
+
+        // Strong enough to carry the player well past the wall in one frame
+        app.world_mut().get_mut::<Knockback>(player).unwrap().velocity =
+            Vec3::new(300.0, 0.0, 0.0);
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let x = app.world().get::<Transform>(player).unwrap().translation.x;
+        assert!(x > 1.0, "player should be pushed toward the wall, got x = {x}");
+        assert!(
+            x + PLAYER_RADIUS <= WALL_FACE + 0.001,
+            "player should stop in front of the wall, got x = {x}"
+        );
+    }
 }
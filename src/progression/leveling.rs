@@ -0,0 +1,64 @@
+//! Experience from kills and level-ups.
+//!
+//! Killing an enemy awards the `experience` from its definition. Each level
+//! gained grants attribute points, sends a `LevelUpEvent` and opens the
+//! attribute screen (`PlayState::LevelUp`), which pauses gameplay.
+
+use bevy::prelude::*;
+
+use crate::core::{EnemyKilledEvent, GameState, LevelUpEvent, PlayState};
+use crate::enemies::EnemyRegistry;
+use crate::player::{Attributes, Experience, Player, PlayerStats};
+
+/// Set up leveling systems.
+pub fn setup_leveling_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (award_experience, apply_attribute_stats)
+            .chain()
+            .run_if(in_state(GameState::InGame)),
+    );
+}
+
+/// Award experience for enemies the player killed.
+fn award_experience(
+    mut kill_events: EventReader<EnemyKilledEvent>,
+    registry: Res<EnemyRegistry>,
+    mut player_query: Query<(Entity, &mut Experience), With<Player>>,
+    mut level_up_events: EventWriter<LevelUpEvent>,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+) {
+    let Ok((player, mut experience)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in kill_events.read() {
+        if event.killer != Some(player) {
+            continue;
+        }
+        let Some(definition) = registry.get(&event.enemy_type) else {
+            continue;
+        };
+
+        let levels = experience.gain(definition.experience);
+        for offset in 0..levels {
+            let new_level = experience.level - levels + offset + 1;
+            info!("Player reached level {}", new_level);
+            level_up_events.send(LevelUpEvent { player, new_level });
+        }
+        if levels > 0 {
+            next_play_state.set(PlayState::LevelUp);
+        }
+    }
+}
+
+/// Recompute stats derived from attributes when they change.
+fn apply_attribute_stats(
+    mut query: Query<(&Attributes, &mut PlayerStats), Changed<Attributes>>,
+) {
+    let base_mana = PlayerStats::default().max_mana;
+    for (attributes, mut stats) in query.iter_mut() {
+        stats.max_mana = attributes.max_mana(base_mana);
+        stats.current_mana = stats.current_mana.min(stats.max_mana);
+    }
+}
@@ -0,0 +1,6 @@
+//! Progression module - experience, leveling and attribute effects.
+
+mod leveling;
+mod plugin;
+
+pub use plugin::ProgressionPlugin;
@@ -0,0 +1,15 @@
+//! Progression plugin - experience and leveling.
+
+use bevy::prelude::*;
+
+use super::leveling;
+
+/// Progression plugin - awards experience and applies attribute effects.
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        // Setup experience and level-up systems
+        leveling::setup_leveling_systems(app);
+    }
+}
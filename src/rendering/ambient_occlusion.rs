@@ -0,0 +1,87 @@
+//! Optional screen-space ambient occlusion.
+//!
+//! Darkens creases where floors meet walls and around pillars, grounding the
+//! tile geometry without per-level authoring. It costs a depth and normal
+//! prepass plus the SSAO pass itself, so it is off by default and toggled
+//! through `RenderConfig::ambient_occlusion` (F5 in game).
+
+use bevy::core_pipeline::prepass::{DepthPrepass, NormalPrepass};
+use bevy::pbr::ScreenSpaceAmbientOcclusion;
+use bevy::prelude::*;
+
+use super::plugin::RenderConfig;
+use super::resolution_scale::WorldCamera;
+
+/// Set up ambient occlusion systems.
+pub fn setup_ambient_occlusion_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (toggle_ambient_occlusion_input, apply_ambient_occlusion).chain(),
+    );
+}
+
+/// Toggle ambient occlusion with F5.
+fn toggle_ambient_occlusion_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut render_config: ResMut<RenderConfig>,
+) {
+    if keyboard.just_pressed(KeyCode::F5) {
+        render_config.ambient_occlusion = !render_config.ambient_occlusion;
+        info!(
+            "Ambient occlusion: {}",
+            if render_config.ambient_occlusion { "on" } else { "off" }
+        );
+    }
+}
+
+/// A world camera, whether it has SSAO and the cameras parented to it.
+type AoCameraData<'a> = (
+    Entity,
+    Ref<'a, WorldCamera>,
+    Has<ScreenSpaceAmbientOcclusion>,
+    Option<&'a Children>,
+);
+
+/// Add or remove SSAO on world cameras to match the config.
+///
+/// Also runs for newly spawned cameras so the setting survives level reloads.
+/// SSAO doesn't support MSAA, so MSAA is turned off while it is enabled.
+/// Cameras parented to a world camera (the weapon camera) draw into the same
+/// target and must use the same MSAA, so they follow it.
+fn apply_ambient_occlusion(
+    mut commands: Commands,
+    render_config: Res<RenderConfig>,
+    camera_query: Query<AoCameraData>,
+    child_camera_query: Query<(), (With<Camera>, Without<WorldCamera>)>,
+) {
+    for (entity, world_camera, has_ssao, children) in camera_query.iter() {
+        if !render_config.is_changed() && !world_camera.is_added() {
+            continue;
+        }
+
+        let msaa = if render_config.ambient_occlusion {
+            Msaa::Off
+        } else {
+            Msaa::default()
+        };
+        for &child in children.into_iter().flatten() {
+            if child_camera_query.contains(child) {
+                commands.entity(child).insert(msaa);
+            }
+        }
+
+        if render_config.ambient_occlusion && !has_ssao {
+            commands.entity(entity).insert((
+                ScreenSpaceAmbientOcclusion::default(),
+                DepthPrepass,
+                NormalPrepass,
+                Msaa::Off,
+            ));
+        } else if !render_config.ambient_occlusion && has_ssao {
+            commands
+                .entity(entity)
+                .remove::<(ScreenSpaceAmbientOcclusion, DepthPrepass, NormalPrepass)>()
+                .insert(Msaa::default());
+        }
+    }
+}
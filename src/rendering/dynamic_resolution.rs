@@ -0,0 +1,92 @@
+//! Adaptive resolution scaling.
+//!
+//! When enabled, `RenderConfig::resolution_scale` is lowered while the
+//! smoothed frame time is above the target and raised again once there is
+//! clear headroom. The band between the two thresholds plus a delay between
+//! adjustments keep the scale from oscillating.
+
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+use super::plugin::RenderConfig;
+
+/// Settings for adaptive resolution scaling. Off by default.
+#[derive(Resource, Clone, Debug)]
+pub struct DynamicResolution {
+    pub enabled: bool,
+    /// Frame time to stay under, in milliseconds
+    pub target_frame_ms: f64,
+    /// Scale is only raised when frame time is below `target_frame_ms * headroom`
+    pub headroom: f64,
+    /// Lowest allowed resolution scale
+    pub min_scale: f32,
+    /// Highest allowed resolution scale
+    pub max_scale: f32,
+    /// Scale change per adjustment
+    pub step: f32,
+    /// Minimum seconds between adjustments
+    pub interval: f32,
+}
+
+impl Default for DynamicResolution {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_frame_ms: 16.7,
+            headroom: 0.75,
+            min_scale: 0.5,
+            max_scale: 1.0,
+            step: 0.05,
+            interval: 0.5,
+        }
+    }
+}
+
+/// Set up adaptive resolution systems.
+pub fn setup_dynamic_resolution_systems(app: &mut App) {
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin);
+    }
+
+    app.init_resource::<DynamicResolution>().add_systems(
+        Update,
+        adjust_resolution_scale.run_if(|settings: Res<DynamicResolution>| settings.enabled),
+    );
+}
+
+/// Nudge the resolution scale toward the frame time target.
+pub fn adjust_resolution_scale(
+    time: Res<Time<Real>>,
+    diagnostics: Res<DiagnosticsStore>,
+    settings: Res<DynamicResolution>,
+    mut render_config: ResMut<RenderConfig>,
+    mut cooldown: Local<f32>,
+) {
+    *cooldown -= time.delta_secs();
+    if *cooldown > 0.0 {
+        return;
+    }
+
+    let Some(frame_ms) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|diagnostic| diagnostic.smoothed())
+    else {
+        return;
+    };
+
+    let current = render_config.resolution_scale;
+    let scale = if frame_ms > settings.target_frame_ms {
+        current - settings.step
+    } else if frame_ms < settings.target_frame_ms * settings.headroom {
+        current + settings.step
+    } else {
+        current
+    }
+    .clamp(settings.min_scale, settings.max_scale);
+
+    if scale != current {
+        debug!("Frame time {:.1}ms, resolution scale {:.2} -> {:.2}", frame_ms, current, scale);
+        render_config.resolution_scale = scale;
+        *cooldown = settings.interval;
+    }
+}
@@ -0,0 +1,51 @@
+//! Render layer assignments.
+//!
+//! The player has two cameras: the main camera draws the world and the weapon
+//! camera draws the first-person viewmodel on top of it. A camera only uses
+//! the lights on its own layers, so the layers also decide what is lit by what:
+//!
+//! - `WORLD_LAYER`: level geometry, enemies, pickups and all level lights.
+//!   Entities without `RenderLayers` are on this layer.
+//! - `VIEWMODEL_LAYER`: the weapon viewmodel, every entity of its loaded scene,
+//!   the weapon camera and the weapon's dedicated light. Nothing else may be
+//!   on this layer, so the weapon light never illuminates the world and world
+//!   lights never double-light the weapon. Only ambient light affects both.
+//!
+//! With `VisualConfig::single_camera_viewmodel` there is no weapon camera and
+//! the main camera renders both layers instead. That camera would apply the
+//! weapon light to the world as well, so the mode spawns no weapon light and
+//! the weapon is lit by the level lights like everything else.
+//!
+//! `PRESENT_LAYER` holds only the sprite showing the low-resolution world
+//! image and the camera drawing it to the window.
+
+use bevy::render::view::{Layer, RenderLayers};
+
+/// Layer rendered by the main camera.
+pub const WORLD_LAYER: Layer = 0;
+
+/// Layer rendered by the weapon camera.
+pub const VIEWMODEL_LAYER: Layer = 1;
+
+/// Layer rendered by the camera presenting the low-resolution world.
+pub const PRESENT_LAYER: Layer = 2;
+
+/// Render layers for world entities.
+pub fn world_layers() -> RenderLayers {
+    RenderLayers::layer(WORLD_LAYER)
+}
+
+/// Render layers for the viewmodel, weapon camera and weapon light.
+pub fn viewmodel_layers() -> RenderLayers {
+    RenderLayers::layer(VIEWMODEL_LAYER)
+}
+
+/// Render layers for the low-resolution world sprite and its camera.
+pub fn present_layers() -> RenderLayers {
+    RenderLayers::layer(PRESENT_LAYER)
+}
+
+/// Render layers for a main camera that also draws the viewmodel.
+pub fn single_camera_layers() -> RenderLayers {
+    RenderLayers::from_layers(&[WORLD_LAYER, VIEWMODEL_LAYER])
+}
@@ -1,9 +1,23 @@
 //! Rendering module - horror visual effects.
 
+mod ambient_occlusion;
+mod dynamic_resolution;
+pub mod layers;
+pub mod particles;
 mod plugin;
 mod post_process;
+mod resolution_scale;
+mod vertex_jitter;
 pub mod visual_config;
 
+pub use dynamic_resolution::DynamicResolution;
+pub use layers::{
+    present_layers, single_camera_layers, viewmodel_layers, world_layers, PRESENT_LAYER,
+    VIEWMODEL_LAYER, WORLD_LAYER,
+};
+pub use particles::{spawn_particle_burst, Particle, ParticleBurst};
 pub use plugin::{RenderConfig, RenderingPlugin};
-pub use post_process::{HorrorPostProcessPlugin, PostProcessSettings};
+pub use post_process::{HorrorPostProcessPlugin, PostProcessSettings, PostProcessToggles};
+pub use resolution_scale::WorldCamera;
+pub use vertex_jitter::{JitterMaterial, JitterSource, VertexJitter, VertexJitterExtension};
 pub use visual_config::VisualConfig;
@@ -0,0 +1,87 @@
+//! Simple CPU particles for one-shot bursts (hit effects, debris).
+//!
+//! Each particle is its own small mesh entity with a velocity and lifetime.
+//! Particles fall under gravity, shrink as they age and despawn themselves.
+
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::*;
+
+/// A single short-lived particle.
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec3,
+    /// Downward acceleration in units per second squared
+    pub gravity: f32,
+    /// Total lifetime in seconds
+    pub lifetime: f32,
+    /// Seconds since spawn
+    pub age: f32,
+}
+
+/// Parameters for a one-shot burst of particles.
+pub struct ParticleBurst {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+    /// Number of particles to spawn
+    pub count: usize,
+    /// Main direction of the burst (zero for an omnidirectional burst)
+    pub direction: Vec3,
+    /// How far particles deviate from `direction` (0.0 = tight cone, 1.0 = very wide)
+    pub spread: f32,
+    /// Initial speed range in units per second
+    pub speed: (f32, f32),
+    pub gravity: f32,
+    /// Lifetime range in seconds
+    pub lifetime: (f32, f32),
+}
+
+/// Spawn a burst of particles at `origin`.
+pub fn spawn_particle_burst(commands: &mut Commands, origin: Vec3, burst: &ParticleBurst) {
+    let direction = burst.direction.normalize_or_zero();
+
+    for _ in 0..burst.count {
+        let jitter = Vec3::new(
+            rand::random::<f32>() * 2.0 - 1.0,
+            rand::random::<f32>() * 2.0 - 1.0,
+            rand::random::<f32>() * 2.0 - 1.0,
+        );
+        let velocity_dir = (direction + jitter * burst.spread).normalize_or_zero();
+        let speed = burst.speed.0 + rand::random::<f32>() * (burst.speed.1 - burst.speed.0);
+        let lifetime =
+            burst.lifetime.0 + rand::random::<f32>() * (burst.lifetime.1 - burst.lifetime.0);
+
+        commands.spawn((
+            Particle {
+                velocity: velocity_dir * speed,
+                gravity: burst.gravity,
+                lifetime,
+                age: 0.0,
+            },
+            Mesh3d(burst.mesh.clone()),
+            MeshMaterial3d(burst.material.clone()),
+            Transform::from_translation(origin),
+            NotShadowCaster,
+        ));
+    }
+}
+
+/// Move, shrink and despawn particles.
+pub fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Particle, &mut Transform)>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut particle, mut transform) in query.iter_mut() {
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle.velocity.y -= particle.gravity * delta;
+        transform.translation += particle.velocity * delta;
+        transform.scale = Vec3::splat(1.0 - particle.age / particle.lifetime);
+    }
+}
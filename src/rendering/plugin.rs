@@ -5,12 +5,22 @@
 //! - Film grain post-processing
 //! - CRT scanlines
 //! - Vignette effect
+//! - One-shot particle bursts
+//! - Low-resolution world rendering with nearest-neighbor upscaling
+//! - Optional adaptive resolution scaling
+//! - Optional screen-space ambient occlusion
+//! - Optional PS1-style vertex jitter
 //!
 //! All effects configurable via assets/data/rendering/visual_config.ron.
 
 use bevy::prelude::*;
 
+use super::ambient_occlusion::setup_ambient_occlusion_systems;
+use super::dynamic_resolution::setup_dynamic_resolution_systems;
+use super::particles::update_particles;
 use super::post_process::HorrorPostProcessPlugin;
+use super::resolution_scale::setup_resolution_scale_systems;
+use super::vertex_jitter::setup_vertex_jitter_systems;
 use super::visual_config::{load_visual_config, VisualConfig};
 
 /// Rendering plugin - configures horror-style visuals.
@@ -20,12 +30,26 @@ impl Plugin for RenderingPlugin {
     fn build(&self, app: &mut App) {
         // Load visual config from RON file
         let visual_config = VisualConfig::load();
+        app.insert_resource(RenderConfig {
+            resolution_scale: visual_config.resolution_scale,
+            vertex_jitter: visual_config.vertex_jitter,
+            ..default()
+        });
         app.insert_resource(visual_config);
-        app.insert_resource(RenderConfig::default());
         // Load visual config system (for potential hot-reloading in future)
         app.add_systems(Startup, load_visual_config);
         // Add horror post-processing effects
         app.add_plugins(HorrorPostProcessPlugin);
+        // Particles run in every state so bursts always finish and despawn
+        app.add_systems(Update, update_particles);
+        // Low-resolution world rendering (native by default)
+        setup_resolution_scale_systems(app);
+        // Adaptive resolution scaling (off by default)
+        setup_dynamic_resolution_systems(app);
+        // Screen-space ambient occlusion (off by default)
+        setup_ambient_occlusion_systems(app);
+        // PS1 vertex jitter on level geometry and enemies (off by default)
+        setup_vertex_jitter_systems(app);
     }
 }
 
@@ -40,6 +64,8 @@ pub struct RenderConfig {
     pub fog_enabled: bool,
     /// Fog density (exponential squared)
     pub fog_density: f32,
+    /// Screen-space ambient occlusion on the world camera
+    pub ambient_occlusion: bool,
 }
 
 impl Default for RenderConfig {
@@ -49,6 +75,7 @@ impl Default for RenderConfig {
             vertex_jitter: 0.0,
             fog_enabled: true,
             fog_density: 0.025,
+            ambient_occlusion: false,
         }
     }
 }
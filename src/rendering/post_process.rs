@@ -1,10 +1,12 @@
-//! Horror post-processing effects: film grain, CRT scanlines, and vignette.
+//! Horror post-processing effects: film grain, CRT scanlines, vignette, and
+//! ordered dithering with color depth reduction.
 //!
 //! Implements a fullscreen post-processing pass using Bevy 0.15's render graph.
 
 use bevy::{
     asset::load_internal_asset,
     core_pipeline::{
+        core_2d::graph::{Core2d, Node2d},
         core_3d::graph::{Core3d, Node3d},
         fullscreen_vertex_shader::fullscreen_shader_vertex_state,
     },
@@ -54,9 +56,17 @@ impl Plugin for HorrorPostProcessPlugin {
             UniformComponentPlugin::<PostProcessSettings>::default(),
         ));
 
+        app.init_resource::<PostProcessToggles>();
+
         // Add system to update time
         app.add_systems(Update, update_post_process_time);
 
+        // Runtime effect toggles
+        app.add_systems(
+            Update,
+            (toggle_post_process_input, apply_post_process_toggles).chain(),
+        );
+
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
         };
@@ -70,6 +80,16 @@ impl Plugin for HorrorPostProcessPlugin {
                     PostProcessLabel,
                     Node3d::EndMainPassPostProcessing,
                 ),
+            )
+            // The camera presenting the low-resolution world is a 2D camera
+            .add_render_graph_node::<ViewNodeRunner<PostProcessNode>>(Core2d, PostProcessLabel)
+            .add_render_graph_edges(
+                Core2d,
+                (
+                    Node2d::Tonemapping,
+                    PostProcessLabel,
+                    Node2d::EndMainPassPostProcessing,
+                ),
             );
     }
 
@@ -87,7 +107,8 @@ impl Plugin for HorrorPostProcessPlugin {
 struct PostProcessLabel;
 
 /// Settings for horror post-processing effects.
-/// Add this component to your camera to enable effects.
+/// Add this component to your camera to enable effects. While the world is
+/// rendered at a reduced resolution it lives on the camera presenting it.
 #[derive(Component, Clone, Copy, ExtractComponent, ShaderType)]
 pub struct PostProcessSettings {
     /// Film grain intensity (0.0 = none, 0.15 = heavy). Default: 0.006
@@ -104,6 +125,10 @@ pub struct PostProcessSettings {
     pub vignette_intensity: f32,
     /// Vignette radius (0.5 = corners only, 0.3 = more coverage). Default: 0.60
     pub vignette_radius: f32,
+    /// Ordered dither strength (0.0 = plain banding, 1.0 = full Bayer pattern). Default: 0.0
+    pub dither_intensity: f32,
+    /// Color levels per channel (32.0 = 5-bit like the PS1, 256.0 = no banding). Default: 256.0
+    pub color_levels: f32,
     /// Animation time (updated automatically)
     pub time: f32,
 }
@@ -118,6 +143,8 @@ impl Default for PostProcessSettings {
             scanline_count: 320.0,
             vignette_intensity: 0.20,
             vignette_radius: 0.60,
+            dither_intensity: 0.0,
+            color_levels: 256.0,
             time: 0.0,
         }
     }
@@ -134,11 +161,82 @@ impl PostProcessSettings {
             scanline_count: config.scanline_count,
             vignette_intensity: config.vignette_intensity,
             vignette_radius: config.vignette_radius,
+            dither_intensity: config.dither_intensity,
+            color_levels: config.color_levels,
             time: 0.0,
         }
     }
 }
 
+/// Runtime on/off switches for the individual post-processing effects.
+///
+/// Disabled effects have their intensity zeroed on the live camera settings;
+/// re-enabling restores the value from `VisualConfig`.
+#[derive(Resource, Clone, Copy)]
+pub struct PostProcessToggles {
+    pub grain: bool,
+    pub scanlines: bool,
+    pub vignette: bool,
+}
+
+impl Default for PostProcessToggles {
+    fn default() -> Self {
+        Self {
+            grain: true,
+            scanlines: true,
+            vignette: true,
+        }
+    }
+}
+
+impl PostProcessSettings {
+    /// Reset intensities from config, then zero out any disabled effects.
+    pub fn apply_toggles(
+        &mut self,
+        config: &super::visual_config::VisualConfig,
+        toggles: &PostProcessToggles,
+    ) {
+        self.grain_intensity = if toggles.grain { config.grain_intensity } else { 0.0 };
+        self.scanline_intensity = if toggles.scanlines { config.scanline_intensity } else { 0.0 };
+        self.vignette_intensity = if toggles.vignette { config.vignette_intensity } else { 0.0 };
+    }
+}
+
+/// Toggle individual effects with F2 (grain), F3 (scanlines) and F4 (vignette).
+fn toggle_post_process_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut toggles: ResMut<PostProcessToggles>,
+) {
+    if keyboard.just_pressed(KeyCode::F2) {
+        toggles.grain = !toggles.grain;
+        info!("Film grain: {}", if toggles.grain { "on" } else { "off" });
+    }
+    if keyboard.just_pressed(KeyCode::F3) {
+        toggles.scanlines = !toggles.scanlines;
+        info!("Scanlines: {}", if toggles.scanlines { "on" } else { "off" });
+    }
+    if keyboard.just_pressed(KeyCode::F4) {
+        toggles.vignette = !toggles.vignette;
+        info!("Vignette: {}", if toggles.vignette { "on" } else { "off" });
+    }
+}
+
+/// Push toggle changes onto every camera's settings component.
+///
+/// Also runs for newly spawned cameras so a toggle survives level reloads.
+fn apply_post_process_toggles(
+    toggles: Res<PostProcessToggles>,
+    config: Res<super::visual_config::VisualConfig>,
+    mut query: Query<&mut PostProcessSettings>,
+) {
+    for mut settings in &mut query {
+        if !toggles.is_changed() && !settings.is_added() {
+            continue;
+        }
+        settings.apply_toggles(&config, &toggles);
+    }
+}
+
 /// System to update the time uniform for animated grain.
 fn update_post_process_time(time: Res<Time>, mut query: Query<&mut PostProcessSettings>) {
     for mut settings in &mut query {
@@ -0,0 +1,187 @@
+//! Low-resolution world rendering.
+//!
+//! While `RenderConfig::resolution_scale` is below 1.0, the `WorldCamera`
+//! renders into an offscreen image of that fraction of the window size. A
+//! present camera draws the image over the whole window with nearest-neighbor
+//! sampling, giving chunky PS1-style pixels. Post-processing moves to the
+//! present camera so grain and scanlines stay at full resolution, and the UI
+//! and weapon camera draw on top of it as before. At 1.0 the world camera
+//! renders straight to the window again.
+//!
+//! The target is resized whenever the scale or the window size changes.
+
+use bevy::{
+    image::ImageSampler,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    },
+    window::{PrimaryWindow, WindowRef},
+};
+
+use super::dynamic_resolution::adjust_resolution_scale;
+use super::layers::present_layers;
+use super::plugin::RenderConfig;
+use super::post_process::PostProcessSettings;
+
+/// Lowest resolution scale the world is rendered at.
+const MIN_RESOLUTION_SCALE: f32 = 0.1;
+
+/// Camera order of the world camera while it renders offscreen, so the
+/// image is ready before the present camera draws it.
+const OFFSCREEN_CAMERA_ORDER: isize = -1;
+
+/// Marker for the camera that draws the world.
+#[derive(Component)]
+pub struct WorldCamera;
+
+/// Marker for the camera that draws the scaled world image to the window.
+#[derive(Component)]
+struct PresentCamera;
+
+/// Marker for the sprite showing the scaled world image.
+#[derive(Component)]
+struct PresentSprite;
+
+/// Offscreen image the world is rendered into while scaled.
+#[derive(Resource, Default)]
+struct WorldRenderTarget {
+    image: Option<Handle<Image>>,
+    size: UVec2,
+}
+
+/// Set up low-resolution rendering systems.
+pub fn setup_resolution_scale_systems(app: &mut App) {
+    app.init_resource::<WorldRenderTarget>().add_systems(
+        Update,
+        apply_resolution_scale.after(adjust_resolution_scale),
+    );
+}
+
+/// Create an image the world camera can render into.
+fn render_target_image(size: Extent3d) -> Image {
+    let mut image = Image::new_fill(
+        size,
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    // Nearest-neighbor upscaling keeps the pixels sharp
+    image.sampler = ImageSampler::nearest();
+    image
+}
+
+/// Point the world camera at the window or the offscreen target, matching
+/// the resolution scale and window size.
+#[allow(clippy::too_many_arguments)]
+fn apply_resolution_scale(
+    mut commands: Commands,
+    render_config: Res<RenderConfig>,
+    mut target: ResMut<WorldRenderTarget>,
+    mut images: ResMut<Assets<Image>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut world_camera_query: Query<
+        (Entity, &mut Camera, Option<&PostProcessSettings>),
+        With<WorldCamera>,
+    >,
+    present_query: Query<(Entity, &PostProcessSettings), With<PresentCamera>>,
+    mut sprite_query: Query<&mut Sprite, With<PresentSprite>>,
+) {
+    let (Ok(window), Ok((world_entity, mut camera, settings))) =
+        (window_query.get_single(), world_camera_query.get_single_mut())
+    else {
+        // No world to present (menus)
+        for (entity, _) in present_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    let scale = render_config.resolution_scale.clamp(MIN_RESOLUTION_SCALE, 1.0);
+
+    if scale >= 1.0 {
+        // Native resolution: render straight to the window again
+        if let Ok((present_entity, present_settings)) = present_query.get_single() {
+            camera.target = RenderTarget::Window(WindowRef::Primary);
+            camera.order = 0;
+            commands.entity(world_entity).insert(*present_settings);
+            commands.entity(present_entity).despawn_recursive();
+            *target = WorldRenderTarget::default();
+        }
+        return;
+    }
+
+    let size = (window.physical_size().as_vec2() * scale)
+        .round()
+        .max(Vec2::ONE)
+        .as_uvec2();
+    let extent = Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+
+    let image = match target.image.clone() {
+        Some(image) => {
+            if target.size != size {
+                if let Some(image) = images.get_mut(&image) {
+                    image.resize(extent);
+                }
+            }
+            image
+        }
+        None => images.add(render_target_image(extent)),
+    };
+    if target.size != size {
+        debug!("World render target set to {}x{}", size.x, size.y);
+    }
+    target.image = Some(image.clone());
+    target.size = size;
+
+    if !matches!(&camera.target, RenderTarget::Image(current) if *current == image) {
+        camera.target = RenderTarget::Image(image.clone());
+        camera.order = OFFSCREEN_CAMERA_ORDER;
+    }
+
+    // Post-processing runs on the present camera instead
+    if let Some(settings) = settings {
+        commands.entity(world_entity).remove::<PostProcessSettings>();
+        if present_query.is_empty() {
+            commands
+                .spawn((
+                    PresentCamera,
+                    Camera2d,
+                    Camera {
+                        clear_color: ClearColorConfig::Custom(Color::BLACK),
+                        ..default()
+                    },
+                    Msaa::Off,
+                    *settings,
+                    present_layers(),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        PresentSprite,
+                        Sprite {
+                            image,
+                            custom_size: Some(window.size()),
+                            ..default()
+                        },
+                        present_layers(),
+                    ));
+                });
+            return;
+        }
+    }
+
+    for mut sprite in sprite_query.iter_mut() {
+        if sprite.custom_size != Some(window.size()) {
+            sprite.custom_size = Some(window.size());
+        }
+    }
+}
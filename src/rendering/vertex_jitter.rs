@@ -0,0 +1,203 @@
+//! PS1-style vertex jitter.
+//!
+//! While `RenderConfig::vertex_jitter` is above zero, meshes of entities
+//! marked `VertexJitter` (and of their descendants, such as enemy scenes)
+//! have their `StandardMaterial` swapped for a `JitterMaterial` wrapping a
+//! copy of it. Its vertex shader snaps screen positions to a coarse grid,
+//! coarser the higher the intensity. Setting the intensity back to zero
+//! restores the original materials.
+//!
+//! The original material stays the source of truth: it is kept in
+//! `JitterSource`, and changes to it (hit flashes, awareness indicators) are
+//! copied into the jitter material. The prepass and shadow passes snap
+//! vertices the same way, so depth, normals and shadows match what's drawn.
+
+use bevy::{
+    asset::load_internal_asset,
+    pbr::{ExtendedMaterial, MaterialExtension},
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+use std::collections::HashMap;
+
+use super::plugin::RenderConfig;
+
+/// Handle to the vertex jitter shader.
+const VERTEX_JITTER_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(0x5c1e9b7a3f2d4e8c6b0a9f1d7e3c5b2a);
+
+/// Handle to the vertex jitter prepass shader.
+const VERTEX_JITTER_PREPASS_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(0x8d4f2a6c1e9b3d7f5a0c8e2b6d4f1a93);
+
+/// Handle to the grid snapping shared by both jitter shaders.
+const VERTEX_JITTER_SNAP_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(0x2b7e5d9a4c1f8e3b6d0a7c5e9f2b4d61);
+
+/// Standard material with jittered vertices.
+pub type JitterMaterial = ExtendedMaterial<StandardMaterial, VertexJitterExtension>;
+
+/// Material extension snapping vertices to a low-resolution screen grid.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+pub struct VertexJitterExtension {
+    /// Jitter intensity (0.0 = none, 1.0 = PS1 resolution grid)
+    #[uniform(100)]
+    pub intensity: f32,
+}
+
+impl MaterialExtension for VertexJitterExtension {
+    fn vertex_shader() -> ShaderRef {
+        VERTEX_JITTER_SHADER_HANDLE.into()
+    }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        VERTEX_JITTER_PREPASS_SHADER_HANDLE.into()
+    }
+}
+
+/// Marker for entities whose meshes, and their descendants' meshes, get
+/// vertex jitter.
+#[derive(Component, Default)]
+pub struct VertexJitter;
+
+/// Original material of a mesh drawn with a `JitterMaterial`.
+#[derive(Component)]
+pub struct JitterSource(pub Handle<StandardMaterial>);
+
+/// Jitter materials by the standard material they wrap, so meshes sharing
+/// a material keep sharing it.
+#[derive(Resource, Default)]
+struct JitterMaterials(HashMap<AssetId<StandardMaterial>, Handle<JitterMaterial>>);
+
+/// Set up vertex jitter systems.
+pub fn setup_vertex_jitter_systems(app: &mut App) {
+    load_internal_asset!(
+        app,
+        VERTEX_JITTER_SNAP_SHADER_HANDLE,
+        "../../assets/shaders/vertex_jitter_snap.wgsl",
+        Shader::from_wgsl
+    );
+    load_internal_asset!(
+        app,
+        VERTEX_JITTER_SHADER_HANDLE,
+        "../../assets/shaders/vertex_jitter.wgsl",
+        Shader::from_wgsl
+    );
+    load_internal_asset!(
+        app,
+        VERTEX_JITTER_PREPASS_SHADER_HANDLE,
+        "../../assets/shaders/vertex_jitter_prepass.wgsl",
+        Shader::from_wgsl
+    );
+
+    app.add_plugins(MaterialPlugin::<JitterMaterial>::default())
+        .init_resource::<JitterMaterials>()
+        // After scene spawning, so enemy models are swapped before their first frame
+        .add_systems(
+            PostUpdate,
+            (apply_vertex_jitter, sync_jitter_materials).chain(),
+        );
+}
+
+/// Swap materials to match `RenderConfig::vertex_jitter`.
+///
+/// Checks every mesh when the config changes, otherwise only new ones.
+#[allow(clippy::too_many_arguments)]
+fn apply_vertex_jitter(
+    mut commands: Commands,
+    render_config: Res<RenderConfig>,
+    mut jitter_materials: ResMut<JitterMaterials>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut materials: ResMut<Assets<JitterMaterial>>,
+    mesh_query: Query<(Entity, Ref<MeshMaterial3d<StandardMaterial>>)>,
+    jittered_query: Query<(Entity, &JitterSource)>,
+    marker_query: Query<(), With<VertexJitter>>,
+    parent_query: Query<&Parent>,
+) {
+    let intensity = render_config.vertex_jitter.max(0.0);
+
+    if render_config.is_changed() {
+        if intensity <= 0.0 {
+            for (entity, source) in jittered_query.iter() {
+                commands
+                    .entity(entity)
+                    .insert(MeshMaterial3d(source.0.clone()))
+                    .remove::<(MeshMaterial3d<JitterMaterial>, JitterSource)>();
+            }
+            jitter_materials.0.clear();
+            return;
+        }
+
+        for (_, material) in materials.iter_mut() {
+            if material.extension.intensity != intensity {
+                material.extension.intensity = intensity;
+            }
+        }
+    }
+
+    if intensity <= 0.0 {
+        return;
+    }
+
+    for (entity, material) in mesh_query.iter() {
+        if !render_config.is_changed() && !material.is_added() {
+            continue;
+        }
+
+        let marked = marker_query.contains(entity)
+            || parent_query
+                .iter_ancestors(entity)
+                .any(|ancestor| marker_query.contains(ancestor));
+        if !marked {
+            continue;
+        }
+
+        let jitter = match jitter_materials.0.get(&material.0.id()) {
+            Some(handle) => handle.clone(),
+            None => {
+                let Some(base) = standard_materials.get(&material.0).cloned() else {
+                    continue;
+                };
+                let handle = materials.add(JitterMaterial {
+                    base,
+                    extension: VertexJitterExtension { intensity },
+                });
+                jitter_materials.0.insert(material.0.id(), handle.clone());
+                handle
+            }
+        };
+
+        commands
+            .entity(entity)
+            .insert((MeshMaterial3d(jitter), JitterSource(material.0.clone())))
+            .remove::<MeshMaterial3d<StandardMaterial>>();
+    }
+}
+
+/// Copy changes to original materials into their jitter materials, and
+/// drop jitter materials whose original is gone.
+fn sync_jitter_materials(
+    mut events: EventReader<AssetEvent<StandardMaterial>>,
+    mut jitter_materials: ResMut<JitterMaterials>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut materials: ResMut<Assets<JitterMaterial>>,
+) {
+    for event in events.read() {
+        match *event {
+            AssetEvent::Modified { id } => {
+                let Some(handle) = jitter_materials.0.get(&id) else {
+                    continue;
+                };
+                if let (Some(base), Some(material)) =
+                    (standard_materials.get(id), materials.get_mut(handle))
+                {
+                    material.base = base.clone();
+                }
+            }
+            AssetEvent::Removed { id } => {
+                jitter_materials.0.remove(&id);
+            }
+            _ => {}
+        }
+    }
+}
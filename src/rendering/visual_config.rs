@@ -6,6 +6,14 @@ use bevy::prelude::*;
 use serde::Deserialize;
 use std::fs;
 
+fn default_resolution_scale() -> f32 {
+    1.0
+}
+
+fn default_color_levels() -> f32 {
+    256.0
+}
+
 /// Visual configuration loaded from assets/data/rendering/visual_config.ron.
 #[derive(Resource, Clone, Deserialize)]
 pub struct VisualConfig {
@@ -17,12 +25,28 @@ pub struct VisualConfig {
     pub scanline_count: f32,
     pub vignette_intensity: f32,
     pub vignette_radius: f32,
+    #[serde(default)]
+    pub dither_intensity: f32,
+    #[serde(default = "default_color_levels")]
+    pub color_levels: f32,
     // Atmosphere
     pub fog_enabled: bool,
     pub fog_density: f32,
     pub fog_color: (f32, f32, f32),
     pub sky_color: (f32, f32, f32),
     pub clear_color: (f32, f32, f32),
+    // Retro effects
+    /// Fraction of the window resolution the world renders at (1.0 = native)
+    #[serde(default = "default_resolution_scale")]
+    pub resolution_scale: f32,
+    /// PS1-style vertex jitter on level geometry and enemies (0.0 = off)
+    #[serde(default)]
+    pub vertex_jitter: f32,
+    // Compatibility
+    /// Draw the viewmodel with the main camera instead of a separate weapon
+    /// camera, for drivers that glitch with the two-camera setup
+    #[serde(default)]
+    pub single_camera_viewmodel: bool,
 }
 
 impl Default for VisualConfig {
@@ -36,12 +60,17 @@ impl Default for VisualConfig {
             scanline_count: 320.0,
             vignette_intensity: 0.20,
             vignette_radius: 0.60,
+            dither_intensity: 0.0,
+            color_levels: 256.0,
             // Atmosphere defaults
             fog_enabled: true,
             fog_density: 0.025,
             fog_color: (0.15, 0.14, 0.13),
             sky_color: (0.12, 0.11, 0.10),
             clear_color: (0.08, 0.07, 0.06),
+            resolution_scale: 1.0,
+            vertex_jitter: 0.0,
+            single_camera_viewmodel: false,
         }
     }
 }
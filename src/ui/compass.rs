@@ -0,0 +1,196 @@
+//! Compass strip at the top of the HUD.
+//!
+//! Shows the player's heading as cardinal and intercardinal markers sliding
+//! across a horizontal strip. North is -Z, the top row of a level's grid, so
+//! headings line up with the level layout. Hidden when `HudConfig::compass`
+//! is off.
+
+use bevy::prelude::*;
+
+use crate::core::{GameSession, GameState, HudConfig};
+use crate::player::Player;
+
+/// Width of the strip in pixels at HUD scale 1.0.
+const STRIP_WIDTH: f32 = 360.0;
+
+/// Height of the strip in pixels at HUD scale 1.0.
+const STRIP_HEIGHT: f32 = 24.0;
+
+/// Degrees of heading visible across the whole strip.
+const VISIBLE_ARC: f32 = 180.0;
+
+/// Width of each marker's label box in pixels at HUD scale 1.0.
+const MARKER_WIDTH: f32 = 28.0;
+
+/// Markers with their bearing in degrees clockwise from north.
+const MARKERS: [(&str, f32); 8] = [
+    ("N", 0.0),
+    ("NE", 45.0),
+    ("E", 90.0),
+    ("SE", 135.0),
+    ("S", 180.0),
+    ("SW", 225.0),
+    ("W", 270.0),
+    ("NW", 315.0),
+];
+
+/// Marker for the compass root container.
+#[derive(Component)]
+struct CompassRoot;
+
+/// A heading label on the compass strip.
+#[derive(Component)]
+struct CompassMarker {
+    /// Degrees clockwise from north
+    bearing: f32,
+}
+
+/// Set up compass systems.
+pub fn setup_compass_systems(app: &mut App) {
+    app.add_systems(OnEnter(GameSession), spawn_compass_on_enter)
+        .add_systems(OnExit(GameSession), cleanup_compass)
+        .add_systems(
+            Update,
+            (update_compass_layout, update_compass_markers)
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        );
+}
+
+/// Heading in degrees clockwise from north (-Z) for a rotation.
+fn heading_degrees(rotation: Quat) -> f32 {
+    let forward = rotation * Vec3::NEG_Z;
+    forward.x.atan2(-forward.z).to_degrees().rem_euclid(360.0)
+}
+
+fn spawn_compass_on_enter(mut commands: Commands, config: Res<HudConfig>) {
+    spawn_compass(&mut commands, &config);
+}
+
+/// Spawn the compass strip centered at the top of the screen.
+fn spawn_compass(commands: &mut Commands, config: &HudConfig) {
+    if !config.compass {
+        return;
+    }
+    let scale = config.clamped_scale();
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                top: Val::Px(12.0 * scale),
+                ..default()
+            },
+            CompassRoot,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(STRIP_WIDTH * scale),
+                        height: Val::Px(STRIP_HEIGHT * scale),
+                        overflow: Overflow::clip(),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+                ))
+                .with_children(|strip| {
+                    // Center tick showing the exact heading
+                    strip.spawn((
+                        Node {
+                            width: Val::Px(2.0),
+                            height: Val::Px(6.0 * scale),
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(STRIP_WIDTH * scale / 2.0 - 1.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.9, 0.85, 0.7, 0.8)),
+                    ));
+
+                    for (label, bearing) in MARKERS {
+                        let cardinal = label.len() == 1;
+                        let (font_size, color) = if cardinal {
+                            (16.0, Color::srgb(0.9, 0.85, 0.7))
+                        } else {
+                            (12.0, Color::srgb(0.6, 0.6, 0.6))
+                        };
+                        strip
+                            .spawn((
+                                Node {
+                                    width: Val::Px(MARKER_WIDTH * scale),
+                                    height: Val::Percent(100.0),
+                                    position_type: PositionType::Absolute,
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                Visibility::Hidden,
+                                CompassMarker { bearing },
+                            ))
+                            .with_children(|marker| {
+                                marker.spawn((
+                                    Text::new(label),
+                                    TextFont {
+                                        font_size: font_size * scale,
+                                        ..default()
+                                    },
+                                    TextColor(color),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+/// Rebuild the compass when the HUD configuration changes.
+fn update_compass_layout(
+    mut commands: Commands,
+    config: Res<HudConfig>,
+    query: Query<Entity, With<CompassRoot>>,
+) {
+    if !config.is_changed() || config.is_added() {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_compass(&mut commands, &config);
+}
+
+/// Slide markers across the strip to match the player's heading.
+fn update_compass_markers(
+    config: Res<HudConfig>,
+    player_query: Query<&Transform, With<Player>>,
+    mut marker_query: Query<(&CompassMarker, &mut Node, &mut Visibility)>,
+) {
+    let Ok(transform) = player_query.get_single() else {
+        return;
+    };
+    let heading = heading_degrees(transform.rotation);
+    let scale = config.clamped_scale();
+    let pixels_per_degree = STRIP_WIDTH * scale / VISIBLE_ARC;
+
+    for (marker, mut node, mut visibility) in marker_query.iter_mut() {
+        // Signed angle from the heading to the marker, in -180..180
+        let offset = (marker.bearing - heading + 180.0).rem_euclid(360.0) - 180.0;
+        if offset.abs() > VISIBLE_ARC / 2.0 {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        *visibility = Visibility::Inherited;
+        node.left = Val::Px(
+            STRIP_WIDTH * scale / 2.0 + offset * pixels_per_degree - MARKER_WIDTH * scale / 2.0,
+        );
+    }
+}
+
+/// Clean up the compass when the game session ends.
+fn cleanup_compass(mut commands: Commands, query: Query<Entity, With<CompassRoot>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,168 @@
+//! Damage direction indicators.
+//!
+//! When something hurts the player, a red marker appears on a ring around the
+//! screen center, pointing toward the attacker relative to where the camera
+//! faces. Markers track their attacker while visible and fade out.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::combat::DamageEvent;
+use crate::core::{GameSession, GameState};
+use crate::player::{Player, PlayerCamera};
+
+/// Seconds an indicator stays visible.
+const INDICATOR_DURATION: f32 = 1.0;
+
+/// Distance of indicators from the screen center, in pixels.
+const INDICATOR_RADIUS: f32 = 140.0;
+
+/// Indicator size in pixels (along the ring, across the ring).
+const INDICATOR_SIZE: Vec2 = Vec2::new(56.0, 8.0);
+
+/// Indicator color at full strength.
+const INDICATOR_COLOR: Color = Color::srgba(0.85, 0.05, 0.05, 0.8);
+
+/// A damage direction indicator.
+#[derive(Component)]
+struct DamageIndicator {
+    /// Entity that dealt the damage
+    source: Entity,
+    /// Last known position of the source (kept if it despawns)
+    source_position: Vec3,
+    /// Seconds until the indicator has faded out
+    remaining: f32,
+}
+
+/// Set up damage indicator systems.
+pub fn setup_damage_indicator_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (spawn_damage_indicators, update_damage_indicators)
+            .chain()
+            .run_if(in_state(GameState::InGame)),
+    )
+    .add_systems(OnExit(GameSession), cleanup_damage_indicators);
+}
+
+/// Show an indicator for each attacker that damaged the player.
+///
+/// Repeated hits from the same attacker refresh its indicator rather than
+/// stacking new ones.
+fn spawn_damage_indicators(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    player_query: Query<Entity, With<Player>>,
+    source_query: Query<&GlobalTransform>,
+    mut indicator_query: Query<&mut DamageIndicator>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+
+    for event in damage_events.read() {
+        if event.target != player || event.source == player {
+            continue;
+        }
+        let Ok(source_transform) = source_query.get(event.source) else {
+            continue;
+        };
+        let source_position = source_transform.translation();
+
+        if let Some(mut indicator) = indicator_query
+            .iter_mut()
+            .find(|indicator| indicator.source == event.source)
+        {
+            indicator.source_position = source_position;
+            indicator.remaining = INDICATOR_DURATION;
+            continue;
+        }
+
+        commands.spawn((
+            DamageIndicator {
+                source: event.source,
+                source_position,
+                remaining: INDICATOR_DURATION,
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(INDICATOR_SIZE.x),
+                height: Val::Px(INDICATOR_SIZE.y),
+                ..default()
+            },
+            BackgroundColor(INDICATOR_COLOR),
+            // Hidden until placed around the ring
+            Visibility::Hidden,
+        ));
+    }
+}
+
+/// Place indicators around the screen center and fade them out.
+fn update_damage_indicators(
+    mut commands: Commands,
+    time: Res<Time>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    source_query: Query<&GlobalTransform>,
+    mut indicator_query: Query<(
+        Entity,
+        &mut DamageIndicator,
+        &mut Node,
+        &mut Transform,
+        &mut BackgroundColor,
+        &mut Visibility,
+    )>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let center = Vec2::new(window.width(), window.height()) / 2.0;
+
+    for (entity, mut indicator, mut node, mut transform, mut color, mut visibility) in
+        indicator_query.iter_mut()
+    {
+        indicator.remaining -= time.delta_secs();
+        if indicator.remaining <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // Follow the attacker while it exists
+        if let Ok(source_transform) = source_query.get(indicator.source) {
+            indicator.source_position = source_transform.translation();
+        }
+
+        // Direction to the source in camera space, flattened onto the view plane
+        let local = camera_transform
+            .affine()
+            .inverse()
+            .transform_point3(indicator.source_position);
+        let Some(direction) = Vec2::new(local.x, -local.z).try_normalize() else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        // Screen y points down: straight ahead is the top of the ring
+        let position = center + Vec2::new(direction.x, -direction.y) * INDICATOR_RADIUS;
+        node.left = Val::Px(position.x - INDICATOR_SIZE.x / 2.0);
+        node.top = Val::Px(position.y - INDICATOR_SIZE.y / 2.0);
+
+        // Lay the bar along the ring
+        let angle = direction.x.atan2(direction.y);
+        transform.rotation = Quat::from_rotation_z(-angle);
+
+        let strength = indicator.remaining / INDICATOR_DURATION;
+        color.0 = INDICATOR_COLOR.with_alpha(INDICATOR_COLOR.alpha() * strength);
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Remove all indicators when leaving the game.
+fn cleanup_damage_indicators(mut commands: Commands, query: Query<Entity, With<DamageIndicator>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,203 @@
+//! Floating health bars above enemies.
+//!
+//! Each enemy gets a small bar anchored above it in screen space. Depending
+//! on `EnemyHealthBars`, bars show for every enemy in view, only for enemies
+//! that were recently hit or are under the crosshair, or never. Bars fade out
+//! after a few seconds without damage and are removed with their enemy.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_rapier3d::prelude::*;
+
+use crate::combat::{DamageEvent, Dead, Health};
+use crate::core::{EnemyHealthBars, GameSession, GameState};
+use crate::enemies::EnemyStats;
+use crate::player::{Player, PlayerCamera};
+
+/// Seconds a bar stays visible after its enemy was hit.
+const SHOW_DURATION: f32 = 3.0;
+
+/// Seconds of fading at the end of `SHOW_DURATION`.
+const FADE_DURATION: f32 = 0.5;
+
+/// Height above the enemy's origin (at its feet) where the bar is anchored.
+const BAR_HEIGHT: f32 = 2.2;
+
+/// Bars are hidden for enemies further away than this.
+const MAX_DISTANCE: f32 = 20.0;
+
+/// Bar size in pixels.
+const BAR_SIZE: Vec2 = Vec2::new(50.0, 5.0);
+
+const BAR_BACKGROUND: Color = Color::srgba(0.05, 0.05, 0.05, 0.7);
+const BAR_FILL: Color = Color::srgb(0.75, 0.1, 0.1);
+
+/// Floating health bar for one enemy.
+#[derive(Component)]
+struct EnemyHealthBar {
+    enemy: Entity,
+    /// Seconds the bar stays visible after the last hit
+    shown: f32,
+}
+
+/// Fill of an enemy health bar.
+#[derive(Component)]
+struct EnemyHealthBarFill;
+
+/// Set up enemy health bar systems.
+pub fn setup_enemy_health_bar_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (spawn_enemy_health_bars, show_damaged_health_bars, update_enemy_health_bars)
+            .chain()
+            .run_if(in_state(GameState::InGame)),
+    )
+    .add_systems(OnExit(GameSession), cleanup_enemy_health_bars);
+}
+
+/// Create a (hidden) bar for each new enemy.
+fn spawn_enemy_health_bars(
+    mut commands: Commands,
+    enemy_query: Query<Entity, (Added<EnemyStats>, With<Health>)>,
+) {
+    for enemy in enemy_query.iter() {
+        commands
+            .spawn((
+                EnemyHealthBar { enemy, shown: 0.0 },
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(BAR_SIZE.x),
+                    height: Val::Px(BAR_SIZE.y),
+                    ..default()
+                },
+                BackgroundColor(BAR_BACKGROUND),
+                Visibility::Hidden,
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(BAR_FILL),
+                    EnemyHealthBarFill,
+                ));
+            });
+    }
+}
+
+/// Show the bars of enemies that took damage.
+fn show_damaged_health_bars(
+    mut damage_events: EventReader<DamageEvent>,
+    mut bar_query: Query<&mut EnemyHealthBar>,
+) {
+    for event in damage_events.read() {
+        for mut bar in bar_query.iter_mut() {
+            if bar.enemy == event.target {
+                bar.shown = SHOW_DURATION;
+            }
+        }
+    }
+}
+
+/// A health bar's state, layout, color, visibility and fill child.
+type HealthBarData<'a> = (
+    Entity,
+    &'a mut EnemyHealthBar,
+    &'a mut Node,
+    &'a mut BackgroundColor,
+    &'a mut Visibility,
+    &'a Children,
+);
+
+/// A health bar's fill, kept apart from the bar itself.
+type HealthBarFill = (With<EnemyHealthBarFill>, Without<EnemyHealthBar>);
+
+/// Position, fill and fade bars; remove bars whose enemy is gone.
+#[allow(clippy::too_many_arguments)]
+fn update_enemy_health_bars(
+    mut commands: Commands,
+    time: Res<Time>,
+    mode: Res<EnemyHealthBars>,
+    rapier_context: Query<&RapierContext>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    player_query: Query<Entity, With<Player>>,
+    enemy_query: Query<(&GlobalTransform, &Health), Without<Dead>>,
+    mut bar_query: Query<HealthBarData>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), HealthBarFill>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let camera_pos = camera_transform.translation();
+
+    // The camera may render to a smaller offscreen target; bars are placed
+    // in window coordinates
+    let viewport_to_window = match (window_query.get_single(), camera.logical_viewport_size()) {
+        (Ok(window), Some(viewport)) => window.size() / viewport,
+        _ => Vec2::ONE,
+    };
+
+    // Enemy under the crosshair, if any
+    let targeted = rapier_context.get_single().ok().and_then(|context| {
+        let filter = match player_query.get_single() {
+            Ok(player) => QueryFilter::default().exclude_collider(player),
+            Err(_) => QueryFilter::default(),
+        };
+        context
+            .cast_ray(camera_pos, camera_transform.forward().as_vec3(), MAX_DISTANCE, true, filter)
+            .map(|(entity, _)| entity)
+    });
+
+    for (bar_entity, mut bar, mut node, mut color, mut visibility, children) in bar_query.iter_mut()
+    {
+        let Ok((enemy_transform, health)) = enemy_query.get(bar.enemy) else {
+            commands.entity(bar_entity).despawn_recursive();
+            continue;
+        };
+
+        bar.shown = (bar.shown - time.delta_secs()).max(0.0);
+
+        let alpha = match *mode {
+            EnemyHealthBars::Always => 1.0,
+            EnemyHealthBars::OnHit if targeted == Some(bar.enemy) => 1.0,
+            EnemyHealthBars::OnHit => (bar.shown / FADE_DURATION).min(1.0),
+            EnemyHealthBars::Never => 0.0,
+        };
+
+        let anchor = enemy_transform.translation() + Vec3::Y * BAR_HEIGHT;
+        let screen_pos = camera
+            .world_to_viewport(camera_transform, anchor)
+            .map(|pos| pos * viewport_to_window);
+        let (Ok(screen_pos), true) = (screen_pos, anchor.distance(camera_pos) <= MAX_DISTANCE)
+        else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        if alpha <= 0.0 {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        node.left = Val::Px(screen_pos.x - BAR_SIZE.x / 2.0);
+        node.top = Val::Px(screen_pos.y - BAR_SIZE.y / 2.0);
+        color.0 = BAR_BACKGROUND.with_alpha(BAR_BACKGROUND.alpha() * alpha);
+        *visibility = Visibility::Inherited;
+
+        for &child in children.iter() {
+            if let Ok((mut fill_node, mut fill_color)) = fill_query.get_mut(child) {
+                fill_node.width = Val::Percent(health.percentage() * 100.0);
+                fill_color.0 = BAR_FILL.with_alpha(alpha);
+            }
+        }
+    }
+}
+
+/// Remove all bars when leaving the game.
+fn cleanup_enemy_health_bars(mut commands: Commands, query: Query<Entity, With<EnemyHealthBar>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,15 +1,23 @@
-//! In-game HUD - health and stamina display.
+//! In-game HUD - health and stamina display, crosshair and interaction prompt.
 
 use bevy::prelude::*;
 
-use crate::combat::{Health, Stamina};
-use crate::core::GameState;
+use crate::combat::{Health, Stamina, StaminaDepletedEvent, StaminaRecoveredEvent};
+use crate::core::{
+    Action, CrosshairConfig, CrosshairStyle, GameSession, GameState, HudConfig, HudCorner,
+    KeyBindings,
+};
 use crate::player::Player;
+use crate::world::{Interactable, InteractionTarget};
 
 /// Marker for HUD root entity.
 #[derive(Component)]
 pub struct HudRoot;
 
+/// Marker for the container of the health and stamina bars.
+#[derive(Component)]
+pub struct HudBars;
+
 /// Marker for health bar fill.
 #[derive(Component)]
 pub struct HealthBar;
@@ -18,39 +26,82 @@ pub struct HealthBar;
 #[derive(Component)]
 pub struct StaminaBar;
 
+/// Stamina bar fill color.
+const STAMINA_COLOR: Color = Color::srgb(0.2, 0.8, 0.3);
+
+/// Stamina bar flash color while exhausted.
+const STAMINA_EXHAUSTED_COLOR: Color = Color::srgb(0.9, 0.15, 0.1);
+
+/// Flashes per second of the stamina bar while exhausted.
+const STAMINA_FLASH_RATE: f32 = 4.0;
+
+/// Marker for the crosshair container.
+#[derive(Component)]
+pub struct Crosshair;
+
+/// Marker for the interaction prompt text.
+#[derive(Component)]
+pub struct InteractionPrompt;
+
 /// Setup HUD systems.
 pub fn setup_hud_systems(app: &mut App) {
-    app.add_systems(OnEnter(GameState::InGame), spawn_hud)
-        .add_systems(OnExit(GameState::InGame), cleanup_hud)
+    app.add_systems(OnEnter(GameSession), spawn_hud)
+        .add_systems(OnExit(GameSession), cleanup_hud)
         .add_systems(
             Update,
-            (update_health_bar, update_stamina_bar)
+            (
+                update_health_bar,
+                update_stamina_bar,
+                flash_stamina_bar,
+                update_hud_layout,
+                update_crosshair,
+                update_interaction_prompt,
+            )
                 .run_if(in_state(GameState::InGame)),
         );
 }
 
 /// Spawn the HUD UI.
-fn spawn_hud(mut commands: Commands) {
-    // HUD root container (bottom-left corner)
+fn spawn_hud(mut commands: Commands, crosshair: Res<CrosshairConfig>, hud: Res<HudConfig>) {
+    spawn_hud_bars(&mut commands, &hud);
+    spawn_crosshair(&mut commands, &crosshair);
+    spawn_interaction_prompt(&mut commands);
+}
+
+/// Spawn the health and stamina bars in the configured corner and scale.
+fn spawn_hud_bars(commands: &mut Commands, config: &HudConfig) {
+    let scale = config.clamped_scale();
+    let justify_content = match config.corner {
+        HudCorner::TopLeft | HudCorner::TopRight => JustifyContent::Start,
+        HudCorner::BottomLeft | HudCorner::BottomRight => JustifyContent::End,
+    };
+    let align_items = match config.corner {
+        HudCorner::TopLeft | HudCorner::BottomLeft => AlignItems::Start,
+        HudCorner::TopRight | HudCorner::BottomRight => AlignItems::End,
+    };
+
+    // HUD root container (bottom-left corner by default)
     commands
         .spawn((
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
                 flex_direction: FlexDirection::Column,
-                justify_content: JustifyContent::End,
-                align_items: AlignItems::Start,
-                padding: UiRect::all(Val::Px(20.0)),
+                justify_content,
+                align_items,
+                padding: UiRect::all(Val::Px(20.0 * scale)),
                 ..default()
             },
             HudRoot,
+            HudBars,
         ))
         .with_children(|parent| {
             // Stamina bar
             spawn_bar(
                 parent,
                 "Stamina",
-                Color::srgb(0.2, 0.8, 0.3),
+                STAMINA_COLOR,
+                scale,
                 StaminaBar,
                 None::<StaminaBar>,
             );
@@ -60,12 +111,33 @@ fn spawn_hud(mut commands: Commands) {
                 parent,
                 "Health",
                 Color::srgb(0.8, 0.2, 0.2),
+                scale,
                 HealthBar,
                 None::<HealthBar>,
             );
         });
+}
+
+/// Rebuild the bars when the HUD configuration changes.
+fn update_hud_layout(
+    mut commands: Commands,
+    config: Res<HudConfig>,
+    query: Query<Entity, With<HudBars>>,
+) {
+    if !config.is_changed() || config.is_added() {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_hud_bars(&mut commands, &config);
+}
+
+/// Spawn the crosshair (center of screen) in the configured style.
+fn spawn_crosshair(commands: &mut Commands, config: &CrosshairConfig) {
+    let color = config.color();
 
-    // Crosshair (center of screen)
     commands.spawn((
         Node {
             width: Val::Percent(100.0),
@@ -76,24 +148,133 @@ fn spawn_hud(mut commands: Commands) {
             ..default()
         },
         HudRoot,
+        Crosshair,
     )).with_children(|parent| {
-        // Crosshair dot
-        parent.spawn((
+        match config.style {
+            CrosshairStyle::Dot => {
+                parent.spawn((
+                    Node {
+                        width: Val::Px(4.0),
+                        height: Val::Px(4.0),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+            }
+            CrosshairStyle::Cross => {
+                // Horizontal and vertical bars overlapping in a 16px square
+                parent.spawn(Node {
+                    width: Val::Px(16.0),
+                    height: Val::Px(16.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                }).with_children(|cross| {
+                    for (width, height) in [(16.0, 2.0), (2.0, 16.0)] {
+                        cross.spawn((
+                            Node {
+                                width: Val::Px(width),
+                                height: Val::Px(height),
+                                position_type: PositionType::Absolute,
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                        ));
+                    }
+                });
+            }
+            CrosshairStyle::Circle => {
+                parent.spawn((
+                    Node {
+                        width: Val::Px(14.0),
+                        height: Val::Px(14.0),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor(color),
+                    BorderRadius::MAX,
+                ));
+            }
+            CrosshairStyle::None => {}
+        }
+    });
+}
+
+/// Spawn the (hidden) interaction prompt below the crosshair.
+fn spawn_interaction_prompt(commands: &mut Commands) {
+    commands
+        .spawn((
             Node {
-                width: Val::Px(4.0),
-                height: Val::Px(4.0),
+                width: Val::Percent(100.0),
+                top: Val::Percent(56.0),
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
                 ..default()
             },
-            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
-        ));
-    });
+            HudRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.85, 0.8, 0.75, 0.9)),
+                Visibility::Hidden,
+                InteractionPrompt,
+            ));
+        });
+}
+
+/// Show what pressing interact would do to the current `InteractionTarget`.
+fn update_interaction_prompt(
+    target: Res<InteractionTarget>,
+    bindings: Res<KeyBindings>,
+    interactable_query: Query<&Interactable>,
+    mut prompt_query: Query<(&mut Text, &mut Visibility), With<InteractionPrompt>>,
+) {
+    let Ok((mut text, mut visibility)) = prompt_query.get_single_mut() else {
+        return;
+    };
+
+    let interactable = target.entity.and_then(|entity| interactable_query.get(entity).ok());
+    let interact = bindings.get(Action::Interact);
+    let binding = interact.primary.or(interact.secondary);
+    let (Some(interactable), Some(binding)) = (interactable, binding) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let prompt = format!("Press {} to {}", binding.label(), interactable.prompt);
+    if text.0 != prompt {
+        text.0 = prompt;
+    }
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+/// Rebuild the crosshair when its configuration changes.
+fn update_crosshair(
+    mut commands: Commands,
+    config: Res<CrosshairConfig>,
+    query: Query<Entity, With<Crosshair>>,
+) {
+    if !config.is_changed() || config.is_added() {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_crosshair(&mut commands, &config);
 }
 
-/// Helper to spawn a status bar.
+/// Helper to spawn a status bar, sized by the HUD scale.
 fn spawn_bar<M: Component, C: Component>(
     parent: &mut ChildBuilder,
     label: &str,
     color: Color,
+    scale: f32,
     bar_marker: M,
     container_marker: Option<C>,
 ) {
@@ -101,7 +282,7 @@ fn spawn_bar<M: Component, C: Component>(
         Node {
             flex_direction: FlexDirection::Row,
             align_items: AlignItems::Center,
-            margin: UiRect::bottom(Val::Px(5.0)),
+            margin: UiRect::bottom(Val::Px(5.0 * scale)),
             ..default()
         },
     ));
@@ -116,12 +297,12 @@ fn spawn_bar<M: Component, C: Component>(
         bar_parent.spawn((
             Text::new(label),
             TextFont {
-                font_size: 14.0,
+                font_size: 14.0 * scale,
                 ..default()
             },
             TextColor(Color::srgb(0.8, 0.8, 0.8)),
             Node {
-                width: Val::Px(60.0),
+                width: Val::Px(60.0 * scale),
                 ..default()
             },
         ));
@@ -130,8 +311,8 @@ fn spawn_bar<M: Component, C: Component>(
         bar_parent
             .spawn((
                 Node {
-                    width: Val::Px(150.0),
-                    height: Val::Px(12.0),
+                    width: Val::Px(150.0 * scale),
+                    height: Val::Px(12.0 * scale),
                     ..default()
                 },
                 BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
@@ -182,6 +363,39 @@ fn update_stamina_bar(
     bar.width = Val::Percent(percentage * 100.0);
 }
 
+/// Flash the stamina bar red while the player is exhausted.
+fn flash_stamina_bar(
+    time: Res<Time>,
+    player_query: Query<Entity, With<Player>>,
+    mut depleted_events: EventReader<StaminaDepletedEvent>,
+    mut recovered_events: EventReader<StaminaRecoveredEvent>,
+    mut bar_query: Query<&mut BackgroundColor, With<StaminaBar>>,
+    // Player that ran out of stamina (a new run spawns a new player)
+    mut exhausted_player: Local<Option<Entity>>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+
+    if depleted_events.read().any(|event| event.entity == player) {
+        *exhausted_player = Some(player);
+    }
+    if recovered_events.read().any(|event| event.entity == player) {
+        *exhausted_player = None;
+    }
+
+    let Ok(mut color) = bar_query.get_single_mut() else {
+        return;
+    };
+
+    let exhausted = *exhausted_player == Some(player);
+    color.0 = if exhausted && (time.elapsed_secs() * STAMINA_FLASH_RATE).fract() < 0.5 {
+        STAMINA_EXHAUSTED_COLOR
+    } else {
+        STAMINA_COLOR
+    };
+}
+
 /// Clean up HUD entities.
 fn cleanup_hud(mut commands: Commands, query: Query<Entity, With<HudRoot>>) {
     for entity in query.iter() {
@@ -0,0 +1,418 @@
+//! Attribute allocation screen.
+//!
+//! Opens on level up (or with `Action::Attributes`) and pauses gameplay via
+//! `PlayState::LevelUp`. Points are applied to `Attributes` immediately so
+//! the derived values shown update as the player chooses; points spent
+//! while the screen is open can be taken back until it closes.
+
+use bevy::prelude::*;
+
+use crate::combat::Weapon;
+use crate::core::{Action, ActionInput, PlayState};
+use crate::player::{Attributes, Experience, Player, PlayerConfig, PlayerStats};
+
+const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.2);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.25, 0.25, 0.3);
+const BUTTON_PRESSED_COLOR: Color = Color::srgb(0.3, 0.3, 0.35);
+const LABEL_COLOR: Color = Color::srgb(0.8, 0.8, 0.85);
+const HINT_COLOR: Color = Color::srgb(0.5, 0.5, 0.55);
+
+/// An attribute that points can be spent on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum AttributeKind {
+    Strength,
+    Magic,
+    Dexterity,
+    Speed,
+    Defense,
+}
+
+impl AttributeKind {
+    const ALL: [AttributeKind; 5] = [
+        AttributeKind::Strength,
+        AttributeKind::Magic,
+        AttributeKind::Dexterity,
+        AttributeKind::Speed,
+        AttributeKind::Defense,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            AttributeKind::Strength => "Strength",
+            AttributeKind::Magic => "Magic",
+            AttributeKind::Dexterity => "Dexterity",
+            AttributeKind::Speed => "Speed",
+            AttributeKind::Defense => "Defense",
+        }
+    }
+
+    /// What the attribute affects, shown next to its value.
+    fn hint(self) -> &'static str {
+        match self {
+            AttributeKind::Strength => "melee damage",
+            AttributeKind::Magic => "max mana",
+            AttributeKind::Dexterity => "attack speed",
+            AttributeKind::Speed => "move speed",
+            AttributeKind::Defense => "damage reduction",
+        }
+    }
+
+    fn value(self, attributes: &Attributes) -> u32 {
+        match self {
+            AttributeKind::Strength => attributes.strength,
+            AttributeKind::Magic => attributes.magic,
+            AttributeKind::Dexterity => attributes.dexterity,
+            AttributeKind::Speed => attributes.speed,
+            AttributeKind::Defense => attributes.defense,
+        }
+    }
+
+    fn value_mut(self, attributes: &mut Attributes) -> &mut u32 {
+        match self {
+            AttributeKind::Strength => &mut attributes.strength,
+            AttributeKind::Magic => &mut attributes.magic,
+            AttributeKind::Dexterity => &mut attributes.dexterity,
+            AttributeKind::Speed => &mut attributes.speed,
+            AttributeKind::Defense => &mut attributes.defense,
+        }
+    }
+}
+
+/// Attributes as they were when the screen opened; points can't be
+/// removed below these values.
+#[derive(Resource)]
+struct AllocationBaseline(Attributes);
+
+/// Marker for attribute screen UI entities.
+#[derive(Component)]
+struct LevelUpUi;
+
+/// Buttons on the attribute screen.
+#[derive(Component, Clone, Copy)]
+enum LevelUpButton {
+    Increase(AttributeKind),
+    Decrease(AttributeKind),
+    Done,
+}
+
+/// Text showing an attribute's current value.
+#[derive(Component)]
+struct AttributeValueText(AttributeKind);
+
+/// Text showing level and unspent points.
+#[derive(Component)]
+struct PointsText;
+
+/// Text listing the values derived from attributes.
+#[derive(Component)]
+struct DerivedStatsText;
+
+/// Set up attribute screen systems.
+pub fn setup_level_up_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        open_attribute_screen.run_if(in_state(PlayState::Exploring)),
+    )
+    .add_systems(OnEnter(PlayState::LevelUp), spawn_level_up_screen)
+    .add_systems(
+        Update,
+        (level_up_input, close_attribute_screen, update_level_up_text)
+            .chain()
+            .run_if(in_state(PlayState::LevelUp)),
+    )
+    .add_systems(OnExit(PlayState::LevelUp), cleanup_level_up_screen);
+}
+
+/// Open the attribute screen with its hotkey.
+fn open_attribute_screen(
+    input: ActionInput,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+) {
+    if input.just_pressed(Action::Attributes) {
+        next_play_state.set(PlayState::LevelUp);
+    }
+}
+
+/// Close the attribute screen with its hotkey or the pause key.
+fn close_attribute_screen(
+    input: ActionInput,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+) {
+    if input.just_pressed(Action::Attributes) || input.just_pressed(Action::Pause) {
+        next_play_state.set(PlayState::Exploring);
+    }
+}
+
+/// Spawn the attribute screen.
+fn spawn_level_up_screen(
+    mut commands: Commands,
+    player_query: Query<&Attributes, With<Player>>,
+) {
+    let baseline = player_query.get_single().copied().unwrap_or_default();
+    commands.insert_resource(AllocationBaseline(baseline));
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            LevelUpUi,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("ATTRIBUTES"),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.7, 0.6)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            // Level and unspent points
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(LABEL_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+                PointsText,
+            ));
+
+            for kind in AttributeKind::ALL {
+                spawn_attribute_row(parent, kind);
+            }
+
+            // Derived values
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+                Node {
+                    margin: UiRect::vertical(Val::Px(30.0)),
+                    ..default()
+                },
+                DerivedStatsText,
+            ));
+
+            spawn_button(parent, "Done", LevelUpButton::Done, Val::Px(200.0));
+        });
+}
+
+/// Spawn one attribute row: name, value, -/+ buttons and what it affects.
+fn spawn_attribute_row(parent: &mut ChildBuilder, kind: AttributeKind) {
+    parent
+        .spawn(Node {
+            width: Val::Px(520.0),
+            align_items: AlignItems::Center,
+            margin: UiRect::vertical(Val::Px(4.0)),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(kind.label()),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(LABEL_COLOR),
+                Node {
+                    width: Val::Px(140.0),
+                    ..default()
+                },
+            ));
+            row.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(LABEL_COLOR),
+                Node {
+                    width: Val::Px(50.0),
+                    ..default()
+                },
+                AttributeValueText(kind),
+            ));
+            spawn_button(row, "-", LevelUpButton::Decrease(kind), Val::Px(40.0));
+            spawn_button(row, "+", LevelUpButton::Increase(kind), Val::Px(40.0));
+            row.spawn((
+                Text::new(kind.hint()),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+                Node {
+                    margin: UiRect::left(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Helper to spawn an attribute screen button.
+fn spawn_button(parent: &mut ChildBuilder, text: &str, button: LevelUpButton, width: Val) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width,
+                height: Val::Px(40.0),
+                margin: UiRect::all(Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+            button,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(text),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(LABEL_COLOR),
+            ));
+        });
+}
+
+/// Buttons whose interaction changed this frame.
+type ButtonInteraction = (Changed<Interaction>, With<Button>);
+
+/// Handle attribute screen button presses.
+///
+/// Spending needs an unspent point, and refunding only takes back points
+/// spent since the screen opened, so neither count can go negative.
+fn level_up_input(
+    mut interaction_query: Query<
+        (&Interaction, &LevelUpButton, &mut BackgroundColor),
+        ButtonInteraction,
+    >,
+    mut player_query: Query<(&mut Attributes, &mut Experience), With<Player>>,
+    baseline: Res<AllocationBaseline>,
+    mut next_play_state: ResMut<NextState<PlayState>>,
+) {
+    for (interaction, button, mut bg_color) in interaction_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                *bg_color = BUTTON_PRESSED_COLOR.into();
+                let Ok((mut attributes, mut experience)) = player_query.get_single_mut() else {
+                    continue;
+                };
+                match *button {
+                    LevelUpButton::Increase(kind) => {
+                        if experience.unspent_points > 0 {
+                            experience.unspent_points -= 1;
+                            *kind.value_mut(&mut attributes) += 1;
+                        }
+                    }
+                    LevelUpButton::Decrease(kind) => {
+                        if kind.value(&attributes) > kind.value(&baseline.0) {
+                            experience.unspent_points += 1;
+                            *kind.value_mut(&mut attributes) -= 1;
+                        }
+                    }
+                    LevelUpButton::Done => {
+                        next_play_state.set(PlayState::Exploring);
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *bg_color = BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+/// The derived stats text, kept apart from the other text queries.
+type DerivedStatsFilter = (
+    With<DerivedStatsText>,
+    Without<AttributeValueText>,
+    Without<PointsText>,
+);
+
+/// Refresh attribute values, unspent points and derived values.
+fn update_level_up_text(
+    player_query: Query<(&Attributes, &Experience, &PlayerStats, Option<&Weapon>), With<Player>>,
+    config: Res<PlayerConfig>,
+    mut value_query: Query<(&mut Text, &AttributeValueText)>,
+    mut points_query: Query<&mut Text, (With<PointsText>, Without<AttributeValueText>)>,
+    mut derived_query: Query<&mut Text, DerivedStatsFilter>,
+) {
+    let Ok((attributes, experience, stats, weapon)) = player_query.get_single() else {
+        return;
+    };
+
+    for (mut text, value) in value_query.iter_mut() {
+        text.0 = value.0.value(attributes).to_string();
+    }
+
+    for mut text in points_query.iter_mut() {
+        text.0 = format!(
+            "Level {}   XP {}/{}   Points to spend: {}",
+            experience.level,
+            experience.current,
+            experience.to_next_level(),
+            experience.unspent_points,
+        );
+    }
+
+    let attack_speed = attributes.attack_speed_multiplier();
+    let mut lines = Vec::new();
+    if let Some(weapon) = weapon {
+        lines.push(format!(
+            "Damage: {:.1}",
+            weapon.base_damage * attributes.damage_multiplier()
+        ));
+        lines.push(format!(
+            "Attack cooldown: {:.2}s",
+            weapon.attack_cooldown / attack_speed
+        ));
+    }
+    lines.push(format!(
+        "Move speed: {:.2}",
+        config.move_speed * attributes.move_speed_multiplier()
+    ));
+    lines.push(format!(
+        "Damage taken: {:.0}%",
+        (1.0 - attributes.damage_reduction()) * 100.0
+    ));
+    lines.push(format!("Max mana: {:.0}", stats.max_mana));
+
+    for mut text in derived_query.iter_mut() {
+        text.0 = lines.join("\n");
+    }
+}
+
+/// Remove the attribute screen.
+fn cleanup_level_up_screen(mut commands: Commands, query: Query<Entity, With<LevelUpUi>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<AllocationBaseline>();
+}
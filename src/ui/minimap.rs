@@ -0,0 +1,267 @@
+//! Corner minimap of explored tiles.
+//!
+//! Draws a square window of the current level's grid centered on the
+//! player: explored floor and wall tiles are filled in, unexplored ones stay
+//! blank. North (-Z) is up, matching the compass. A dot next to the center
+//! cell shows which way the player faces. Toggled with `Action::Map`, and
+//! placed in the top corner the HUD bars don't use.
+
+use bevy::prelude::*;
+
+use crate::core::{Action, ActionInput, GameSession, GameState, HudConfig, HudCorner};
+use crate::player::Player;
+use crate::world::{CurrentLevel, ExploredTiles, LevelRegistry};
+
+/// Tiles shown on each side of the player's tile.
+const MINIMAP_RADIUS: i32 = 10;
+
+/// Size of one tile cell in pixels at HUD scale 1.0.
+const CELL_SIZE: f32 = 6.0;
+
+/// Distance from the screen edge in pixels at HUD scale 1.0.
+const MARGIN: f32 = 20.0;
+
+/// Gap below the compass strip in pixels at HUD scale 1.0.
+const TOP_OFFSET: f32 = 48.0;
+
+const FLOOR_COLOR: Color = Color::srgba(0.55, 0.5, 0.4, 0.8);
+const WALL_COLOR: Color = Color::srgba(0.25, 0.22, 0.2, 0.9);
+const PLAYER_COLOR: Color = Color::srgb(0.9, 0.3, 0.2);
+const HEADING_COLOR: Color = Color::srgb(0.95, 0.85, 0.6);
+
+/// Whether the minimap is shown, kept across HUD layout rebuilds.
+#[derive(Resource)]
+struct MinimapShown(bool);
+
+impl Default for MinimapShown {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Marker for the minimap root container.
+#[derive(Component)]
+struct MinimapRoot;
+
+/// A tile cell, offset in tiles from the player's tile.
+#[derive(Component)]
+struct MinimapCell {
+    dx: i32,
+    dz: i32,
+}
+
+/// Dot showing the player's facing direction.
+#[derive(Component)]
+struct MinimapHeading;
+
+/// Set up minimap systems.
+pub fn setup_minimap_systems(app: &mut App) {
+    app.init_resource::<MinimapShown>()
+        .add_systems(OnEnter(GameSession), spawn_minimap_on_enter)
+        .add_systems(OnExit(GameSession), cleanup_minimap)
+        .add_systems(
+            Update,
+            (
+                toggle_minimap,
+                update_minimap_layout,
+                update_minimap_cells,
+                update_minimap_heading,
+            )
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        );
+}
+
+/// Side length of the map in cells.
+fn cells_across() -> i32 {
+    MINIMAP_RADIUS * 2 + 1
+}
+
+fn spawn_minimap_on_enter(
+    mut commands: Commands,
+    config: Res<HudConfig>,
+    shown: Res<MinimapShown>,
+) {
+    spawn_minimap(&mut commands, &config, shown.0);
+}
+
+/// Spawn the minimap in the top corner opposite the HUD bars.
+fn spawn_minimap(commands: &mut Commands, config: &HudConfig, shown: bool) {
+    let scale = config.clamped_scale();
+    let cell = CELL_SIZE * scale;
+    let size = cell * cells_across() as f32;
+
+    let (left, right) = if config.corner == HudCorner::TopRight {
+        (Val::Px(MARGIN * scale), Val::Auto)
+    } else {
+        (Val::Auto, Val::Px(MARGIN * scale))
+    };
+    let visibility = if shown {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Px(size),
+                height: Val::Px(size),
+                position_type: PositionType::Absolute,
+                top: Val::Px(TOP_OFFSET * scale),
+                left,
+                right,
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+            visibility,
+            MinimapRoot,
+        ))
+        .with_children(|parent| {
+            for dz in -MINIMAP_RADIUS..=MINIMAP_RADIUS {
+                for dx in -MINIMAP_RADIUS..=MINIMAP_RADIUS {
+                    parent.spawn((
+                        Node {
+                            width: Val::Px(cell),
+                            height: Val::Px(cell),
+                            position_type: PositionType::Absolute,
+                            left: Val::Px((dx + MINIMAP_RADIUS) as f32 * cell),
+                            top: Val::Px((dz + MINIMAP_RADIUS) as f32 * cell),
+                            ..default()
+                        },
+                        BackgroundColor(Color::NONE),
+                        MinimapCell { dx, dz },
+                    ));
+                }
+            }
+
+            // Player's tile, drawn over its cell
+            parent.spawn((
+                Node {
+                    width: Val::Px(cell),
+                    height: Val::Px(cell),
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(MINIMAP_RADIUS as f32 * cell),
+                    top: Val::Px(MINIMAP_RADIUS as f32 * cell),
+                    ..default()
+                },
+                BackgroundColor(PLAYER_COLOR),
+            ));
+
+            parent.spawn((
+                Node {
+                    width: Val::Px(cell / 2.0),
+                    height: Val::Px(cell / 2.0),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                BackgroundColor(HEADING_COLOR),
+                MinimapHeading,
+            ));
+        });
+}
+
+/// Show or hide the minimap.
+fn toggle_minimap(
+    input: ActionInput,
+    mut shown: ResMut<MinimapShown>,
+    mut query: Query<&mut Visibility, With<MinimapRoot>>,
+) {
+    if !input.just_pressed(Action::Map) {
+        return;
+    }
+
+    shown.0 = !shown.0;
+    for mut visibility in query.iter_mut() {
+        *visibility = if shown.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Rebuild the minimap when the HUD configuration changes.
+fn update_minimap_layout(
+    mut commands: Commands,
+    config: Res<HudConfig>,
+    shown: Res<MinimapShown>,
+    query: Query<Entity, With<MinimapRoot>>,
+) {
+    if !config.is_changed() || config.is_added() {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_minimap(&mut commands, &config, shown.0);
+}
+
+/// Color each cell by the explored tile under it.
+fn update_minimap_cells(
+    shown: Res<MinimapShown>,
+    explored: Res<ExploredTiles>,
+    current_level: Res<CurrentLevel>,
+    registry: Res<LevelRegistry>,
+    player_query: Query<&Transform, With<Player>>,
+    mut cell_query: Query<(&MinimapCell, &mut BackgroundColor)>,
+) {
+    if !shown.0 {
+        return;
+    }
+    let Some(level) = registry.get(&current_level.name) else {
+        return;
+    };
+    let Ok(transform) = player_query.get_single() else {
+        return;
+    };
+    let (x, z) = level.world_to_grid(transform.translation);
+
+    for (cell, mut background) in cell_query.iter_mut() {
+        let (tile_x, tile_z) = (x + cell.dx, z + cell.dz);
+        let color = if !explored.is_explored(tile_x, tile_z) {
+            Color::NONE
+        } else {
+            let kind = level.get_geometry(tile_x, tile_z).kind;
+            if kind.is_solid() {
+                WALL_COLOR
+            } else if kind.has_floor() {
+                FLOOR_COLOR
+            } else {
+                Color::NONE
+            }
+        };
+        background.set_if_neq(BackgroundColor(color));
+    }
+}
+
+/// Place the heading dot on the side of the player's cell they face.
+fn update_minimap_heading(
+    config: Res<HudConfig>,
+    player_query: Query<&Transform, With<Player>>,
+    mut heading_query: Query<&mut Node, With<MinimapHeading>>,
+) {
+    let Ok(transform) = player_query.get_single() else {
+        return;
+    };
+    let forward = (transform.rotation * Vec3::NEG_Z).xz().normalize_or_zero();
+
+    let cell = CELL_SIZE * config.clamped_scale();
+    let center = (MINIMAP_RADIUS as f32 + 0.5) * cell;
+    // x runs right and z runs down the map, like the level grid
+    let position = Vec2::splat(center) + forward * cell - Vec2::splat(cell / 4.0);
+
+    for mut node in heading_query.iter_mut() {
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+    }
+}
+
+/// Clean up the minimap when the game session ends.
+fn cleanup_minimap(mut commands: Commands, query: Query<Entity, With<MinimapRoot>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,6 +1,11 @@
 //! UI module - menus, HUD, and interface elements.
 
+mod compass;
+mod damage_indicator;
+mod enemy_health_bars;
 mod hud;
+mod level_up;
+mod minimap;
 mod plugin;
 
 pub use plugin::UiPlugin;
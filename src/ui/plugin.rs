@@ -1,9 +1,15 @@
 //! UI plugin - menus, HUD, and interface elements.
 
 use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
 
-use crate::core::GameState;
-use super::hud;
+use crate::combat::RunStats;
+use crate::core::{
+    format_play_time, AutoPickup, GameState, LookOptions, MasterVolume, OptionsMut, PlayTime,
+};
+use crate::persistence::{load_game, save_exists, PendingLoad};
+use crate::world::{CurrentLevel, LevelRegistry};
+use super::{compass, damage_indicator, enemy_health_bars, hud, level_up, minimap};
 
 /// UI plugin - handles all user interface.
 pub struct UiPlugin;
@@ -13,10 +19,28 @@ impl Plugin for UiPlugin {
         // Setup HUD systems
         hud::setup_hud_systems(app);
 
+        // Setup compass strip
+        compass::setup_compass_systems(app);
+
+        // Setup explored tile minimap
+        minimap::setup_minimap_systems(app);
+
+        // Setup damage direction indicators
+        damage_indicator::setup_damage_indicator_systems(app);
+
+        // Setup floating enemy health bars
+        enemy_health_bars::setup_enemy_health_bar_systems(app);
+
+        // Setup attribute allocation screen
+        level_up::setup_level_up_systems(app);
+
         app
             // Main menu
             .add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
-            .add_systems(Update, main_menu_input.run_if(in_state(GameState::MainMenu)))
+            .add_systems(
+                Update,
+                (main_menu_input, level_select_input).run_if(in_state(GameState::MainMenu)),
+            )
             .add_systems(OnExit(GameState::MainMenu), cleanup_main_menu)
 
             // Pause menu
@@ -24,6 +48,16 @@ impl Plugin for UiPlugin {
             .add_systems(Update, pause_menu_input.run_if(in_state(GameState::Paused)))
             .add_systems(OnExit(GameState::Paused), cleanup_pause_menu)
 
+            // Options panel (over the main or pause menu)
+            .add_systems(
+                Update,
+                (
+                    options_panel_input,
+                    (options_menu_input, update_option_labels).chain(),
+                )
+                    .run_if(in_state(GameState::MainMenu).or(in_state(GameState::Paused))),
+            )
+
             // Game over
             .add_systems(OnEnter(GameState::GameOver), setup_game_over)
             .add_systems(Update, game_over_input.run_if(in_state(GameState::GameOver)))
@@ -31,6 +65,18 @@ impl Plugin for UiPlugin {
     }
 }
 
+/// Buttons whose interaction changed this frame.
+type ChangedButton = (Changed<Interaction>, With<Button>);
+
+/// A menu button along with the background tinted on hover and press.
+type MenuButtonData<'a> = (&'a Interaction, &'a MenuButton, &'a mut BackgroundColor);
+
+/// Everything spawned while in the main menu, panels included.
+type MainMenuRootFilter = Or<(With<MainMenuUi>, With<LevelSelectUi>, With<OptionsUi>)>;
+
+/// Everything spawned while paused, panels included.
+type PauseMenuRootFilter = Or<(With<PauseMenuUi>, With<OptionsUi>)>;
+
 /// Marker for main menu UI entities.
 #[derive(Component)]
 struct MainMenuUi;
@@ -39,6 +85,23 @@ struct MainMenuUi;
 #[derive(Component)]
 struct MenuCamera;
 
+/// Marker for the level select panel (shown over the main menu).
+#[derive(Component)]
+struct LevelSelectUi;
+
+/// Marker for the options panel (shown over the main or pause menu).
+#[derive(Component)]
+struct OptionsUi;
+
+/// Text showing the current value of an option.
+#[derive(Component, Clone, Copy)]
+enum OptionLabel {
+    MouseSensitivity,
+    InvertY,
+    MasterVolume,
+    AutoPickup,
+}
+
 /// Marker for pause menu UI entities.
 #[derive(Component)]
 struct PauseMenuUi;
@@ -51,16 +114,30 @@ struct GameOverUi;
 #[derive(Component)]
 enum MenuButton {
     NewGame,
-    #[allow(dead_code)]
     Continue,
-    #[allow(dead_code)]
     Options,
     Quit,
     Resume,
     MainMenu,
     Retry,
+    LevelSelect,
+    /// Start the level with this registry key
+    Level(String),
+    Back,
+    SensitivityDown,
+    SensitivityUp,
+    ToggleInvertY,
+    VolumeDown,
+    VolumeUp,
+    ToggleAutoPickup,
 }
 
+/// Mouse sensitivity change per button press.
+const SENSITIVITY_STEP: f32 = 0.1;
+
+/// Range the options menu keeps mouse sensitivity in.
+const SENSITIVITY_RANGE: (f32, f32) = (0.1, 5.0);
+
 /// Set up the main menu.
 fn setup_main_menu(mut commands: Commands) {
     // Spawn a camera for UI rendering in menu state
@@ -112,9 +189,20 @@ fn setup_main_menu(mut commands: Commands) {
                 },
             ));
 
+            // Continue button (only with a save to continue from)
+            if save_exists() {
+                spawn_menu_button(parent, "Continue", MenuButton::Continue);
+            }
+
             // New Game button
             spawn_menu_button(parent, "New Game", MenuButton::NewGame);
 
+            // Level select button
+            spawn_menu_button(parent, "Level Select", MenuButton::LevelSelect);
+
+            // Options button
+            spawn_menu_button(parent, "Options", MenuButton::Options);
+
             // Quit button
             spawn_menu_button(parent, "Quit", MenuButton::Quit);
         });
@@ -148,13 +236,301 @@ fn spawn_menu_button(parent: &mut ChildBuilder, text: &str, button: MenuButton)
         });
 }
 
+/// Spawn the level select panel listing every loaded level.
+fn spawn_level_select(commands: &mut Commands, level_registry: &LevelRegistry) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.05, 0.05, 0.08)),
+            // Keep clicks from reaching the menu buttons underneath
+            FocusPolicy::Block,
+            LevelSelectUi,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("SELECT LEVEL"),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.7, 0.6)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(40.0)),
+                    ..default()
+                },
+            ));
+
+            let names = level_registry.names();
+            if names.is_empty() {
+                parent.spawn((
+                    Text::new("No levels loaded"),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.5, 0.5, 0.55)),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            for name in names {
+                spawn_menu_button(parent, name, MenuButton::Level(name.to_string()));
+            }
+
+            // Back button
+            spawn_menu_button(parent, "Back", MenuButton::Back);
+        });
+}
+
+/// Spawn the options panel over the current menu.
+fn spawn_options_menu(commands: &mut Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.05, 0.05, 0.08)),
+            // Keep clicks from reaching the menu buttons underneath
+            FocusPolicy::Block,
+            OptionsUi,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("OPTIONS"),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.7, 0.6)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(40.0)),
+                    ..default()
+                },
+            ));
+
+            spawn_option_row(
+                parent,
+                "Mouse sensitivity",
+                OptionLabel::MouseSensitivity,
+                MenuButton::SensitivityDown,
+                MenuButton::SensitivityUp,
+            );
+            spawn_option_row(
+                parent,
+                "Invert Y",
+                OptionLabel::InvertY,
+                MenuButton::ToggleInvertY,
+                MenuButton::ToggleInvertY,
+            );
+            spawn_option_row(
+                parent,
+                "Master volume",
+                OptionLabel::MasterVolume,
+                MenuButton::VolumeDown,
+                MenuButton::VolumeUp,
+            );
+            spawn_option_row(
+                parent,
+                "Auto-pickup",
+                OptionLabel::AutoPickup,
+                MenuButton::ToggleAutoPickup,
+                MenuButton::ToggleAutoPickup,
+            );
+
+            // Back button
+            spawn_menu_button(parent, "Back", MenuButton::Back);
+        });
+}
+
+/// Spawn an options row: a name, then the value between a lower and a raise button.
+fn spawn_option_row(
+    parent: &mut ChildBuilder,
+    name: &str,
+    label: OptionLabel,
+    lower: MenuButton,
+    raise: MenuButton,
+) {
+    parent
+        .spawn(Node {
+            width: Val::Px(480.0),
+            align_items: AlignItems::Center,
+            margin: UiRect::bottom(Val::Px(10.0)),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(name),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.85)),
+                Node {
+                    flex_grow: 1.0,
+                    ..default()
+                },
+            ));
+
+            spawn_option_button(row, "<", lower);
+            row.spawn((
+                // Filled in by `update_option_labels`
+                Text::new(""),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.7, 0.6)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    width: Val::Px(80.0),
+                    ..default()
+                },
+                label,
+            ));
+            spawn_option_button(row, ">", raise);
+        });
+}
+
+/// Helper to spawn a small button changing an option.
+fn spawn_option_button(parent: &mut ChildBuilder, text: &str, button: MenuButton) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(50.0),
+                height: Val::Px(50.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.2)),
+            button,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(text),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.85)),
+            ));
+        });
+}
+
+/// Open the options panel from the main or pause menu, and close it on `Back`.
+fn options_panel_input(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &MenuButton), ChangedButton>,
+    options_query: Query<Entity, With<OptionsUi>>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            MenuButton::Options if options_query.is_empty() => {
+                spawn_options_menu(&mut commands);
+            }
+            MenuButton::Back => {
+                for entity in options_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Apply option button presses to the live settings.
+///
+/// Button colors are left to each menu's input system.
+fn options_menu_input(
+    interaction_query: Query<(&Interaction, &MenuButton), ChangedButton>,
+    options: OptionsMut,
+) {
+    let OptionsMut {
+        mut look,
+        mut master_volume,
+        mut auto_pickup,
+        ..
+    } = options;
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            MenuButton::SensitivityDown | MenuButton::SensitivityUp => {
+                let step = if matches!(button, MenuButton::SensitivityUp) {
+                    SENSITIVITY_STEP
+                } else {
+                    -SENSITIVITY_STEP
+                };
+                // Round so repeated steps land on whole tenths
+                let sensitivity = ((look.sensitivity + step) * 10.0).round() / 10.0;
+                look.sensitivity = sensitivity.clamp(SENSITIVITY_RANGE.0, SENSITIVITY_RANGE.1);
+            }
+            MenuButton::ToggleInvertY => {
+                look.invert_y = !look.invert_y;
+            }
+            MenuButton::VolumeDown => master_volume.step(false),
+            MenuButton::VolumeUp => master_volume.step(true),
+            MenuButton::ToggleAutoPickup => {
+                auto_pickup.enabled = !auto_pickup.enabled;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Show the current option values in the options panel.
+fn update_option_labels(
+    look: Res<LookOptions>,
+    master_volume: Res<MasterVolume>,
+    auto_pickup: Res<AutoPickup>,
+    mut label_query: Query<(&mut Text, Ref<OptionLabel>)>,
+) {
+    let changed = look.is_changed() || master_volume.is_changed() || auto_pickup.is_changed();
+    for (mut text, label) in label_query.iter_mut() {
+        if !changed && !label.is_added() {
+            continue;
+        }
+        text.0 = match *label {
+            OptionLabel::MouseSensitivity => format!("{:.1}", look.sensitivity),
+            OptionLabel::InvertY => String::from(if look.invert_y { "On" } else { "Off" }),
+            OptionLabel::MasterVolume => format!("{:.0}%", master_volume.clamped() * 100.0),
+            OptionLabel::AutoPickup => String::from(if auto_pickup.enabled { "On" } else { "Off" }),
+        };
+    }
+}
+
 /// Handle main menu button interactions.
 fn main_menu_input(
-    mut interaction_query: Query<
-        (&Interaction, &MenuButton, &mut BackgroundColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut commands: Commands,
+    mut interaction_query: Query<MenuButtonData, ChangedButton>,
+    mut current_level: ResMut<CurrentLevel>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut play_time: ResMut<PlayTime>,
+    mut run_stats: ResMut<RunStats>,
     mut exit: EventWriter<AppExit>,
 ) {
     for (interaction, button, mut bg_color) in interaction_query.iter_mut() {
@@ -163,6 +539,27 @@ fn main_menu_input(
                 *bg_color = Color::srgb(0.3, 0.3, 0.35).into();
                 match button {
                     MenuButton::NewGame => {
+                        *current_level = CurrentLevel::default();
+                        play_time.reset();
+                        *run_stats = RunStats::start(&play_time);
+                        next_state.set(GameState::InGame);
+                    }
+                    MenuButton::Continue => match load_game() {
+                        Ok(data) => {
+                            current_level.name = data.level.clone();
+                            *play_time = PlayTime::restore(data.play_time);
+                            *run_stats = RunStats::start(&play_time);
+                            commands.insert_resource(PendingLoad(data));
+                            next_state.set(GameState::InGame);
+                        }
+                        Err(e) => {
+                            warn!("Cannot continue: {}", e);
+                        }
+                    },
+                    MenuButton::Level(name) => {
+                        current_level.name = name.clone();
+                        play_time.reset();
+                        *run_stats = RunStats::start(&play_time);
                         next_state.set(GameState::InGame);
                     }
                     MenuButton::Quit => {
@@ -181,10 +578,35 @@ fn main_menu_input(
     }
 }
 
+/// Open the level select panel, and close it on `Back`.
+fn level_select_input(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &MenuButton), ChangedButton>,
+    level_select_query: Query<Entity, With<LevelSelectUi>>,
+    level_registry: Res<LevelRegistry>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            MenuButton::LevelSelect if level_select_query.is_empty() => {
+                spawn_level_select(&mut commands, &level_registry);
+            }
+            MenuButton::Back => {
+                for entity in level_select_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Clean up main menu entities.
 fn cleanup_main_menu(
     mut commands: Commands,
-    ui_query: Query<Entity, With<MainMenuUi>>,
+    ui_query: Query<Entity, MainMenuRootFilter>,
     camera_query: Query<Entity, With<MenuCamera>>,
 ) {
     for entity in ui_query.iter() {
@@ -196,7 +618,7 @@ fn cleanup_main_menu(
 }
 
 /// Set up the pause menu.
-fn setup_pause_menu(mut commands: Commands) {
+fn setup_pause_menu(mut commands: Commands, play_time: Res<PlayTime>) {
     // Semi-transparent overlay
     commands
         .spawn((
@@ -229,17 +651,35 @@ fn setup_pause_menu(mut commands: Commands) {
             // Resume button
             spawn_menu_button(parent, "Resume", MenuButton::Resume);
 
+            // Options button
+            spawn_menu_button(parent, "Options", MenuButton::Options);
+
             // Main Menu button
             spawn_menu_button(parent, "Main Menu", MenuButton::MainMenu);
+
+            // Play time footer
+            parent.spawn((
+                Text::new(format!(
+                    "Session {}   Total {}",
+                    format_play_time(play_time.session),
+                    format_play_time(play_time.total),
+                )),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.5, 0.5, 0.55)),
+                Node {
+                    margin: UiRect::top(Val::Px(40.0)),
+                    ..default()
+                },
+            ));
         });
 }
 
 /// Handle pause menu button interactions.
 fn pause_menu_input(
-    mut interaction_query: Query<
-        (&Interaction, &MenuButton, &mut BackgroundColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut interaction_query: Query<MenuButtonData, ChangedButton>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     for (interaction, button, mut bg_color) in interaction_query.iter_mut() {
@@ -267,21 +707,40 @@ fn pause_menu_input(
 }
 
 /// Clean up pause menu entities.
-fn cleanup_pause_menu(mut commands: Commands, query: Query<Entity, With<PauseMenuUi>>) {
+fn cleanup_pause_menu(
+    mut commands: Commands,
+    query: Query<Entity, PauseMenuRootFilter>,
+) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
     }
 }
 
 /// Set up the game over screen.
-fn setup_game_over(mut commands: Commands) {
-    // Spawn a camera for UI rendering
-    commands.spawn((
-        Camera2d,
-        MenuCamera,
-    ));
+fn setup_game_over(
+    mut commands: Commands,
+    run_stats: Res<RunStats>,
+    play_time: Res<PlayTime>,
+    current_level: Option<Res<CurrentLevel>>,
+    level_registry: Option<Res<LevelRegistry>>,
+) {
+    let level_name = current_level
+        .map(|current| {
+            level_registry
+                .and_then(|registry| registry.get(&current.name).map(|level| level.name.clone()))
+                .unwrap_or_else(|| current.name.clone())
+        })
+        .unwrap_or_default();
 
-    // Dark overlay
+    let stats_lines = [
+        format!("Level: {}", level_name),
+        format!("Time survived: {}", format_play_time(run_stats.time_survived(&play_time))),
+        format!("Enemies killed: {}", run_stats.enemies_killed),
+        format!("Damage dealt: {:.0}", run_stats.damage_dealt),
+        format!("Damage taken: {:.0}", run_stats.damage_taken),
+    ];
+
+    // Dark overlay (the level stays loaded underneath, as when paused)
     commands
         .spawn((
             Node {
@@ -305,11 +764,32 @@ fn setup_game_over(mut commands: Commands) {
                 },
                 TextColor(Color::srgb(0.8, 0.2, 0.2)),
                 Node {
-                    margin: UiRect::bottom(Val::Px(60.0)),
+                    margin: UiRect::bottom(Val::Px(30.0)),
                     ..default()
                 },
             ));
 
+            // Run statistics
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                })
+                .with_children(|stats| {
+                    for line in stats_lines {
+                        stats.spawn((
+                            Text::new(line),
+                            TextFont {
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.7, 0.6, 0.6)),
+                        ));
+                    }
+                });
+
             // Retry button
             spawn_menu_button(parent, "Retry", MenuButton::Retry);
 
@@ -320,11 +800,10 @@ fn setup_game_over(mut commands: Commands) {
 
 /// Handle game over button interactions.
 fn game_over_input(
-    mut interaction_query: Query<
-        (&Interaction, &MenuButton, &mut BackgroundColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut interaction_query: Query<MenuButtonData, ChangedButton>,
     mut next_state: ResMut<NextState<GameState>>,
+    play_time: Res<PlayTime>,
+    mut run_stats: ResMut<RunStats>,
 ) {
     for (interaction, button, mut bg_color) in interaction_query.iter_mut() {
         match interaction {
@@ -332,6 +811,7 @@ fn game_over_input(
                 *bg_color = Color::srgb(0.3, 0.3, 0.35).into();
                 match button {
                     MenuButton::Retry => {
+                        *run_stats = RunStats::start(&play_time);
                         next_state.set(GameState::InGame);
                     }
                     MenuButton::MainMenu => {
@@ -351,15 +831,115 @@ fn game_over_input(
 }
 
 /// Clean up game over entities.
-fn cleanup_game_over(
-    mut commands: Commands,
-    ui_query: Query<Entity, With<GameOverUi>>,
-    camera_query: Query<Entity, With<MenuCamera>>,
-) {
+fn cleanup_game_over(mut commands: Commands, ui_query: Query<Entity, With<GameOverUi>>) {
     for entity in ui_query.iter() {
         commands.entity(entity).despawn_recursive();
     }
-    for entity in camera_query.iter() {
-        commands.entity(entity).despawn_recursive();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::input::mouse::MouseButtonInput;
+    use bevy::input::{ButtonState, InputPlugin};
+    use bevy::render::camera::{camera_system, ManualTextureViews};
+    use bevy::render::view::VisibilitySystems;
+    use bevy::text::TextPlugin;
+    use bevy::transform::TransformPlugin;
+    use bevy::window::{PrimaryWindow, WindowResolution};
+
+    /// Mark every node as rendered; the focus system ignores hidden nodes
+    /// and there is no renderer here to do it.
+    fn show_nodes(mut query: Query<&mut ViewVisibility, With<Node>>) {
+        for mut visibility in query.iter_mut() {
+            visibility.set();
+        }
+    }
+
+    /// A headless app laying out and focusing the main menu in an 800x600
+    /// window.
+    fn menu_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            WindowPlugin {
+                primary_window: None,
+                ..default()
+            },
+            TransformPlugin,
+            HierarchyPlugin,
+            TextPlugin,
+            bevy::ui::UiPlugin {
+                enable_rendering: false,
+                add_picking: false,
+            },
+        ))
+        .init_asset::<Image>()
+        .init_asset::<TextureAtlasLayout>()
+        .init_resource::<ManualTextureViews>()
+        .add_systems(
+            PostUpdate,
+            (
+                camera_system::<OrthographicProjection>.before(bevy::ui::UiSystem::Prepare),
+                show_nodes.in_set(VisibilitySystems::CheckVisibility),
+            ),
+        );
+
+        app.world_mut().spawn((
+            Window {
+                resolution: WindowResolution::new(800.0, 600.0),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        app.world_mut().run_system_once(setup_main_menu).unwrap();
+        app
+    }
+
+    /// Left-click at the center of the `Quit` button and report its interaction.
+    fn click_quit(app: &mut App) -> Interaction {
+        // Lay out the nodes before aiming the click
+        app.update();
+
+        let world = app.world_mut();
+        let quit = world
+            .query::<(Entity, &MenuButton)>()
+            .iter(world)
+            .find(|(_, button)| matches!(button, MenuButton::Quit))
+            .map(|(entity, _)| entity)
+            .unwrap();
+        let center = world.get::<GlobalTransform>(quit).unwrap().translation().truncate();
+        let (window, mut primary) = world
+            .query_filtered::<(Entity, &mut Window), With<PrimaryWindow>>()
+            .single_mut(world);
+        primary.set_physical_cursor_position(Some(center.as_dvec2()));
+        world.send_event(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+            window,
+        });
+
+        app.update();
+        *app.world().get::<Interaction>(quit).unwrap()
+    }
+
+    #[test]
+    fn click_reaches_main_menu_without_panel() {
+        let mut app = menu_app();
+        assert_eq!(click_quit(&mut app), Interaction::Pressed);
+    }
+
+    #[test]
+    fn level_select_blocks_clicks_to_main_menu() {
+        let mut app = menu_app();
+        let world = app.world_mut();
+        spawn_level_select(&mut world.commands(), &LevelRegistry::default());
+        world.flush();
+
+        // Quit sits under empty panel space, clear of the Back button
+        assert_eq!(click_quit(&mut app), Interaction::None);
     }
 }
@@ -0,0 +1,215 @@
+//! Ambient particle emitters placed by the level's ambient palette.
+//!
+//! Each `ParticleDef` on an ambient tile becomes a `ParticleEmitter` at the
+//! tile's position. Emitters spawn `rendering::Particle`s at their `rate`,
+//! which move, shrink and despawn on their own. Emitters and their particles
+//! are `LevelGeometry`, so they're cleaned up with the level.
+
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::*;
+
+use super::builder::LevelGeometry;
+use super::data::ParticleDef;
+use crate::core::GameState;
+use crate::rendering::Particle;
+
+/// Radius of the shared particle mesh.
+const PARTICLE_RADIUS: f32 = 0.02;
+
+/// Most particles one emitter spawns in a frame, so a long frame doesn't
+/// dump a burst.
+const MAX_EMITS_PER_FRAME: u32 = 4;
+
+/// Gravity drips fall under, in units per second squared.
+const DRIP_GRAVITY: f32 = 9.8;
+
+/// Kinds of ambient particles, keyed by `ParticleDef::kind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmbientParticleKind {
+    /// Motes drifting slowly through the whole tile
+    Dust,
+    /// Glowing sparks rising from the emitter
+    Embers,
+    /// Water drops falling from the emitter to the floor
+    Drip,
+}
+
+impl AmbientParticleKind {
+    /// Parse a palette kind name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "dust" => Some(Self::Dust),
+            "embers" | "ember" => Some(Self::Embers),
+            "drip" | "drips" => Some(Self::Drip),
+            _ => None,
+        }
+    }
+
+    /// Color used when the palette doesn't give one.
+    fn default_color(self) -> Color {
+        match self {
+            Self::Dust => Color::srgba(0.8, 0.78, 0.7, 0.35),
+            Self::Embers => Color::srgb(1.0, 0.55, 0.15),
+            Self::Drip => Color::srgba(0.6, 0.7, 0.8, 0.7),
+        }
+    }
+}
+
+/// Spawns ambient particles around its position.
+#[derive(Component)]
+pub struct ParticleEmitter {
+    pub kind: AmbientParticleKind,
+    /// Particles per second
+    pub rate: f32,
+    pub color: Color,
+    /// Height of the emitter above the tile's floor
+    pub height: f32,
+    /// Half the tile size, the horizontal extent dust fills
+    pub half_extent: f32,
+    /// Fractional particles carried over between frames
+    accumulator: f32,
+    /// Created on first emit, shared by this emitter's particles
+    material: Option<Handle<StandardMaterial>>,
+}
+
+/// Set up ambient particle systems.
+pub fn setup_ambient_particle_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        emit_ambient_particles.run_if(in_state(GameState::InGame)),
+    );
+}
+
+/// Spawn an emitter for a particle definition on the tile at `floor_pos`.
+///
+/// Unknown kinds are skipped with a warning.
+pub fn spawn_particle_emitter(
+    commands: &mut Commands,
+    floor_pos: Vec3,
+    tile_size: f32,
+    def: &ParticleDef,
+) {
+    let Some(kind) = AmbientParticleKind::from_name(&def.kind) else {
+        warn!("Unknown ambient particle kind '{}'", def.kind);
+        return;
+    };
+
+    let color = def
+        .color
+        .map_or(kind.default_color(), |(r, g, b, a)| Color::srgba(r, g, b, a));
+
+    commands.spawn((
+        ParticleEmitter {
+            kind,
+            rate: def.rate.max(0.0),
+            color,
+            height: def.height,
+            half_extent: tile_size / 2.0,
+            accumulator: 0.0,
+            material: None,
+        },
+        Transform::from_translation(floor_pos + Vec3::Y * def.height),
+        LevelGeometry,
+    ));
+}
+
+/// Spawn particles from every emitter at its rate.
+fn emit_ambient_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+    mut query: Query<(&mut ParticleEmitter, &Transform)>,
+) {
+    let mesh = mesh
+        .get_or_insert_with(|| meshes.add(Sphere::new(PARTICLE_RADIUS).mesh().ico(0).unwrap()))
+        .clone();
+
+    for (mut emitter, transform) in query.iter_mut() {
+        emitter.accumulator += emitter.rate * time.delta_secs();
+        let count = (emitter.accumulator.floor() as u32).min(MAX_EMITS_PER_FRAME);
+        if count == 0 {
+            continue;
+        }
+        emitter.accumulator = emitter.accumulator.fract();
+
+        let kind = emitter.kind;
+        let color = emitter.color;
+        let material = emitter
+            .material
+            .get_or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: color,
+                    // Embers glow; dust and drips just catch the light
+                    emissive: if kind == AmbientParticleKind::Embers {
+                        LinearRgba::from(color) * 4.0
+                    } else {
+                        LinearRgba::BLACK
+                    },
+                    alpha_mode: if color.alpha() < 1.0 {
+                        AlphaMode::Blend
+                    } else {
+                        AlphaMode::Opaque
+                    },
+                    unlit: kind != AmbientParticleKind::Drip,
+                    ..default()
+                })
+            })
+            .clone();
+
+        for _ in 0..count {
+            let (offset, particle) = new_particle(&emitter);
+            commands.spawn((
+                particle,
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(transform.translation + offset),
+                NotShadowCaster,
+                LevelGeometry,
+            ));
+        }
+    }
+}
+
+/// Random spawn offset from the emitter and initial state of one particle.
+fn new_particle(emitter: &ParticleEmitter) -> (Vec3, Particle) {
+    let signed = || rand::random::<f32>() * 2.0 - 1.0;
+    let range = |min: f32, max: f32| min + rand::random::<f32>() * (max - min);
+
+    match emitter.kind {
+        AmbientParticleKind::Dust => (
+            Vec3::new(
+                signed() * emitter.half_extent,
+                signed() * 0.5,
+                signed() * emitter.half_extent,
+            ),
+            Particle {
+                velocity: Vec3::new(signed() * 0.1, signed() * 0.05, signed() * 0.1),
+                gravity: 0.01,
+                lifetime: range(4.0, 7.0),
+                age: 0.0,
+            },
+        ),
+        AmbientParticleKind::Embers => (
+            Vec3::new(signed() * 0.15, 0.0, signed() * 0.15),
+            Particle {
+                velocity: Vec3::new(signed() * 0.15, range(0.4, 0.9), signed() * 0.15),
+                // Negative gravity: embers keep rising on the heat
+                gravity: -0.3,
+                lifetime: range(1.0, 2.5),
+                age: 0.0,
+            },
+        ),
+        AmbientParticleKind::Drip => (
+            Vec3::new(signed() * 0.1, 0.0, signed() * 0.1),
+            Particle {
+                velocity: Vec3::ZERO,
+                gravity: DRIP_GRAVITY,
+                // Just long enough to reach the floor
+                lifetime: (2.0 * emitter.height.max(0.0) / DRIP_GRAVITY).sqrt().max(0.1),
+                age: 0.0,
+            },
+        ),
+    }
+}
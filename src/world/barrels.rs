@@ -0,0 +1,95 @@
+//! Explosive barrels - destructible props that blow up when destroyed.
+//!
+//! Barrels are placed as prefabs in the level file. Their health and blast
+//! come from assets/data/props/explosive_barrel.ron and are added once the
+//! barrel is spawned, so the level builder stays free of global resources.
+//! The blast damages enemies and the player alike and sets off other barrels
+//! caught in it.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fs;
+
+use crate::combat::{Element, Explosive, Health};
+use crate::core::GameState;
+
+/// Radius of the barrel mesh and collider.
+pub const BARREL_RADIUS: f32 = 0.35;
+
+/// Height of the barrel mesh and collider.
+pub const BARREL_HEIGHT: f32 = 1.0;
+
+/// Marker for explosive barrels.
+#[derive(Component)]
+pub struct ExplosiveBarrel;
+
+/// Barrel tuning loaded from assets/data/props/explosive_barrel.ron.
+///
+/// Missing fields fall back to their defaults.
+#[derive(Resource, Clone, Deserialize)]
+#[serde(default)]
+pub struct BarrelConfig {
+    pub max_health: f32,
+    /// Damage at the center of the blast
+    pub explosion_damage: f32,
+    pub explosion_radius: f32,
+}
+
+impl Default for BarrelConfig {
+    fn default() -> Self {
+        Self {
+            max_health: 20.0,
+            explosion_damage: 40.0,
+            explosion_radius: 3.5,
+        }
+    }
+}
+
+impl BarrelConfig {
+    /// Load barrel config from RON file, falling back to defaults.
+    pub fn load() -> Self {
+        let path = "assets/data/props/explosive_barrel.ron";
+        match fs::read_to_string(path) {
+            Ok(contents) => match ron::from_str(&contents) {
+                Ok(config) => {
+                    info!("Loaded barrel config from {}", path);
+                    config
+                }
+                Err(e) => {
+                    error!("Failed to parse {}: {}. Using defaults.", path, e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                warn!("Could not read {}: {}. Using defaults.", path, e);
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Set up explosive barrel systems.
+pub fn setup_barrel_systems(app: &mut App) {
+    app.insert_resource(BarrelConfig::load()).add_systems(
+        Update,
+        arm_barrels.run_if(in_state(GameState::InGame)),
+    );
+}
+
+/// Give newly spawned barrels their health and blast.
+fn arm_barrels(
+    mut commands: Commands,
+    config: Res<BarrelConfig>,
+    query: Query<Entity, Added<ExplosiveBarrel>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert((
+            Health::new(config.max_health),
+            Explosive {
+                damage: config.explosion_damage,
+                radius: config.explosion_radius,
+                element: Element::Fire,
+            },
+        ));
+    }
+}
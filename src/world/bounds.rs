@@ -0,0 +1,59 @@
+//! Level bounds - rescues the player after falling out of the level.
+//!
+//! If the player clips through the floor they would otherwise fall forever.
+//! Once they drop below the level's kill plane they are moved back to the
+//! respawn point and a warning is logged so the geometry bug can be tracked
+//! down.
+
+use bevy::prelude::*;
+
+use crate::combat::Knockback;
+use crate::core::GameState;
+use crate::player::{MovementState, Player};
+
+/// Bounds of the current level, set when the level is built.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct LevelBounds {
+    /// Y below which the player is rescued
+    pub kill_plane_y: f32,
+    /// Where a rescued player is placed
+    pub respawn: Vec3,
+}
+
+/// Set up level bounds systems.
+pub fn setup_bounds_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        catch_fall_through
+            .run_if(in_state(GameState::InGame))
+            .run_if(resource_exists::<LevelBounds>),
+    );
+}
+
+/// Teleport the player back to the respawn point after falling below the kill plane.
+fn catch_fall_through(
+    bounds: Res<LevelBounds>,
+    mut player_query: Query<
+        (&mut Transform, &mut MovementState, Option<&mut Knockback>),
+        With<Player>,
+    >,
+) {
+    let Ok((mut transform, mut movement, knockback)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    if transform.translation.y >= bounds.kill_plane_y {
+        return;
+    }
+
+    warn!(
+        "Player fell out of the level at {:?} (kill plane y = {}), respawning at {:?}",
+        transform.translation, bounds.kill_plane_y, bounds.respawn
+    );
+
+    transform.translation = bounds.respawn;
+    movement.vertical_velocity = 0.0;
+    if let Some(mut knockback) = knockback {
+        knockback.velocity = Vec3::ZERO;
+    }
+}
@@ -1,37 +1,117 @@
 //! Level construction from data definitions.
 
+use bevy::ecs::system::SystemState;
 use bevy::pbr::NotShadowCaster;
 use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
-use super::data::{GeometryKind, LevelDefinition};
+use super::ambient_particles::spawn_particle_emitter;
+use super::damage_traps::spawn_damage_trap;
+use super::data::{FloorLayer, GeometryKind, LevelDefinition, MonsterSpawn};
+use super::doors::spawn_door;
+use super::exits::spawn_exit_trigger;
 use super::geometry::{
-    spawn_ceiling_tile, spawn_floor_tile, spawn_pillar, spawn_wall_cube, spawn_walls_for_tile,
+    spawn_ceiling_tile, spawn_floor_tile, spawn_pillar, spawn_risers_for_tile, spawn_wall_cube,
+    spawn_walls_for_tile, TileContext,
 };
 use super::materials::MaterialRegistry;
 use super::prefabs::spawn_prefab;
-use super::spawning::{spawn_light, spawn_monsters_from_grid};
+use super::spawning::{spawn_light, spawn_monsters_from_grid, spawn_traps_from_grid};
+use super::triggers::spawn_trigger_volume;
 use crate::enemies::data::EnemyRegistry;
-use crate::rendering::VisualConfig;
+use crate::enemies::traps::TrapRegistry;
+use crate::rendering::{VertexJitter, VisualConfig};
 
 /// Marker for all level geometry that should be cleaned up.
+///
+/// Level geometry (enemies and traps included) gets vertex jitter when it
+/// is enabled.
 #[derive(Component)]
+#[require(VertexJitter)]
 pub struct LevelGeometry;
 
 /// Marker component for the sky sphere (doesn't cast shadows).
 #[derive(Component)]
 pub struct SkySphere;
 
+/// Enemy and trap definitions the builder spawns from the monster grid.
+#[derive(Clone, Copy)]
+pub struct Registries<'a> {
+    pub enemies: &'a EnemyRegistry,
+    pub traps: &'a TrapRegistry,
+}
+
+/// Parameters `spawn_level_in_world` builds with; the registries and visual
+/// config are optional.
+type InWorldBuilder = (
+    Commands<'static, 'static>,
+    ResMut<'static, Assets<Mesh>>,
+    ResMut<'static, Assets<StandardMaterial>>,
+    Res<'static, AssetServer>,
+    Option<Res<'static, EnemyRegistry>>,
+    Option<Res<'static, TrapRegistry>>,
+    Option<Res<'static, VisualConfig>>,
+);
+
+/// Build an in-memory level directly into a `World`.
+///
+/// Intended for tests and editors: only `Assets<Mesh>`, `Assets<StandardMaterial>`
+/// and an `AssetServer` are required. `EnemyRegistry`, `TrapRegistry` and
+/// `VisualConfig` are used if present, otherwise defaults are used. Returns the
+/// player spawn position.
+pub fn spawn_level_in_world(world: &mut World, level: &LevelDefinition) -> Vec3 {
+    let mut system_state: SystemState<InWorldBuilder> = SystemState::new(world);
+
+    let player_pos = {
+        let (
+            mut commands,
+            mut meshes,
+            mut materials,
+            asset_server,
+            enemy_registry,
+            trap_registry,
+            visual_config,
+        ) = system_state.get_mut(world);
+        let default_registry = EnemyRegistry::default();
+        let default_traps = TrapRegistry::default();
+        let default_config = VisualConfig::default();
+
+        build_level_from_data(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            level,
+            &asset_server,
+            &Registries {
+                enemies: enemy_registry.as_deref().unwrap_or(&default_registry),
+                traps: trap_registry.as_deref().unwrap_or(&default_traps),
+            },
+            visual_config.as_deref().unwrap_or(&default_config),
+        )
+    };
+    system_state.apply(world);
+
+    player_pos
+}
+
 /// Build a level from a level definition.
+///
+/// Everything the builder needs is passed in explicitly; it reads no
+/// global resources, so levels can come from files or be built in code.
 pub fn build_level_from_data(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<StandardMaterial>,
     level: &LevelDefinition,
     asset_server: &AssetServer,
-    enemy_registry: &EnemyRegistry,
+    registries: &Registries,
     visual_config: &VisualConfig,
 ) -> Vec3 {
-    let mat_registry = MaterialRegistry::new(materials);
+    let mut mat_registry = MaterialRegistry::new(materials);
+    for (name, def) in &level.materials {
+        mat_registry.register(materials, name, def);
+    }
     let tile_size = level.tile_size;
     let wall_thickness = 0.2;
 
@@ -67,12 +147,11 @@ pub fn build_level_from_data(
         level,
         tile_size,
         asset_server,
-        enemy_registry,
+        registries,
     );
 
     // Return player spawn position
-    let player_world_pos = level.grid_to_world(level.player_start.0, level.player_start.1);
-    Vec3::new(player_world_pos.x, 1.0, player_world_pos.z)
+    level.spawn_position(level.player_start)
 }
 
 /// Set up global ambient light and directional light.
@@ -113,11 +192,26 @@ fn build_geometry(
     level: &LevelDefinition,
     tile_size: f32,
     wall_thickness: f32,
+) {
+    for layer in &level.floors {
+        build_floor_layer(commands, meshes, mat_registry, level, layer, tile_size, wall_thickness);
+    }
+}
+
+/// Build the geometry of a single floor layer, offset by its base elevation.
+fn build_floor_layer(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    mat_registry: &MaterialRegistry,
+    level: &LevelDefinition,
+    layer: &FloorLayer,
+    tile_size: f32,
+    wall_thickness: f32,
 ) {
     for z in 0..level.height as i32 {
         for x in 0..level.width as i32 {
-            let geo_tile = level.get_geometry(x, z);
-            let world_pos = level.grid_to_world(x, z);
+            let geo_tile = layer.get_geometry(x, z);
+            let world_pos = level.grid_to_world(x, z) + Vec3::Y * layer.base_elevation;
 
             match geo_tile.kind {
                 GeometryKind::Floor | GeometryKind::Doorway => {
@@ -129,7 +223,7 @@ fn build_geometry(
                             commands,
                             meshes,
                             mat_registry,
-                            level,
+                            layer,
                             x,
                             z,
                             world_pos,
@@ -145,7 +239,7 @@ fn build_geometry(
                         commands,
                         meshes,
                         mat_registry,
-                        level,
+                        layer,
                         x,
                         z,
                         world_pos,
@@ -153,6 +247,20 @@ fn build_geometry(
                         wall_thickness,
                     );
                 }
+                GeometryKind::Door => {
+                    spawn_floor_tile(commands, meshes, mat_registry, world_pos, tile_size, geo_tile);
+                    spawn_door(
+                        commands,
+                        meshes,
+                        mat_registry,
+                        layer,
+                        x,
+                        z,
+                        world_pos,
+                        tile_size,
+                        geo_tile,
+                    );
+                }
                 GeometryKind::Wall => {
                     spawn_wall_cube(commands, meshes, mat_registry, world_pos, tile_size, geo_tile);
                 }
@@ -161,8 +269,27 @@ fn build_geometry(
                 }
             }
 
+            // Close the sides of floors raised above their neighbors
+            if geo_tile.kind.has_floor() {
+                let tile = TileContext {
+                    layer,
+                    x,
+                    z,
+                    world_pos,
+                    tile_size,
+                    wall_thickness,
+                };
+                spawn_risers_for_tile(commands, meshes, mat_registry, &tile);
+            }
+
+            // Hurt the player standing on lava, spikes and the like
+            if let Some(trap_def) = &geo_tile.damage_trap {
+                let floor_pos = world_pos + Vec3::Y * geo_tile.elevation;
+                spawn_damage_trap(commands, floor_pos, tile_size, trap_def);
+            }
+
             // Process ambient tile at this position
-            let ambient_tile = level.get_ambient(x, z);
+            let ambient_tile = layer.get_ambient(x, z);
 
             // Spawn lights
             for light_def in &ambient_tile.lights {
@@ -176,12 +303,9 @@ fn build_geometry(
                 );
             }
 
-            // Log placeholder warnings for particles
+            // Spawn particle emitters
             for particle_def in &ambient_tile.particles {
-                warn!(
-                    "Particle system '{}' at ({}, {}) not yet implemented",
-                    particle_def.kind, x, z
-                );
+                spawn_particle_emitter(commands, world_pos, tile_size, particle_def);
             }
 
             // Log placeholder warnings for audio
@@ -193,14 +317,14 @@ fn build_geometry(
             }
 
             // Spawn ceiling tile if present (None means open sky/void)
-            if let Some(ceiling_tile) = level.get_ceiling(x, z) {
+            if let Some(ceiling_tile) = layer.get_ceiling(x, z) {
                 spawn_ceiling_tile(commands, meshes, mat_registry, world_pos, tile_size, ceiling_tile);
             }
         }
     }
 }
 
-/// Spawn monsters and prefabs.
+/// Spawn monsters, traps, prefabs, exits and triggers.
 fn spawn_entities(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
@@ -208,21 +332,52 @@ fn spawn_entities(
     level: &LevelDefinition,
     tile_size: f32,
     asset_server: &AssetServer,
-    enemy_registry: &EnemyRegistry,
+    registries: &Registries,
 ) {
-    // Spawn monsters from grid
-    spawn_monsters_from_grid(
-        commands,
-        level,
-        &level.monster_spawns,
-        asset_server,
-        enemy_registry,
-    );
+    // Weighted palette entries pick their enemy type here, once per spawn point
+    let mut rng = StdRng::seed_from_u64(level.spawn_seed.unwrap_or_else(rand::random));
+
+    // Spawn monsters and traps from each floor layer's grid
+    for (floor, layer) in level.floors.iter().enumerate() {
+        let monster_spawns: Vec<MonsterSpawn> = layer
+            .monster_spawns
+            .iter()
+            .filter_map(|spawn| spawn.roll(&mut rng))
+            .collect();
 
-    // Spawn prefabs (stairs, etc.)
-    let stair_material = mat_registry.get_floor("stone");
+        spawn_monsters_from_grid(
+            commands,
+            level,
+            &monster_spawns,
+            floor,
+            layer.base_elevation,
+            asset_server,
+            registries,
+        );
+        spawn_traps_from_grid(
+            commands,
+            meshes,
+            mat_registry,
+            level,
+            &monster_spawns,
+            layer.base_elevation,
+            registries.traps,
+        );
+    }
+
+    // Spawn prefabs (stairs, barrels, etc.)
     for prefab in &level.prefabs {
-        spawn_prefab(commands, meshes, prefab, tile_size, stair_material.clone());
+        spawn_prefab(commands, meshes, prefab, tile_size, mat_registry);
+    }
+
+    // Spawn exits to other levels
+    for exit in &level.exits {
+        spawn_exit_trigger(commands, level, exit);
+    }
+
+    // Spawn trigger volumes and pressure plates
+    for trigger in &level.triggers {
+        spawn_trigger_volume(commands, meshes, mat_registry, level, trigger);
     }
 }
 
@@ -284,3 +439,58 @@ fn spawn_sky_sphere(
         NotShadowCaster, // Prevent sky sphere from blocking directional light shadows
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::asset::AssetPlugin;
+    use bevy_rapier3d::prelude::Collider;
+
+    use crate::world::PaletteRegistry;
+
+    /// Two floor tiles walled in, open to the void on the east.
+    const LEVEL: &str = r#####"(
+        name: "Corridor",
+        tile_size: 2.0,
+        player_start: (1, 1),
+        geometry_palette: {
+            '#': (kind: Wall),
+            '.': (kind: Floor),
+        },
+        geometry: [
+            "####",
+            "#.. ",
+            "####",
+        ],
+        ambient: [
+            "....",
+            "....",
+            "....",
+        ],
+    )"#####;
+
+    #[test]
+    fn builds_in_memory_level_into_world() {
+        let level =
+            LevelDefinition::from_ron_str(LEVEL, "corridor", &PaletteRegistry::default()).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>();
+
+        let player_pos = spawn_level_in_world(app.world_mut(), &level);
+        assert_eq!(player_pos, Vec3::new(3.0, 1.0, 3.0));
+
+        // Floor slabs sit below y = 0, walls rise above it
+        let world = app.world_mut();
+        let mut query =
+            world.query_filtered::<&Transform, (With<Collider>, With<LevelGeometry>)>();
+        let (floors, walls): (Vec<&Transform>, Vec<&Transform>) =
+            query.iter(world).partition(|transform| transform.translation.y < 0.0);
+
+        assert_eq!(floors.len(), 2);
+        // Nine wall cubes, plus an edge wall where the floor meets the void
+        assert_eq!(walls.len(), 10);
+    }
+}
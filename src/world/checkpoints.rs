@@ -0,0 +1,286 @@
+//! Checkpoints - shrines that set where the player respawns after dying.
+//!
+//! Checkpoints are placed as prefabs in the level file. Stepping onto one
+//! records it in `LastCheckpoint` and fully heals the player. When the
+//! player dies, the level is kept; retrying from the game over screen moves
+//! them back to the last checkpoint of the current level (or the level's
+//! respawn point if none was reached) at full health, and enemies spawned
+//! near it are rebuilt from the level's monster grid.
+
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use super::bounds::LevelBounds;
+use super::builder::{LevelGeometry, Registries};
+use super::data::{CurrentLevel, LevelRegistry, MonsterSpawn, PrefabInstance};
+use super::geometry::is_on_tile;
+use super::spawning::spawn_monsters_from_grid;
+use crate::combat::{CombatState, Dead, Health, Knockback, Stamina};
+use crate::core::{GameSession, GameState, PlayState};
+use crate::enemies::data::EnemyRegistry;
+use crate::enemies::{EnemyType, SpawnPoint, TrapRegistry};
+use crate::player::{DodgeRoll, MovementState, Player};
+
+/// Radius of the checkpoint's floor rune.
+const RUNE_RADIUS: f32 = 0.6;
+
+/// Thickness of the floor rune.
+const RUNE_HEIGHT: f32 = 0.05;
+
+/// Height above a checkpoint's floor within which the player touches it.
+const TOUCH_HEIGHT: f32 = 2.5;
+
+/// Height above the floor the player respawns at, as for the player start.
+const RESPAWN_HEIGHT: f32 = 1.0;
+
+/// Enemies that spawned this close to the respawn point are reset.
+const ENEMY_RESET_RADIUS: f32 = 12.0;
+
+/// A tile that becomes the respawn point when the player steps on it.
+#[derive(Component)]
+pub struct Checkpoint {
+    /// Half the tile size, the horizontal extent of the checkpoint
+    pub half_extent: f32,
+    /// Whether the player is standing on it, so it only triggers on entry
+    occupied: bool,
+}
+
+/// The checkpoint the player last touched.
+#[derive(Resource, Clone, Debug)]
+pub struct LastCheckpoint {
+    /// Registry key of the level the checkpoint is in
+    pub level: String,
+    /// Where the player respawns
+    pub position: Vec3,
+}
+
+/// Set up checkpoint systems.
+pub fn setup_checkpoint_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        touch_checkpoints
+            .run_if(in_state(PlayState::Exploring))
+            .run_if(in_state(GameState::InGame)),
+    )
+    .add_systems(
+        OnTransition {
+            exited: GameState::GameOver,
+            entered: GameState::InGame,
+        },
+        (respawn_player, reset_nearby_enemies),
+    )
+    .add_systems(OnExit(GameSession), clear_last_checkpoint);
+}
+
+/// Spawn a checkpoint rune with a light on its tile.
+pub fn spawn_checkpoint(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    prefab: &PrefabInstance,
+    tile_size: f32,
+    material: Handle<StandardMaterial>,
+) {
+    let x = prefab.position.0 as f32 * tile_size + tile_size / 2.0;
+    let z = prefab.position.1 as f32 * tile_size + tile_size / 2.0;
+    let floor_pos = Vec3::new(x, prefab.from_elevation, z);
+
+    commands
+        .spawn((
+            Checkpoint {
+                half_extent: tile_size / 2.0,
+                occupied: false,
+            },
+            Mesh3d(meshes.add(Cylinder::new(RUNE_RADIUS, RUNE_HEIGHT))),
+            MeshMaterial3d(material),
+            Transform::from_translation(floor_pos + Vec3::Y * RUNE_HEIGHT / 2.0),
+            NotShadowCaster,
+            LevelGeometry,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                PointLight {
+                    color: Color::srgb(0.6, 0.8, 1.0),
+                    intensity: 20_000.0,
+                    range: 4.0,
+                    shadows_enabled: false,
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 1.0, 0.0),
+            ));
+        });
+}
+
+/// Record the checkpoint the player steps onto and fully heal them.
+fn touch_checkpoints(
+    mut commands: Commands,
+    current_level: Res<CurrentLevel>,
+    mut player_query: Query<(&Transform, &mut Health, &mut Stamina), With<Player>>,
+    mut checkpoint_query: Query<(&mut Checkpoint, &Transform), Without<Player>>,
+) {
+    let Ok((player_transform, mut health, mut stamina)) = player_query.get_single_mut() else {
+        return;
+    };
+    let player_pos = player_transform.translation;
+
+    for (mut checkpoint, transform) in checkpoint_query.iter_mut() {
+        let floor_y = transform.translation.y - RUNE_HEIGHT / 2.0;
+        let offset = player_pos - transform.translation.with_y(floor_y);
+        let inside = is_on_tile(offset, checkpoint.half_extent, TOUCH_HEIGHT);
+
+        if !inside {
+            checkpoint.occupied = false;
+            continue;
+        }
+        if checkpoint.occupied {
+            continue;
+        }
+        checkpoint.occupied = true;
+
+        let position = transform.translation.with_y(floor_y + RESPAWN_HEIGHT);
+        info!("Checkpoint reached at {:?}", position);
+        commands.insert_resource(LastCheckpoint {
+            level: current_level.name.clone(),
+            position,
+        });
+        health.current = health.maximum;
+        stamina.current = stamina.maximum;
+        stamina.exhausted = false;
+    }
+}
+
+/// Where the player respawns: the last checkpoint if it is in the current
+/// level, otherwise the level's respawn point.
+fn respawn_position(
+    last_checkpoint: Option<&LastCheckpoint>,
+    current_level: &CurrentLevel,
+    bounds: Option<&LevelBounds>,
+) -> Option<Vec3> {
+    last_checkpoint
+        .filter(|checkpoint| checkpoint.level == current_level.name)
+        .map(|checkpoint| checkpoint.position)
+        .or_else(|| bounds.map(|bounds| bounds.respawn))
+}
+
+/// Everything a respawn resets on the player.
+type RespawnedPlayerData<'a> = (
+    Entity,
+    &'a mut Transform,
+    &'a mut Health,
+    &'a mut Stamina,
+    &'a mut CombatState,
+    &'a mut MovementState,
+    Option<&'a mut Knockback>,
+);
+
+/// Bring the dead player back at the respawn point with full health.
+fn respawn_player(
+    mut commands: Commands,
+    last_checkpoint: Option<Res<LastCheckpoint>>,
+    current_level: Res<CurrentLevel>,
+    bounds: Option<Res<LevelBounds>>,
+    mut player_query: Query<RespawnedPlayerData, With<Player>>,
+) {
+    let Ok((entity, mut transform, mut health, mut stamina, mut combat, mut movement, knockback)) =
+        player_query.get_single_mut()
+    else {
+        return;
+    };
+
+    if let Some(position) =
+        respawn_position(last_checkpoint.as_deref(), &current_level, bounds.as_deref())
+    {
+        transform.translation = position;
+    }
+    health.current = health.maximum;
+    stamina.current = stamina.maximum;
+    stamina.exhausted = false;
+    *combat = CombatState::default();
+    movement.vertical_velocity = 0.0;
+    if let Some(mut knockback) = knockback {
+        knockback.velocity = Vec3::ZERO;
+    }
+    commands.entity(entity).remove::<(Dead, DodgeRoll)>();
+    info!("Player respawned at {:?}", transform.translation);
+}
+
+/// Rebuild the enemies that spawned near the respawn point, reviving the
+/// dead ones and returning the living ones to their spawn at full health.
+///
+/// Enemies still in the level keep their type; only spawn points with no
+/// enemy left roll a new one.
+#[allow(clippy::too_many_arguments)]
+fn reset_nearby_enemies(
+    mut commands: Commands,
+    last_checkpoint: Option<Res<LastCheckpoint>>,
+    current_level: Res<CurrentLevel>,
+    bounds: Option<Res<LevelBounds>>,
+    level_registry: Res<LevelRegistry>,
+    asset_server: Res<AssetServer>,
+    enemy_registry: Res<EnemyRegistry>,
+    trap_registry: Res<TrapRegistry>,
+    enemy_query: Query<(Entity, &SpawnPoint, &EnemyType)>,
+) {
+    let Some(level) = level_registry.get(&current_level.name) else {
+        return;
+    };
+    let Some(respawn) =
+        respawn_position(last_checkpoint.as_deref(), &current_level, bounds.as_deref())
+    else {
+        return;
+    };
+
+    let mut rng = StdRng::seed_from_u64(level.spawn_seed.unwrap_or_else(rand::random));
+
+    for (floor, layer) in level.floors.iter().enumerate() {
+        let is_nearby = |(x, z): (i32, i32)| {
+            let spawn_pos = level.grid_to_world(x, z).with_y(layer.base_elevation);
+            spawn_pos.distance(respawn) <= ENEMY_RESET_RADIUS
+        };
+
+        // Enemies still in the level come back as the same type
+        let mut monster_spawns = Vec::new();
+        for (entity, spawn_point, enemy_type) in enemy_query.iter() {
+            if spawn_point.floor == floor && is_nearby(spawn_point.tile) {
+                commands.entity(entity).despawn_recursive();
+                monster_spawns.push(MonsterSpawn {
+                    grid_pos: spawn_point.tile,
+                    enemy_type: enemy_type.0.clone(),
+                });
+            }
+        }
+
+        // Spawn points whose enemy is gone are rolled again
+        let revived: Vec<MonsterSpawn> = layer
+            .monster_spawns
+            .iter()
+            .filter(|spawn| is_nearby(spawn.grid_pos))
+            .filter(|spawn| {
+                !monster_spawns
+                    .iter()
+                    .any(|existing| existing.grid_pos == spawn.grid_pos)
+            })
+            .filter_map(|spawn| spawn.roll(&mut rng))
+            .collect();
+        monster_spawns.extend(revived);
+
+        spawn_monsters_from_grid(
+            &mut commands,
+            level,
+            &monster_spawns,
+            floor,
+            layer.base_elevation,
+            &asset_server,
+            &Registries {
+                enemies: &enemy_registry,
+                traps: &trap_registry,
+            },
+        );
+    }
+}
+
+/// Forget the last checkpoint when the game session ends.
+fn clear_last_checkpoint(mut commands: Commands) {
+    commands.remove_resource::<LastCheckpoint>();
+}
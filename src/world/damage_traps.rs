@@ -0,0 +1,189 @@
+//! Damaging floor tiles - lava pools and spike beds.
+//!
+//! Geometry palette entries with a `damage_trap` get a `DamageTrap` over
+//! their floor. While the player stands on it, the trap sends a
+//! `DamageEvent` every `interval` seconds, starting the moment they step on.
+//! Damage goes through the regular pipeline, so resistances apply and the
+//! player's i-frames space out hits from continuous traps. Traps marked
+//! `hurts_enemies` treat enemies standing on them the same way; enemies have
+//! no i-frames, so they're hit at most once per player i-frame duration.
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use super::builder::LevelGeometry;
+use super::data::DamageTrapDef;
+use super::geometry::is_on_tile;
+use crate::combat::{CombatTuning, DamageEvent, Dead};
+use crate::core::{Element, GameState, PlayState};
+use crate::enemies::EnemyStats;
+use crate::player::Player;
+
+/// Height above the trap's floor within which the player counts as
+/// standing on it. The player's center is 0.8 above their feet, so a
+/// jumping player clears the trap.
+const TRAP_CONTACT_HEIGHT: f32 = 1.0;
+
+/// A floor tile that hurts the player (and optionally enemies) standing on it.
+#[derive(Component)]
+pub struct DamageTrap {
+    pub damage: f32,
+    pub element: Element,
+    /// Seconds between hits (0.0 = every frame)
+    pub interval: f32,
+    /// Whether enemies standing on the trap are damaged too
+    pub hurts_enemies: bool,
+    /// Half the tile size, the horizontal extent of the trap
+    pub half_extent: f32,
+    /// Seconds until the next hit for each entity staying on the trap
+    cooldowns: HashMap<Entity, f32>,
+}
+
+/// Set up damage trap systems.
+pub fn setup_damage_trap_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        damage_on_traps
+            .run_if(in_state(GameState::InGame))
+            .run_if(in_state(PlayState::Exploring)),
+    );
+}
+
+/// Spawn a damage trap over the floor at `floor_pos`.
+pub fn spawn_damage_trap(
+    commands: &mut Commands,
+    floor_pos: Vec3,
+    tile_size: f32,
+    def: &DamageTrapDef,
+) {
+    commands.spawn((
+        DamageTrap {
+            damage: def.damage,
+            element: def.element,
+            interval: def.interval.max(0.0),
+            hurts_enemies: def.hurts_enemies,
+            half_extent: tile_size / 2.0,
+            cooldowns: HashMap::default(),
+        },
+        Transform::from_translation(floor_pos),
+        LevelGeometry,
+    ));
+}
+
+/// Filter for the player while still alive.
+type LivingPlayer = (With<Player>, Without<Dead>);
+
+/// Filter for enemies that are still alive.
+type LivingEnemy = (With<EnemyStats>, Without<Dead>);
+
+/// Damage the player, and enemies on traps that hurt them, on every trap
+/// they're standing on.
+fn damage_on_traps(
+    time: Res<Time>,
+    tuning: Res<CombatTuning>,
+    player_query: Query<(Entity, &Transform), LivingPlayer>,
+    enemy_query: Query<(Entity, &Transform), LivingEnemy>,
+    mut trap_query: Query<(Entity, &mut DamageTrap, &Transform)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (trap_entity, mut trap, transform) in trap_query.iter_mut() {
+        let trap = &mut *trap;
+        let player = player_query
+            .iter()
+            .map(|(entity, victim)| (entity, victim.translation, trap.interval));
+        let enemy_interval = trap.interval.max(tuning.player_i_frames);
+        let enemies = enemy_query
+            .iter()
+            .filter(|_| trap.hurts_enemies)
+            .map(|(entity, victim)| (entity, victim.translation, enemy_interval));
+
+        // Only entities still on the trap keep their cooldown, so stepping
+        // back on hurts straight away
+        let mut cooldowns = HashMap::default();
+        for (victim, position, interval) in player.chain(enemies) {
+            let offset = position - transform.translation;
+            if !is_on_tile(offset, trap.half_extent, TRAP_CONTACT_HEIGHT) {
+                continue;
+            }
+
+            let cooldown = trap.cooldowns.get(&victim).copied().unwrap_or(0.0) - time.delta_secs();
+            if cooldown > 0.0 {
+                cooldowns.insert(victim, cooldown);
+                continue;
+            }
+            cooldowns.insert(victim, interval);
+
+            damage_events.send(DamageEvent {
+                target: victim,
+                source: trap_entity,
+                amount: trap.damage,
+                element: trap.element,
+                knockback: Vec3::ZERO,
+                was_crit: false,
+                melee: false,
+            });
+        }
+        trap.cooldowns = cooldowns;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+    use std::time::Duration;
+
+    /// An app running only `damage_on_traps`, with a continuous trap at the
+    /// origin and an enemy standing on it.
+    fn trap_app(hurts_enemies: bool) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)))
+            .insert_resource(CombatTuning::default())
+            .add_event::<DamageEvent>()
+            .add_systems(Update, damage_on_traps);
+
+        app.world_mut().spawn((
+            DamageTrap {
+                damage: 8.0,
+                element: Element::Fire,
+                interval: 0.0,
+                hurts_enemies,
+                half_extent: 1.0,
+                cooldowns: HashMap::default(),
+            },
+            Transform::default(),
+        ));
+        let enemy = app
+            .world_mut()
+            .spawn((EnemyStats::default(), Transform::from_xyz(0.3, 0.0, -0.2)))
+            .id();
+        (app, enemy)
+    }
+
+    /// Run `frames` updates and count the hits on `target`.
+    fn hits_over(app: &mut App, target: Entity, frames: u32) -> usize {
+        let mut cursor = app.world().resource::<Events<DamageEvent>>().get_cursor();
+        let mut hits = 0;
+        for _ in 0..frames {
+            app.update();
+            let events = app.world().resource::<Events<DamageEvent>>();
+            hits += cursor.read(events).filter(|event| event.target == target).count();
+        }
+        hits
+    }
+
+    #[test]
+    fn continuous_trap_hurts_enemy_at_i_frame_rate() {
+        let (mut app, enemy) = trap_app(true);
+
+        // 0.8 s on the trap with 0.5 s spacing: on stepping on and once more
+        assert_eq!(hits_over(&mut app, enemy, 16), 2);
+    }
+
+    #[test]
+    fn trap_ignores_enemies_unless_marked() {
+        let (mut app, enemy) = trap_app(false);
+        assert_eq!(hits_over(&mut app, enemy, 20), 0);
+    }
+}
@@ -1,21 +1,27 @@
 //! Level data structures and RON loading.
 
 use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
 use super::error::DataLoadError;
+use crate::core::Element;
 
 // === External Palette File Types ===
 
 /// Generic palette file structure for tile-based definitions.
 /// Uses "tiles" as the field name, with "entries" as an alias for backwards compatibility.
+/// Geometry and ceiling palettes may also define named `materials` for their tiles.
 #[derive(Debug, Clone, Deserialize)]
 pub struct Palette<T> {
     #[serde(alias = "entries")]
     pub tiles: HashMap<char, T>,
+    #[serde(default)]
+    pub materials: HashMap<String, MaterialDef>,
 }
 
 /// Type alias for geometry palette files.
@@ -24,8 +30,8 @@ pub type GeometryPaletteFile = Palette<GeometryTileDef>;
 /// Type alias for ambient palette files.
 pub type AmbientPaletteFile = Palette<AmbientTileDef>;
 
-/// Type alias for monster palette files (maps char to enemy type name).
-pub type MonsterPaletteFile = Palette<String>;
+/// Type alias for monster palette files (maps char to enemy types).
+pub type MonsterPaletteFile = Palette<MonsterPaletteEntry>;
 
 /// Type alias for ceiling palette files.
 pub type CeilingPaletteFile = Palette<CeilingTileDef>;
@@ -89,19 +95,31 @@ pub enum GeometryKind {
     Wall,
     Pillar,
     Doorway,
+    /// Doorway closed off by a hinged door
+    Door,
     Void,
 }
 
 impl GeometryKind {
     /// Whether this tile kind has a floor.
     pub fn has_floor(&self) -> bool {
-        matches!(self, GeometryKind::Floor | GeometryKind::Pillar | GeometryKind::Doorway)
+        matches!(
+            self,
+            GeometryKind::Floor | GeometryKind::Pillar | GeometryKind::Doorway | GeometryKind::Door
+        )
     }
 
     /// Whether this tile kind is solid (blocks movement).
     pub fn is_solid(&self) -> bool {
         matches!(self, GeometryKind::Wall)
     }
+
+    /// Whether the player or enemies can stand in the middle of this tile.
+    ///
+    /// Door tiles aren't: the closed panel runs through the middle.
+    pub fn is_walkable(&self) -> bool {
+        matches!(self, GeometryKind::Floor | GeometryKind::Doorway)
+    }
 }
 
 /// Definition of a geometry tile in the palette.
@@ -116,6 +134,28 @@ pub struct GeometryTileDef {
     pub floor_depth: Option<f32>,
     #[serde(default)]
     pub elevation: Option<f32>,  // Y-offset for floor surface
+    /// Door tiles only: trigger event that opens and closes the door instead
+    /// of the player interacting with it
+    #[serde(default)]
+    pub trigger: Option<String>,
+    /// Damage dealt to the player standing on this tile (lava, spikes)
+    #[serde(default)]
+    pub damage_trap: Option<DamageTrapDef>,
+}
+
+/// Floor damage of a geometry tile.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DamageTrapDef {
+    pub damage: f32,
+    #[serde(default)]
+    pub element: Element,
+    /// Seconds between hits; 0.0 hits every frame (continuous, like lava),
+    /// limited only by the player's i-frames
+    #[serde(default)]
+    pub interval: f32,
+    /// Whether enemies standing on the tile are damaged too, not just the player
+    #[serde(default)]
+    pub hurts_enemies: bool,
 }
 
 // === Ambient Types ===
@@ -186,6 +226,26 @@ pub struct AmbientTileDef {
     pub audio: Vec<AudioDef>,
 }
 
+// === Materials ===
+
+fn default_roughness() -> f32 {
+    0.9
+}
+
+/// A named material defined in level data, usable by geometry and ceiling
+/// tiles like the built-in ones.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaterialDef {
+    pub base_color: (f32, f32, f32),
+    #[serde(default = "default_roughness", alias = "roughness")]
+    pub perceptual_roughness: f32,
+    #[serde(default)]
+    pub metallic: f32,
+    /// Glow color, in linear RGB (can exceed 1.0)
+    #[serde(default)]
+    pub emissive: Option<(f32, f32, f32)>,
+}
+
 // === Global Ambient ===
 
 /// Global ambient light settings.
@@ -219,21 +279,133 @@ pub struct SpawnZoneDef {
 
 // === Monster Spawns ===
 
+/// One option of a weighted monster palette entry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeightedEnemy {
+    pub enemy_type: String,
+    /// Relative chance of this option (need not sum to 1.0)
+    pub weight: f32,
+}
+
+/// A monster palette entry.
+///
+/// Either a plain enemy type (`'O': "orc"`) or a weighted table that picks
+/// one type per spawn (`'R': [(enemy_type: "rat", weight: 0.7), ...]`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MonsterPaletteEntry {
+    Single(String),
+    Weighted(Vec<WeightedEnemy>),
+}
+
+impl MonsterPaletteEntry {
+    /// Every enemy type this entry can spawn.
+    pub fn enemy_types(&self) -> Vec<&str> {
+        match self {
+            Self::Single(enemy_type) => vec![enemy_type.as_str()],
+            Self::Weighted(options) => {
+                options.iter().map(|option| option.enemy_type.as_str()).collect()
+            }
+        }
+    }
+
+    /// Pick the enemy type for one spawn. `None` if the weights are invalid.
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<&str> {
+        match self {
+            Self::Single(enemy_type) => Some(enemy_type),
+            Self::Weighted(options) => {
+                let index = WeightedIndex::new(options.iter().map(|option| option.weight)).ok()?;
+                Some(&options[index.sample(rng)].enemy_type)
+            }
+        }
+    }
+}
+
 /// A resolved monster spawn point from the monster grid.
 #[derive(Debug, Clone)]
 pub struct ResolvedMonsterSpawn {
+    /// Grid position (x, z).
+    pub grid_pos: (i32, i32),
+    /// Enemy type(s) that can spawn here (match EnemyRegistry keys).
+    pub entry: MonsterPaletteEntry,
+}
+
+impl ResolvedMonsterSpawn {
+    /// Pick the enemy type spawned here this time.
+    pub fn roll(&self, rng: &mut impl Rng) -> Option<MonsterSpawn> {
+        let Some(enemy_type) = self.entry.pick(rng) else {
+            warn!(
+                "Monster spawn at ({}, {}) has no valid weights",
+                self.grid_pos.0, self.grid_pos.1
+            );
+            return None;
+        };
+        Some(MonsterSpawn {
+            grid_pos: self.grid_pos,
+            enemy_type: enemy_type.to_string(),
+        })
+    }
+}
+
+/// A monster spawn with its enemy type decided.
+#[derive(Debug, Clone)]
+pub struct MonsterSpawn {
     /// Grid position (x, z).
     pub grid_pos: (i32, i32),
     /// Enemy type identifier (matches EnemyRegistry key).
     pub enemy_type: String,
 }
 
+// === Level Exits ===
+
+/// Exit palette entry: where stepping onto the tile takes the player.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExitDef {
+    /// Registry key of the destination level (e.g. "ruined_temple")
+    pub target_level: String,
+    /// Grid position (x, z) the player arrives at
+    pub target_spawn: (i32, i32),
+}
+
+/// An exit resolved from the exit grid.
+#[derive(Debug, Clone)]
+pub struct ResolvedExit {
+    /// Grid position (x, z).
+    pub grid_pos: (i32, i32),
+    pub exit: ExitDef,
+}
+
+// === Trigger Volumes ===
+
+/// Trigger palette entry: the event fired when the player steps onto the tile.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TriggerDef {
+    pub event_id: String,
+    /// Fire only the first time the player enters
+    #[serde(default)]
+    pub once: bool,
+    /// Show a pressure plate on the floor (otherwise the trigger is invisible)
+    #[serde(default)]
+    pub pressure_plate: bool,
+}
+
+/// A trigger resolved from the trigger grid.
+#[derive(Debug, Clone)]
+pub struct ResolvedTrigger {
+    /// Grid position (x, z).
+    pub grid_pos: (i32, i32),
+    pub trigger: TriggerDef,
+}
+
 // === Prefab Types ===
 
 /// The kind of prefab structure.
 #[derive(Debug, Clone, Deserialize)]
 pub enum PrefabKind {
-    StepStairs,   // Cube-step stairs (uses autostep)
+    StepStairs,      // Cube-step stairs (uses autostep)
+    ExplosiveBarrel, // Destructible barrel that explodes (stands at from_elevation)
+    MovingPlatform,  // Lift moving between from_elevation and to_elevation
+    Checkpoint,      // Respawn point that heals the player (rune at from_elevation)
 }
 
 /// A prefab instance definition (from level file).
@@ -243,10 +415,20 @@ pub struct PrefabInstance {
     pub position: (i32, i32),      // Grid position
     #[serde(default)]
     pub rotation: f32,             // Degrees (0, 90, 180, 270)
+    #[serde(default)]
     pub from_elevation: f32,       // Starting Y
-    pub to_elevation: f32,         // Ending Y
+    #[serde(default)]
+    pub to_elevation: f32,         // Ending Y (stairs only)
     #[serde(default)]
     pub length: Option<i32>,       // Tiles long (default: 1)
+    #[serde(default)]
+    pub side_walls: bool,          // Enclose both sides with walls (stairs only)
+    #[serde(default)]
+    pub railings: bool,            // Railings along both edges (stairs only)
+    #[serde(default)]
+    pub period: Option<f32>,       // Seconds for a full round trip (platforms only)
+    #[serde(default)]
+    pub trigger: Option<String>,   // Sends it to its other end (platforms only; None = always moving)
 }
 
 // === Level Definition ===
@@ -267,6 +449,25 @@ fn default_ceiling_thickness() -> f32 {
     0.3
 }
 
+/// Distance below the lowest floor of the default kill plane.
+const KILL_PLANE_MARGIN: f32 = 10.0;
+
+/// An additional floor stacked at its own base elevation.
+///
+/// Uses the same palettes and dimensions as the level's main grids. Tile
+/// elevations, wall heights and ceiling heights are relative to `base_elevation`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FloorLayerRaw {
+    pub base_elevation: f32,
+    pub geometry: Vec<String>,
+    #[serde(default)]
+    pub ambient: Vec<String>,
+    #[serde(default)]
+    pub monsters: Vec<String>,
+    #[serde(default)]
+    pub ceiling: Vec<String>,
+}
+
 /// Raw level definition as read from RON.
 #[derive(Debug, Clone, Deserialize)]
 pub struct LevelDefinitionRaw {
@@ -284,6 +485,12 @@ pub struct LevelDefinitionRaw {
     #[serde(default)]
     pub global_ambient: GlobalAmbientDef,
     pub player_start: (i32, i32),
+    /// Y below which the player is rescued. Defaults to a margin below the lowest floor.
+    #[serde(default)]
+    pub kill_plane: Option<f32>,
+    /// Seed for weighted monster picks. Random each time the level is built if unset.
+    #[serde(default)]
+    pub spawn_seed: Option<u64>,
 
     // External palette file references (optional)
     #[serde(default)]
@@ -295,15 +502,23 @@ pub struct LevelDefinitionRaw {
     #[serde(default)]
     pub ceiling_palette_file: Option<String>,
 
+    // Custom materials, by name (override palette and built-in materials of the same name)
+    #[serde(default)]
+    pub materials: HashMap<String, MaterialDef>,
+
     // Inline palettes (optional - used as fallback if no external file)
     #[serde(default)]
     pub geometry_palette: HashMap<char, GeometryTileDef>,
     #[serde(default)]
     pub ambient_palette: HashMap<char, AmbientTileDef>,
     #[serde(default)]
-    pub monster_palette: HashMap<char, String>,
+    pub monster_palette: HashMap<char, MonsterPaletteEntry>,
     #[serde(default)]
     pub ceiling_palette: HashMap<char, CeilingTileDef>,
+    #[serde(default)]
+    pub exit_palette: HashMap<char, ExitDef>,
+    #[serde(default)]
+    pub trigger_palette: HashMap<char, TriggerDef>,
 
     // Grids
     pub geometry: Vec<String>,
@@ -312,6 +527,16 @@ pub struct LevelDefinitionRaw {
     pub monsters: Vec<String>,
     #[serde(default)]
     pub ceiling: Vec<String>,
+    /// Tiles leading to other levels (ground floor only)
+    #[serde(default)]
+    pub exits: Vec<String>,
+    /// Trigger volumes (ground floor only)
+    #[serde(default)]
+    pub triggers: Vec<String>,
+
+    // Additional stacked floors (basements, upper storeys)
+    #[serde(default)]
+    pub floors: Vec<FloorLayerRaw>,
 
     // Prefabs (stairs, etc.)
     #[serde(default)]
@@ -330,6 +555,8 @@ pub struct ResolvedGeometryTile {
     pub height: f32,
     pub floor_depth: f32,
     pub elevation: f32,  // Y-offset for floor surface (default 0.0)
+    pub trigger: Option<String>,
+    pub damage_trap: Option<DamageTrapDef>,
 }
 
 impl Default for ResolvedGeometryTile {
@@ -340,6 +567,8 @@ impl Default for ResolvedGeometryTile {
             height: 4.0,
             floor_depth: 0.5,
             elevation: 0.0,
+            trigger: None,
+            damage_trap: None,
         }
     }
 }
@@ -352,6 +581,118 @@ pub struct ResolvedAmbientTile {
     pub audio: Vec<AudioDef>,
 }
 
+/// A resolved floor of a level.
+///
+/// All positions on this floor are offset vertically by `base_elevation`.
+#[derive(Debug, Clone)]
+pub struct FloorLayer {
+    pub base_elevation: f32,
+    pub geometry: Vec<Vec<ResolvedGeometryTile>>,
+    pub ambient: Vec<Vec<ResolvedAmbientTile>>,
+    /// Ceiling grid (None = open sky/void).
+    pub ceiling: Vec<Vec<Option<ResolvedCeilingTile>>>,
+    /// Monster spawn points resolved from the monster grid.
+    pub monster_spawns: Vec<ResolvedMonsterSpawn>,
+}
+
+impl FloorLayer {
+    /// Resolve a floor's grids against the level's palettes.
+    fn resolve(
+        base_elevation: f32,
+        grids: FloorGrids,
+        palettes: &ResolvedPalettes,
+        width: usize,
+        defaults: &LevelDefinitionRaw,
+    ) -> Self {
+        let geometry = resolve_geometry_grid(grids.geometry, &palettes.geometry, width, defaults);
+        let ambient = resolve_ambient_grid(grids.ambient, &palettes.ambient, width);
+        let monster_spawns = resolve_monster_spawns(grids.monsters, &palettes.monster);
+        let ceiling = resolve_ceiling_grid(grids.ceiling, &palettes.ceiling, &geometry, width, defaults);
+
+        Self {
+            base_elevation,
+            geometry,
+            ambient,
+            ceiling,
+            monster_spawns,
+        }
+    }
+
+    /// Get geometry tile at grid position. Returns default (Void) if out of bounds.
+    pub fn get_geometry(&self, x: i32, z: i32) -> &ResolvedGeometryTile {
+        static DEFAULT: ResolvedGeometryTile = ResolvedGeometryTile {
+            kind: GeometryKind::Void,
+            material: String::new(),
+            height: 4.0,
+            floor_depth: 0.5,
+            elevation: 0.0,
+            trigger: None,
+            damage_trap: None,
+        };
+
+        grid_cell(&self.geometry, x, z).unwrap_or(&DEFAULT)
+    }
+
+    /// Get ambient tile at grid position. Returns empty ambient if out of bounds.
+    pub fn get_ambient(&self, x: i32, z: i32) -> &ResolvedAmbientTile {
+        static DEFAULT: ResolvedAmbientTile = ResolvedAmbientTile {
+            lights: Vec::new(),
+            particles: Vec::new(),
+            audio: Vec::new(),
+        };
+
+        grid_cell(&self.ambient, x, z).unwrap_or(&DEFAULT)
+    }
+
+    /// Get ceiling tile at grid position. Returns None if out of bounds or open sky.
+    pub fn get_ceiling(&self, x: i32, z: i32) -> Option<&ResolvedCeilingTile> {
+        grid_cell(&self.ceiling, x, z).and_then(|tile| tile.as_ref())
+    }
+
+    /// Grid coordinates of all tiles matching `predicate`, in row-major order.
+    pub fn tiles_where<'a, F>(&'a self, predicate: F) -> impl Iterator<Item = (i32, i32)> + 'a
+    where
+        F: Fn(&ResolvedGeometryTile) -> bool + 'a,
+    {
+        self.geometry
+            .iter()
+            .enumerate()
+            .flat_map(|(z, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(x, tile)| (x as i32, z as i32, tile))
+            })
+            .filter(move |(_, _, tile)| predicate(tile))
+            .map(|(x, z, _)| (x, z))
+    }
+
+    /// Grid coordinates of all tiles with a floor (floors, doorways, pillars).
+    pub fn floor_tiles(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.tiles_where(|tile| tile.kind.has_floor())
+    }
+
+    /// Grid coordinates of all tiles that can be walked through (no pillar or wall).
+    pub fn walkable_tiles(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.tiles_where(|tile| tile.kind.is_walkable())
+    }
+}
+
+/// Look up a cell in a row-major grid, returning None if out of bounds.
+fn grid_cell<T>(grid: &[Vec<T>], x: i32, z: i32) -> Option<&T> {
+    if x < 0 || z < 0 {
+        return None;
+    }
+    grid.get(z as usize).and_then(|row| row.get(x as usize))
+}
+
+/// Borrowed raw grids for one floor.
+struct FloorGrids<'a> {
+    geometry: &'a [String],
+    ambient: &'a [String],
+    monsters: &'a [String],
+    ceiling: &'a [String],
+}
+
 /// Processed level definition with resolved tiles.
 #[derive(Debug, Clone)]
 pub struct LevelDefinition {
@@ -363,14 +704,20 @@ pub struct LevelDefinition {
     pub default_ceiling_thickness: f32,
     pub global_ambient: GlobalAmbientDef,
     pub player_start: (i32, i32),
+    /// Explicit kill plane height, see `kill_plane_y`.
+    pub kill_plane: Option<f32>,
+    /// Seed for weighted monster picks (`None` = random).
+    pub spawn_seed: Option<u64>,
     pub width: usize,
     pub height: usize,
-    pub geometry: Vec<Vec<ResolvedGeometryTile>>,
-    pub ambient: Vec<Vec<ResolvedAmbientTile>>,
-    /// Ceiling grid (None = open sky/void).
-    pub ceiling: Vec<Vec<Option<ResolvedCeilingTile>>>,
-    /// Monster spawn points resolved from the monster grid.
-    pub monster_spawns: Vec<ResolvedMonsterSpawn>,
+    /// Stacked floors. The first is the main grid at elevation 0.0.
+    pub floors: Vec<FloorLayer>,
+    /// Custom materials defined by the level, by name.
+    pub materials: HashMap<String, MaterialDef>,
+    /// Ground-floor exits to other levels.
+    pub exits: Vec<ResolvedExit>,
+    /// Ground-floor trigger volumes.
+    pub triggers: Vec<ResolvedTrigger>,
     /// Prefab instances (stairs, etc.).
     pub prefabs: Vec<PrefabInstance>,
     /// Legacy spawn zones (deprecated - use monster_spawns).
@@ -378,16 +725,16 @@ pub struct LevelDefinition {
 }
 
 /// Intermediate struct holding resolved palettes during level construction.
-struct ResolvedPalettes {
-    geometry: HashMap<char, GeometryTileDef>,
-    ambient: HashMap<char, AmbientTileDef>,
-    monster: HashMap<char, String>,
+pub(super) struct ResolvedPalettes {
+    pub(super) geometry: HashMap<char, GeometryTileDef>,
+    pub(super) ambient: HashMap<char, AmbientTileDef>,
+    pub(super) monster: HashMap<char, MonsterPaletteEntry>,
     ceiling: HashMap<char, CeilingTileDef>,
 }
 
 impl ResolvedPalettes {
     /// Resolve all palettes from raw level and registry.
-    fn resolve(raw: &LevelDefinitionRaw, registry: &PaletteRegistry) -> Self {
+    pub(super) fn resolve(raw: &LevelDefinitionRaw, registry: &PaletteRegistry) -> Self {
         Self {
             geometry: Self::resolve_palette(
                 raw.geometry_palette_file.as_deref(),
@@ -452,6 +799,8 @@ fn resolve_geometry_grid(
                         height: def.height.unwrap_or(defaults.default_wall_height),
                         floor_depth: def.floor_depth.unwrap_or(defaults.default_floor_depth),
                         elevation: def.elevation.unwrap_or(0.0),
+                        trigger: def.trigger.clone(),
+                        damage_trap: def.damage_trap.clone(),
                     }).unwrap_or_default()
                 })
                 .collect();
@@ -492,24 +841,34 @@ fn resolve_ambient_grid(
 /// Resolve monster spawns from the monster grid.
 fn resolve_monster_spawns(
     rows: &[String],
-    palette: &HashMap<char, String>,
+    palette: &HashMap<char, MonsterPaletteEntry>,
 ) -> Vec<ResolvedMonsterSpawn> {
-    let mut spawns = Vec::new();
+    resolve_sparse_grid(rows, palette, "monster")
+        .into_iter()
+        .map(|(grid_pos, entry)| ResolvedMonsterSpawn { grid_pos, entry })
+        .collect()
+}
+
+/// Resolve a mostly empty grid into the positions and palette entries of
+/// its non-empty cells. `grid` names it in warnings.
+fn resolve_sparse_grid<T: Clone>(
+    rows: &[String],
+    palette: &HashMap<char, T>,
+    grid: &str,
+) -> Vec<((i32, i32), T)> {
+    let mut entries = Vec::new();
     for (z, row) in rows.iter().enumerate() {
         for (x, c) in row.chars().enumerate() {
             if c != '.' && c != ' ' {
-                if let Some(enemy_type) = palette.get(&c) {
-                    spawns.push(ResolvedMonsterSpawn {
-                        grid_pos: (x as i32, z as i32),
-                        enemy_type: enemy_type.clone(),
-                    });
+                if let Some(entry) = palette.get(&c) {
+                    entries.push(((x as i32, z as i32), entry.clone()));
                 } else {
-                    warn!("Unknown monster character '{}' at ({}, {})", c, x, z);
+                    warn!("Unknown {} character '{}' at ({}, {})", grid, c, x, z);
                 }
             }
         }
     }
-    spawns
+    entries
 }
 
 /// Resolve ceiling grid from raw strings, or generate defaults from geometry.
@@ -571,57 +930,76 @@ fn resolve_ceiling_grid(
     }
 }
 
-/// Calculate grid dimensions and validate they match.
-fn validate_grid_dimensions(
-    raw: &LevelDefinitionRaw,
-) -> Result<(usize, usize), DataLoadError> {
-    let geo_height = raw.geometry.len();
-    let geo_width = raw.geometry.iter().map(|row| row.chars().count()).max().unwrap_or(0);
-
-    let amb_height = raw.ambient.len();
-    let amb_width = raw.ambient.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+/// Width (longest row) and height of a grid.
+fn grid_size(rows: &[String]) -> (usize, usize) {
+    let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+    (width, rows.len())
+}
 
-    if geo_height != amb_height || geo_width != amb_width {
+/// Check that a grid has the main grid's dimensions. Optional grids may be
+/// left empty instead.
+fn check_grid_size(
+    rows: &[String],
+    optional: bool,
+    (width, height): (usize, usize),
+) -> Result<(), DataLoadError> {
+    if optional && rows.is_empty() {
+        return Ok(());
+    }
+    let (actual_width, actual_height) = grid_size(rows);
+    if actual_width != width || actual_height != height {
         return Err(DataLoadError::GridMismatch {
-            expected_width: geo_width,
-            expected_height: geo_height,
-            actual_width: amb_width,
-            actual_height: amb_height,
+            expected_width: width,
+            expected_height: height,
+            actual_width,
+            actual_height,
         });
     }
+    Ok(())
+}
 
-    // Validate monster grid if present
-    if !raw.monsters.is_empty() {
-        let mon_height = raw.monsters.len();
-        let mon_width = raw.monsters.iter().map(|row| row.chars().count()).max().unwrap_or(0);
-        if mon_height != geo_height || mon_width != geo_width {
-            return Err(DataLoadError::GridMismatch {
-                expected_width: geo_width,
-                expected_height: geo_height,
-                actual_width: mon_width,
-                actual_height: mon_height,
-            });
-        }
+/// Calculate grid dimensions and validate they match.
+fn validate_grid_dimensions(
+    raw: &LevelDefinitionRaw,
+) -> Result<(usize, usize), DataLoadError> {
+    let size = grid_size(&raw.geometry);
+
+    // Ambient is required; the monster, ceiling, exit and trigger grids are
+    // optional
+    check_grid_size(&raw.ambient, false, size)?;
+    for grid in [&raw.monsters, &raw.ceiling, &raw.exits, &raw.triggers] {
+        check_grid_size(grid, true, size)?;
     }
 
-    // Validate ceiling grid if present
-    if !raw.ceiling.is_empty() {
-        let ceil_height = raw.ceiling.len();
-        let ceil_width = raw.ceiling.iter().map(|row| row.chars().count()).max().unwrap_or(0);
-        if ceil_height != geo_height || ceil_width != geo_width {
-            return Err(DataLoadError::GridMismatch {
-                expected_width: geo_width,
-                expected_height: geo_height,
-                actual_width: ceil_width,
-                actual_height: ceil_height,
-            });
+    // Validate stacked floors - every layer shares the main grid's dimensions
+    for floor in &raw.floors {
+        // Geometry is required; the other grids are optional
+        check_grid_size(&floor.geometry, false, size)?;
+        for grid in [&floor.ambient, &floor.monsters, &floor.ceiling] {
+            check_grid_size(grid, true, size)?;
         }
     }
 
-    Ok((geo_width, geo_height))
+    Ok(size)
 }
 
 impl LevelDefinition {
+    /// Parse a level from RON text and resolve it.
+    ///
+    /// `source` names where the text came from (used in error messages), so
+    /// levels can be built from in-memory strings as well as files.
+    pub fn from_ron_str(
+        contents: &str,
+        source: &str,
+        palette_registry: &PaletteRegistry,
+    ) -> Result<Self, DataLoadError> {
+        let raw = ron::from_str::<LevelDefinitionRaw>(contents).map_err(|e| DataLoadError::ParseError {
+            path: source.to_string(),
+            details: e.to_string(),
+        })?;
+        Self::from_raw(raw, palette_registry)
+    }
+
     /// Create from raw definition by resolving palette references.
     /// Uses PaletteRegistry to look up external palette files.
     pub fn from_raw(raw: LevelDefinitionRaw, palette_registry: &PaletteRegistry) -> Result<Self, DataLoadError> {
@@ -631,11 +1009,60 @@ impl LevelDefinition {
         // Resolve all palettes
         let palettes = ResolvedPalettes::resolve(&raw, palette_registry);
 
-        // Resolve grids
-        let geometry = resolve_geometry_grid(&raw.geometry, &palettes.geometry, width, &raw);
-        let ambient = resolve_ambient_grid(&raw.ambient, &palettes.ambient, width);
-        let monster_spawns = resolve_monster_spawns(&raw.monsters, &palettes.monster);
-        let ceiling = resolve_ceiling_grid(&raw.ceiling, &palettes.ceiling, &geometry, width, &raw);
+        // Resolve the main grid as the ground floor, then any stacked floors
+        let mut floors = vec![FloorLayer::resolve(
+            0.0,
+            FloorGrids {
+                geometry: &raw.geometry,
+                ambient: &raw.ambient,
+                monsters: &raw.monsters,
+                ceiling: &raw.ceiling,
+            },
+            &palettes,
+            width,
+            &raw,
+        )];
+        for floor in &raw.floors {
+            floors.push(FloorLayer::resolve(
+                floor.base_elevation,
+                FloorGrids {
+                    geometry: &floor.geometry,
+                    ambient: &floor.ambient,
+                    monsters: &floor.monsters,
+                    ceiling: &floor.ceiling,
+                },
+                &palettes,
+                width,
+                &raw,
+            ));
+        }
+
+        // Materials from the external palettes, overridden by the level's own
+        let mut materials = HashMap::new();
+        if let Some(palette) = raw
+            .geometry_palette_file
+            .as_deref()
+            .and_then(|file| palette_registry.get_geometry(file))
+        {
+            materials.extend(palette.materials.clone());
+        }
+        if let Some(palette) = raw
+            .ceiling_palette_file
+            .as_deref()
+            .and_then(|file| palette_registry.get_ceiling(file))
+        {
+            materials.extend(palette.materials.clone());
+        }
+        materials.extend(raw.materials);
+
+        let exits = resolve_sparse_grid(&raw.exits, &raw.exit_palette, "exit")
+            .into_iter()
+            .map(|(grid_pos, exit)| ResolvedExit { grid_pos, exit })
+            .collect();
+        let triggers = resolve_sparse_grid(&raw.triggers, &raw.trigger_palette, "trigger")
+            .into_iter()
+            .map(|(grid_pos, trigger)| ResolvedTrigger { grid_pos, trigger })
+            .collect();
 
         Ok(Self {
             name: raw.name,
@@ -646,68 +1073,65 @@ impl LevelDefinition {
             default_ceiling_thickness: raw.default_ceiling_thickness,
             global_ambient: raw.global_ambient,
             player_start: raw.player_start,
+            kill_plane: raw.kill_plane,
+            spawn_seed: raw.spawn_seed,
             width,
             height,
-            geometry,
-            ambient,
-            ceiling,
-            monster_spawns,
+            floors,
+            materials,
+            exits,
+            triggers,
             prefabs: raw.prefabs,
             spawn_zones: raw.spawn_zones,
         })
     }
 
-    /// Get geometry tile at grid position. Returns default (Void) if out of bounds.
-    pub fn get_geometry(&self, x: i32, z: i32) -> &ResolvedGeometryTile {
-        static DEFAULT: ResolvedGeometryTile = ResolvedGeometryTile {
-            kind: GeometryKind::Void,
-            material: String::new(),
-            height: 4.0,
-            floor_depth: 0.5,
-            elevation: 0.0,
-        };
+    /// The ground floor (main grid at elevation 0.0).
+    pub fn ground(&self) -> &FloorLayer {
+        &self.floors[0]
+    }
 
-        if x < 0 || z < 0 {
-            return &DEFAULT;
-        }
-        let ux = x as usize;
-        let uz = z as usize;
-        if uz >= self.height || ux >= self.width {
-            return &DEFAULT;
-        }
-        &self.geometry[uz][ux]
+    /// Height below which a falling player is considered out of bounds.
+    pub fn kill_plane_y(&self) -> f32 {
+        self.kill_plane.unwrap_or_else(|| {
+            let lowest = self
+                .floors
+                .iter()
+                .map(|layer| layer.base_elevation)
+                .fold(0.0, f32::min);
+            lowest - self.default_floor_depth - KILL_PLANE_MARGIN
+        })
     }
 
-    /// Get ambient tile at grid position. Returns empty ambient if out of bounds.
-    pub fn get_ambient(&self, x: i32, z: i32) -> &ResolvedAmbientTile {
-        static DEFAULT: ResolvedAmbientTile = ResolvedAmbientTile {
-            lights: Vec::new(),
-            particles: Vec::new(),
-            audio: Vec::new(),
-        };
+    /// Get ground-floor geometry tile at grid position. Returns default (Void) if out of bounds.
+    pub fn get_geometry(&self, x: i32, z: i32) -> &ResolvedGeometryTile {
+        self.ground().get_geometry(x, z)
+    }
 
-        if x < 0 || z < 0 {
-            return &DEFAULT;
-        }
-        let ux = x as usize;
-        let uz = z as usize;
-        if uz >= self.height || ux >= self.width {
-            return &DEFAULT;
-        }
-        &self.ambient[uz][ux]
+    /// Get ground-floor ambient tile at grid position. Returns empty ambient if out of bounds.
+    pub fn get_ambient(&self, x: i32, z: i32) -> &ResolvedAmbientTile {
+        self.ground().get_ambient(x, z)
     }
 
-    /// Get ceiling tile at grid position. Returns None if out of bounds or open sky.
+    /// Get ground-floor ceiling tile at grid position. Returns None if out of bounds or open sky.
     pub fn get_ceiling(&self, x: i32, z: i32) -> Option<&ResolvedCeilingTile> {
-        if x < 0 || z < 0 {
-            return None;
-        }
-        let ux = x as usize;
-        let uz = z as usize;
-        if uz >= self.height || ux >= self.width {
-            return None;
-        }
-        self.ceiling[uz][ux].as_ref()
+        self.ground().get_ceiling(x, z)
+    }
+
+    /// Ground-floor grid coordinates of all tiles with a floor.
+    pub fn floor_tiles(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.ground().floor_tiles()
+    }
+
+    /// Ground-floor grid coordinates of all walkable tiles.
+    pub fn walkable_tiles(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.ground().walkable_tiles()
+    }
+
+    /// Where the player is placed when spawned on a ground-floor tile.
+    pub fn spawn_position(&self, (x, z): (i32, i32)) -> Vec3 {
+        let tile_pos = self.grid_to_world(x, z);
+        Vec3::new(tile_pos.x, self.get_geometry(x, z).elevation + 1.0, tile_pos.z)
     }
 
     /// Convert grid coordinates to world position (center of tile).
@@ -718,12 +1142,22 @@ impl LevelDefinition {
             z as f32 * self.tile_size + self.tile_size / 2.0,
         )
     }
+
+    /// Convert a world position to the grid coordinates of the tile containing it.
+    pub fn world_to_grid(&self, position: Vec3) -> (i32, i32) {
+        (
+            (position.x / self.tile_size).floor() as i32,
+            (position.z / self.tile_size).floor() as i32,
+        )
+    }
 }
 
 /// Resource storing all loaded level definitions.
 #[derive(Resource, Default)]
 pub struct LevelRegistry {
     pub levels: HashMap<String, LevelDefinition>,
+    /// Levels whose files failed to load, with the error
+    pub failed: HashMap<String, String>,
 }
 
 impl LevelRegistry {
@@ -731,6 +1165,24 @@ impl LevelRegistry {
     pub fn get(&self, name: &str) -> Option<&LevelDefinition> {
         self.levels.get(name)
     }
+
+    /// Explain why a level isn't in the registry.
+    pub fn missing_reason(&self, name: &str) -> String {
+        if let Some(error) = self.failed.get(name) {
+            format!("its file failed to load: {}", error)
+        } else if self.levels.is_empty() {
+            "no levels were loaded from assets/data/levels".to_string()
+        } else {
+            format!("no such level (available: {})", self.names().join(", "))
+        }
+    }
+
+    /// Registry keys of all loaded levels, sorted alphabetically.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.levels.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
 }
 
 /// Resource indicating which level to load.
@@ -747,84 +1199,114 @@ impl Default for CurrentLevel {
     }
 }
 
-/// Load all external palette files from assets/data/palettes/.
-pub fn load_palette_files(mut commands: Commands) {
-    let mut registry = PaletteRegistry::default();
-    let palettes_path = Path::new("assets/data/palettes");
+/// Directory holding external palette files.
+pub const PALETTES_DIR: &str = "assets/data/palettes";
 
-    if palettes_path.exists() {
-        if let Ok(entries) = fs::read_dir(palettes_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().is_some_and(|ext| ext == "ron") {
-                    let filename = path.file_name()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("")
-                        .to_string();
-
-                    if let Ok(contents) = fs::read_to_string(&path) {
-                        // Try to determine palette type by filename convention first
-                        let is_ceiling = filename.contains("ceiling");
-                        let is_geometry = filename.contains("geometry");
-                        let is_ambient = filename.contains("ambient");
-                        let is_monster = filename.contains("monster");
-
-                        if is_ceiling {
-                            if let Ok(ceil_palette) = ron::from_str::<CeilingPaletteFile>(&contents) {
-                                info!("Loaded ceiling palette: {}", filename);
-                                registry.ceiling.insert(filename.clone(), ceil_palette);
-                            } else {
-                                warn!("Failed to parse ceiling palette {:?}", path);
-                            }
-                        } else if is_geometry {
-                            if let Ok(geo_palette) = ron::from_str::<GeometryPaletteFile>(&contents) {
-                                info!("Loaded geometry palette: {}", filename);
-                                registry.geometry.insert(filename.clone(), geo_palette);
-                            } else {
-                                warn!("Failed to parse geometry palette {:?}", path);
-                            }
-                        } else if is_ambient {
-                            if let Ok(amb_palette) = ron::from_str::<AmbientPaletteFile>(&contents) {
-                                info!("Loaded ambient palette: {}", filename);
-                                registry.ambient.insert(filename.clone(), amb_palette);
-                            } else {
-                                warn!("Failed to parse ambient palette {:?}", path);
-                            }
-                        } else if is_monster {
-                            if let Ok(mon_palette) = ron::from_str::<MonsterPaletteFile>(&contents) {
-                                info!("Loaded monster palette: {}", filename);
-                                registry.monster.insert(filename.clone(), mon_palette);
-                            } else {
-                                warn!("Failed to parse monster palette {:?}", path);
-                            }
-                        } else {
-                            // Fallback: try each format in order
-                            if let Ok(geo_palette) = ron::from_str::<GeometryPaletteFile>(&contents) {
-                                info!("Loaded geometry palette: {}", filename);
-                                registry.geometry.insert(filename.clone(), geo_palette);
-                            } else if let Ok(mon_palette) = ron::from_str::<MonsterPaletteFile>(&contents) {
-                                info!("Loaded monster palette: {}", filename);
-                                registry.monster.insert(filename.clone(), mon_palette);
-                            } else if let Ok(ceil_palette) = ron::from_str::<CeilingPaletteFile>(&contents) {
-                                info!("Loaded ceiling palette: {}", filename);
-                                registry.ceiling.insert(filename.clone(), ceil_palette);
-                            } else if let Ok(amb_palette) = ron::from_str::<AmbientPaletteFile>(&contents) {
-                                info!("Loaded ambient palette: {}", filename);
-                                registry.ambient.insert(filename.clone(), amb_palette);
-                            } else {
-                                warn!("Unknown palette format in {:?}", path);
-                            }
-                        }
-                    } else {
-                        error!("Failed to read palette file {:?}", path);
-                    }
-                }
+/// Directory holding level files.
+pub const LEVELS_DIR: &str = "assets/data/levels";
+
+/// Registry key of a level file: its name without the `.level.ron` suffix.
+pub fn level_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_string_lossy();
+    Some(stem.strip_suffix(".level").unwrap_or(&stem).to_string())
+}
+
+/// Parse one palette file into the registry, picking its type by filename.
+///
+/// Files whose name doesn't say what they hold are tried as each type in turn.
+fn read_palette_file(
+    registry: &mut PaletteRegistry,
+    path: &Path,
+    filename: String,
+) -> Result<(), DataLoadError> {
+    let contents = fs::read_to_string(path).map_err(|e| DataLoadError::ReadError {
+        path: path.to_string_lossy().to_string(),
+        details: e.to_string(),
+    })?;
+    let parse_error = |details: String| DataLoadError::ParseError {
+        path: path.to_string_lossy().to_string(),
+        details,
+    };
+
+    if filename.contains("ceiling") {
+        let palette = ron::from_str::<CeilingPaletteFile>(&contents)
+            .map_err(|e| parse_error(e.to_string()))?;
+        info!("Loaded ceiling palette: {}", filename);
+        registry.ceiling.insert(filename, palette);
+    } else if filename.contains("geometry") {
+        let palette = ron::from_str::<GeometryPaletteFile>(&contents)
+            .map_err(|e| parse_error(e.to_string()))?;
+        info!("Loaded geometry palette: {}", filename);
+        registry.geometry.insert(filename, palette);
+    } else if filename.contains("ambient") {
+        let palette = ron::from_str::<AmbientPaletteFile>(&contents)
+            .map_err(|e| parse_error(e.to_string()))?;
+        info!("Loaded ambient palette: {}", filename);
+        registry.ambient.insert(filename, palette);
+    } else if filename.contains("monster") {
+        let palette = ron::from_str::<MonsterPaletteFile>(&contents)
+            .map_err(|e| parse_error(e.to_string()))?;
+        info!("Loaded monster palette: {}", filename);
+        registry.monster.insert(filename, palette);
+    } else if let Ok(palette) = ron::from_str::<GeometryPaletteFile>(&contents) {
+        info!("Loaded geometry palette: {}", filename);
+        registry.geometry.insert(filename, palette);
+    } else if let Ok(palette) = ron::from_str::<MonsterPaletteFile>(&contents) {
+        info!("Loaded monster palette: {}", filename);
+        registry.monster.insert(filename, palette);
+    } else if let Ok(palette) = ron::from_str::<CeilingPaletteFile>(&contents) {
+        info!("Loaded ceiling palette: {}", filename);
+        registry.ceiling.insert(filename, palette);
+    } else if let Ok(palette) = ron::from_str::<AmbientPaletteFile>(&contents) {
+        info!("Loaded ambient palette: {}", filename);
+        registry.ambient.insert(filename, palette);
+    } else {
+        return Err(parse_error("unknown palette format".to_string()));
+    }
+    Ok(())
+}
+
+/// Read every palette file in `dir`.
+///
+/// Returns the palettes that loaded along with an error for each file that
+/// didn't, so callers can log them or report them.
+pub fn read_palette_files(dir: &Path) -> (PaletteRegistry, Vec<DataLoadError>) {
+    let mut registry = PaletteRegistry::default();
+    let mut errors = Vec::new();
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (registry, errors);
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "ron") {
+            let filename = path.file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("")
+                .to_string();
+
+            if let Err(e) = read_palette_file(&mut registry, &path, filename) {
+                errors.push(e);
             }
         }
-    } else {
+    }
+
+    (registry, errors)
+}
+
+/// Load all external palette files from assets/data/palettes/.
+pub fn load_palette_files(mut commands: Commands) {
+    let palettes_path = Path::new(PALETTES_DIR);
+    if !palettes_path.exists() {
         info!("Palettes directory not found, using inline palettes only");
     }
 
+    let (registry, errors) = read_palette_files(palettes_path);
+    for e in errors {
+        warn!("Skipping palette: {}", e);
+    }
+
     info!(
         "Loaded {} geometry, {} ambient, {} monster, {} ceiling palettes",
         registry.geometry.len(),
@@ -839,34 +1321,32 @@ pub fn load_palette_files(mut commands: Commands) {
 pub fn load_level_definitions(mut commands: Commands, palette_registry: Res<PaletteRegistry>) {
     let mut registry = LevelRegistry::default();
 
-    let levels_path = Path::new("assets/data/levels");
+    let levels_path = Path::new(LEVELS_DIR);
 
     if levels_path.exists() {
         if let Ok(entries) = fs::read_dir(levels_path) {
             for entry in entries.flatten() {
                 let path = entry.path();
                 if path.extension().is_some_and(|ext| ext == "ron") {
-                    if let Some(stem) = path.file_stem() {
-                        let name = stem.to_string_lossy();
-                        let level_name = name.strip_suffix(".level").unwrap_or(&name).to_string();
-
+                    if let Some(level_name) = level_name(&path) {
                         match fs::read_to_string(&path) {
-                            Ok(contents) => match ron::from_str::<LevelDefinitionRaw>(&contents) {
-                                Ok(raw) => match LevelDefinition::from_raw(raw, &palette_registry) {
-                                    Ok(level) => {
-                                        info!("Loaded level: {}", level_name);
-                                        registry.levels.insert(level_name, level);
-                                    }
-                                    Err(e) => {
-                                        error!("Failed to process level {:?}: {}", path, e);
-                                    }
-                                },
+                            Ok(contents) => match LevelDefinition::from_ron_str(
+                                &contents,
+                                &path.to_string_lossy(),
+                                &palette_registry,
+                            ) {
+                                Ok(level) => {
+                                    info!("Loaded level: {}", level_name);
+                                    registry.levels.insert(level_name, level);
+                                }
                                 Err(e) => {
-                                    error!("Failed to parse level {:?}: {}", path, e);
+                                    error!("Failed to load level {:?}: {}", path, e);
+                                    registry.failed.insert(level_name, e.to_string());
                                 }
                             },
                             Err(e) => {
                                 error!("Failed to read level file {:?}: {}", path, e);
+                                registry.failed.insert(level_name, e.to_string());
                             }
                         }
                     }
@@ -881,3 +1361,66 @@ pub fn load_level_definitions(mut commands: Commands, palette_registry: Res<Pale
     commands.insert_resource(registry);
     commands.insert_resource(CurrentLevel::default());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walls around floor, pillar, doorway, door and void tiles.
+    const FIXTURE: &str = r######"(
+        name: "Fixture",
+        player_start: (1, 1),
+        geometry_palette: {
+            '#': (kind: Wall),
+            '.': (kind: Floor),
+            'P': (kind: Pillar),
+            '+': (kind: Doorway),
+            'D': (kind: Door),
+            ' ': (kind: Void),
+        },
+        geometry: [
+            "#####",
+            "#.P.#",
+            "#+D #",
+            "#####",
+        ],
+        ambient: [
+            ".....",
+            ".....",
+            ".....",
+            ".....",
+        ],
+    )"######;
+
+    fn fixture() -> LevelDefinition {
+        LevelDefinition::from_ron_str(FIXTURE, "fixture", &PaletteRegistry::default()).unwrap()
+    }
+
+    #[test]
+    fn floor_tiles_include_everything_with_a_floor() {
+        let tiles: Vec<_> = fixture().floor_tiles().collect();
+        assert_eq!(tiles, vec![(1, 1), (2, 1), (3, 1), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn walkable_tiles_skip_pillars_and_doors() {
+        let tiles: Vec<_> = fixture().walkable_tiles().collect();
+        assert_eq!(tiles, vec![(1, 1), (3, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn tiles_where_matches_predicate_in_row_major_order() {
+        let level = fixture();
+
+        let walls: Vec<_> = level.ground().tiles_where(|tile| tile.kind.is_solid()).collect();
+        assert_eq!(walls.len(), 14);
+        assert_eq!(walls.first(), Some(&(0, 0)));
+        assert_eq!(walls.last(), Some(&(4, 3)));
+
+        let void: Vec<_> = level
+            .ground()
+            .tiles_where(|tile| tile.kind == GeometryKind::Void)
+            .collect();
+        assert_eq!(void, vec![(3, 2)]);
+    }
+}
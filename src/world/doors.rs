@@ -0,0 +1,193 @@
+//! Hinged doors placed by `Door` tiles in the geometry palette.
+//!
+//! A door is three entities: the root at the center of the closed panel
+//! holds the `Door` state and is the `Interactable`, a hinge child at one
+//! edge of the tile swings via `SmoothTransform`, and the panel under the
+//! hinge has the mesh and collider. The panel's collider is disabled while
+//! the door is open, so closed doors block the player, enemies and their
+//! line-of-sight ray casts alike.
+//!
+//! Door tiles with a `trigger` aren't interactable; they open and close
+//! whenever a `TriggerEvent` with that id fires instead.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+use super::builder::LevelGeometry;
+use super::data::{FloorLayer, ResolvedGeometryTile};
+use super::interaction::{InteractEvent, Interactable};
+use super::materials::MaterialRegistry;
+use super::triggers::TriggerEvent;
+use crate::core::{GameState, SmoothTransform};
+
+/// Thickness of the door panel.
+const DOOR_THICKNESS: f32 = 0.15;
+
+/// Distance from the camera within which a door can be opened.
+const DOOR_INTERACT_RANGE: f32 = 3.0;
+
+/// How fast the hinge swings toward its target rotation.
+const DOOR_SWING_SPEED: f32 = 6.0;
+
+/// Material of door panels.
+const DOOR_MATERIAL: &str = "wood";
+
+/// A door opened and closed by interacting with it or by a trigger.
+#[derive(Component)]
+pub struct Door {
+    pub open: bool,
+    /// Child entity the panel swings around
+    pub hinge: Entity,
+    /// Grandchild entity with the panel's mesh and collider
+    pub panel: Entity,
+    /// Trigger event id that toggles the door (None = opened by interacting)
+    pub trigger: Option<String>,
+}
+
+/// Marker for the collider of a door panel.
+#[derive(Component)]
+pub struct DoorPanel;
+
+/// Set up door systems.
+pub fn setup_door_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (toggle_doors, toggle_triggered_doors).run_if(in_state(GameState::InGame)),
+    );
+}
+
+/// Spawn a closed door on the tile at grid position (x, z).
+///
+/// The panel spans the tile between its solid neighbors: across X when the
+/// tiles to the east and west are walls, across Z otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_door(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    mat_registry: &MaterialRegistry,
+    layer: &FloorLayer,
+    x: i32,
+    z: i32,
+    world_pos: Vec3,
+    tile_size: f32,
+    geo_tile: &ResolvedGeometryTile,
+) {
+    let spans_x = layer.get_geometry(x - 1, z).kind.is_solid()
+        && layer.get_geometry(x + 1, z).kind.is_solid();
+    let rotation = if spans_x {
+        Quat::IDENTITY
+    } else {
+        Quat::from_rotation_y(FRAC_PI_2)
+    };
+    let height = geo_tile.height;
+    let center = world_pos + Vec3::Y * (geo_tile.elevation + height / 2.0);
+
+    let panel = commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::new(tile_size, height, DOOR_THICKNESS))),
+            MeshMaterial3d(mat_registry.get_wall(DOOR_MATERIAL)),
+            Transform::from_xyz(tile_size / 2.0, 0.0, 0.0),
+            Collider::cuboid(tile_size / 2.0, height / 2.0, DOOR_THICKNESS / 2.0),
+            DoorPanel,
+        ))
+        .id();
+
+    let hinge = commands
+        .spawn((
+            Transform::from_xyz(-tile_size / 2.0, 0.0, 0.0),
+            Visibility::default(),
+            SmoothTransform::new(0.0, DOOR_SWING_SPEED),
+        ))
+        .add_child(panel)
+        .id();
+
+    let mut door = commands.spawn((
+        Door {
+            open: false,
+            hinge,
+            panel,
+            trigger: geo_tile.trigger.clone(),
+        },
+        Transform::from_translation(center).with_rotation(rotation),
+        Visibility::default(),
+        LevelGeometry,
+    ));
+    if geo_tile.trigger.is_none() {
+        door.insert(Interactable::new("open", DOOR_INTERACT_RANGE));
+    }
+    door.add_child(hinge);
+}
+
+/// Swing a door open or shut. `open_angle` is the hinge's rotation about Y
+/// when open; its sign picks the side the door swings to.
+fn set_door_open(
+    commands: &mut Commands,
+    door: &mut Door,
+    hinge_query: &mut Query<&mut SmoothTransform>,
+    open: bool,
+    open_angle: f32,
+) {
+    door.open = open;
+
+    if let Ok(mut smooth) = hinge_query.get_mut(door.hinge) {
+        let angle = if open { open_angle } else { 0.0 };
+        smooth.target_rotation = Some(Quat::from_rotation_y(angle));
+    }
+
+    if open {
+        commands.entity(door.panel).insert(ColliderDisabled);
+    } else {
+        commands.entity(door.panel).remove::<ColliderDisabled>();
+    }
+}
+
+/// Open or close doors the player interacts with.
+///
+/// Doors swing away from whoever opens them.
+fn toggle_doors(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    mut door_query: Query<(&mut Door, &mut Interactable, &GlobalTransform)>,
+    mut hinge_query: Query<&mut SmoothTransform>,
+    interactor_query: Query<&GlobalTransform, Without<Door>>,
+) {
+    for event in interact_events.read() {
+        let Ok((mut door, mut interactable, door_transform)) = door_query.get_mut(event.target)
+        else {
+            continue;
+        };
+
+        // The closed panel's normal is the root's local Z
+        let normal = door_transform.back().as_vec3();
+        let side = interactor_query
+            .get(event.interactor)
+            .map_or(1.0, |transform| {
+                (transform.translation() - door_transform.translation()).dot(normal)
+            });
+        // Positive rotation about Y swings the panel toward -Z
+        let angle = if side >= 0.0 { FRAC_PI_2 } else { -FRAC_PI_2 };
+
+        let open = !door.open;
+        set_door_open(&mut commands, &mut door, &mut hinge_query, open, angle);
+        interactable.prompt = if open { "close" } else { "open" }.to_string();
+    }
+}
+
+/// Open or close doors wired to a trigger when it fires.
+fn toggle_triggered_doors(
+    mut commands: Commands,
+    mut trigger_events: EventReader<TriggerEvent>,
+    mut door_query: Query<&mut Door, Without<Interactable>>,
+    mut hinge_query: Query<&mut SmoothTransform>,
+) {
+    for event in trigger_events.read() {
+        for mut door in door_query.iter_mut() {
+            if door.trigger.as_deref() != Some(event.id.as_str()) {
+                continue;
+            }
+            let open = !door.open;
+            set_door_open(&mut commands, &mut door, &mut hinge_query, open, FRAC_PI_2);
+        }
+    }
+}
@@ -27,6 +27,37 @@ pub enum DataLoadError {
     },
 
     /// Invalid palette reference.
-    #[error("Unknown palette entry '{character}' at position ({x}, {z})")]
-    UnknownPaletteEntry { character: char, x: usize, z: usize },
+    #[error("Unknown {grid} palette entry '{character}' at position ({x}, {z})")]
+    UnknownPaletteEntry {
+        grid: String,
+        character: char,
+        x: usize,
+        z: usize,
+    },
+
+    /// A level names an external palette file that isn't loaded.
+    #[error("{kind} palette file '{file}' not found")]
+    MissingPaletteFile { kind: String, file: String },
+
+    /// A level spawns an enemy type with no enemy definition.
+    #[error("Unknown enemy type '{enemy_type}' at position ({x}, {z})")]
+    UnknownEnemyType { enemy_type: String, x: i32, z: i32 },
+
+    /// An exit leads to a level that doesn't exist.
+    #[error("Exit at ({x}, {z}) leads to unknown level '{target_level}'")]
+    UnknownExitTarget {
+        target_level: String,
+        x: i32,
+        z: i32,
+    },
+
+    /// An exit's arrival tile can't be stood on in the target level.
+    #[error("Exit at ({x}, {z}) arrives at ({spawn_x}, {spawn_z}) in '{target_level}', which isn't walkable")]
+    ExitSpawnNotWalkable {
+        target_level: String,
+        spawn_x: i32,
+        spawn_z: i32,
+        x: i32,
+        z: i32,
+    },
 }
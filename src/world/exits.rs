@@ -0,0 +1,142 @@
+//! Exit tiles that take the player to another level.
+//!
+//! Exits come from a level's exit grid and palette. Walking onto one queues
+//! a `LevelTransition`, which despawns the current level's entities, builds
+//! the target level and moves the player to the exit's target spawn.
+//! Everything spawned into the level - enemies, pickups, thrown items and
+//! trap projectiles included - is `LevelGeometry`; particle bursts in flight
+//! are cleared along with it. The player entity itself is kept, so health,
+//! stamina, weapon and inventory carry over between levels.
+
+use bevy::prelude::*;
+
+use super::builder::LevelGeometry;
+use super::data::{CurrentLevel, LevelDefinition, ResolvedExit};
+use super::geometry::is_on_tile;
+use super::plugin::LevelBuilder;
+use crate::combat::Knockback;
+use crate::core::{GameState, PlayState};
+use crate::player::{MovementState, Player};
+use crate::rendering::Particle;
+
+/// Height above an exit's floor within which the player triggers it.
+const EXIT_TRIGGER_HEIGHT: f32 = 2.5;
+
+/// A tile that sends the player to another level.
+#[derive(Component)]
+pub struct ExitTrigger {
+    /// Registry key of the destination level
+    pub target_level: String,
+    /// Grid position (x, z) the player arrives at
+    pub target_spawn: (i32, i32),
+    /// Half the tile size, the horizontal extent of the trigger
+    pub half_extent: f32,
+    /// Whether the player has been off the tile since it spawned, so arriving
+    /// on an exit doesn't immediately send the player back
+    armed: bool,
+}
+
+/// A pending move to another level, applied on the next update.
+#[derive(Resource, Clone, Debug)]
+pub struct LevelTransition {
+    pub target_level: String,
+    pub target_spawn: (i32, i32),
+}
+
+/// Set up level exit systems.
+pub fn setup_exit_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            detect_level_exits.run_if(in_state(PlayState::Exploring)),
+            run_level_transition.run_if(resource_exists::<LevelTransition>),
+        )
+            .chain()
+            .run_if(in_state(GameState::InGame)),
+    );
+}
+
+/// Spawn the trigger for an exit on the level's ground floor.
+pub fn spawn_exit_trigger(commands: &mut Commands, level: &LevelDefinition, exit: &ResolvedExit) {
+    let (x, z) = exit.grid_pos;
+    let floor_pos = level.grid_to_world(x, z) + Vec3::Y * level.get_geometry(x, z).elevation;
+
+    commands.spawn((
+        ExitTrigger {
+            target_level: exit.exit.target_level.clone(),
+            target_spawn: exit.exit.target_spawn,
+            half_extent: level.tile_size / 2.0,
+            armed: false,
+        },
+        Transform::from_translation(floor_pos),
+        LevelGeometry,
+    ));
+}
+
+/// Queue a transition when the player steps onto an armed exit.
+fn detect_level_exits(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    mut exit_query: Query<(&mut ExitTrigger, &Transform), Without<Player>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation;
+
+    for (mut exit, transform) in exit_query.iter_mut() {
+        let offset = player_pos - transform.translation;
+        let inside = is_on_tile(offset, exit.half_extent, EXIT_TRIGGER_HEIGHT);
+
+        if !inside {
+            exit.armed = true;
+            continue;
+        }
+        if exit.armed {
+            info!(
+                "Player reached exit to '{}' at {:?}",
+                exit.target_level, exit.target_spawn
+            );
+            commands.insert_resource(LevelTransition {
+                target_level: exit.target_level.clone(),
+                target_spawn: exit.target_spawn,
+            });
+            return;
+        }
+    }
+}
+
+/// Everything torn down with the level being left.
+type LevelContent = Or<(With<LevelGeometry>, With<Particle>)>;
+
+/// Replace the current level with the transition's target and move the
+/// player to its target spawn.
+fn run_level_transition(
+    mut commands: Commands,
+    transition: Res<LevelTransition>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut builder: LevelBuilder,
+    level_query: Query<Entity, LevelContent>,
+    mut player_query: Query<
+        (&mut Transform, &mut MovementState, Option<&mut Knockback>),
+        With<Player>,
+    >,
+) {
+    commands.remove_resource::<LevelTransition>();
+
+    for entity in level_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    current_level.name = transition.target_level.clone();
+    let arrival = builder.build(&mut commands, &current_level.name, Some(transition.target_spawn));
+
+    let Ok((mut transform, mut movement, knockback)) = player_query.get_single_mut() else {
+        return;
+    };
+    transform.translation = arrival;
+    movement.vertical_velocity = 0.0;
+    if let Some(mut knockback) = knockback {
+        knockback.velocity = Vec3::ZERO;
+    }
+}
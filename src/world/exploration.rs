@@ -0,0 +1,106 @@
+//! Explored tiles of the current level, for the minimap.
+//!
+//! Every frame the tiles around the player's ground-floor grid position are
+//! marked as explored. The grid is sized to the current level and starts
+//! over whenever a different level is loaded, either through an exit or a
+//! new game session.
+
+use bevy::prelude::*;
+
+use super::data::{CurrentLevel, LevelRegistry};
+use crate::core::{GameSession, GameState};
+use crate::player::Player;
+
+/// Tiles this far from the player's tile (in each direction) are revealed.
+const REVEAL_RADIUS: i32 = 1;
+
+/// Which ground-floor tiles of the current level the player has seen.
+#[derive(Resource, Default, Debug)]
+pub struct ExploredTiles {
+    /// Registry key of the level the grid belongs to
+    pub level: String,
+    pub width: usize,
+    pub height: usize,
+    /// Row-major explored flags, `width * height` long
+    tiles: Vec<bool>,
+}
+
+impl ExploredTiles {
+    /// Empty grid for a level of the given size.
+    fn new(level: &str, width: usize, height: usize) -> Self {
+        Self {
+            level: level.to_string(),
+            width,
+            height,
+            tiles: vec![false; width * height],
+        }
+    }
+
+    /// Index of a tile in the grid, if it is in bounds.
+    fn index(&self, x: i32, z: i32) -> Option<usize> {
+        if x < 0 || z < 0 || x as usize >= self.width || z as usize >= self.height {
+            return None;
+        }
+        Some(z as usize * self.width + x as usize)
+    }
+
+    /// Whether the tile at grid position has been explored. Out of bounds
+    /// tiles never are.
+    pub fn is_explored(&self, x: i32, z: i32) -> bool {
+        self.index(x, z).is_some_and(|index| self.tiles[index])
+    }
+
+    /// Mark the tile at grid position as explored.
+    fn reveal(&mut self, x: i32, z: i32) {
+        if let Some(index) = self.index(x, z) {
+            self.tiles[index] = true;
+        }
+    }
+}
+
+/// Set up explored tile tracking systems.
+pub fn setup_exploration_systems(app: &mut App) {
+    app.init_resource::<ExploredTiles>()
+        .add_systems(
+            Update,
+            update_explored_tiles.run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(OnExit(GameSession), reset_explored_tiles);
+}
+
+/// Reveal the tiles around the player, starting a fresh grid when the
+/// level changed.
+fn update_explored_tiles(
+    mut explored: ResMut<ExploredTiles>,
+    current_level: Res<CurrentLevel>,
+    registry: Res<LevelRegistry>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Some(level) = registry.get(&current_level.name) else {
+        return;
+    };
+    let Ok(transform) = player_query.get_single() else {
+        return;
+    };
+
+    if explored.level != current_level.name
+        || explored.width != level.width
+        || explored.height != level.height
+    {
+        *explored = ExploredTiles::new(&current_level.name, level.width, level.height);
+    }
+
+    let (x, z) = level.world_to_grid(transform.translation);
+    for dz in -REVEAL_RADIUS..=REVEAL_RADIUS {
+        for dx in -REVEAL_RADIUS..=REVEAL_RADIUS {
+            if !explored.is_explored(x + dx, z + dz) {
+                explored.reveal(x + dx, z + dz);
+            }
+        }
+    }
+}
+
+/// Forget the explored tiles when the game session ends.
+fn reset_explored_tiles(mut explored: ResMut<ExploredTiles>) {
+    *explored = ExploredTiles::default();
+}
@@ -0,0 +1,108 @@
+//! Safe-room fallback for levels that fail to load.
+//!
+//! If the requested level isn't in the `LevelRegistry` (missing file, parse
+//! error, or no levels at all), a small lit box is built instead so the game
+//! still has a floor and a player, and a warning is shown on screen.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::builder::LevelGeometry;
+
+/// Inner width and depth of the safe room.
+const ROOM_SIZE: f32 = 8.0;
+
+/// Inner height of the safe room.
+const ROOM_HEIGHT: f32 = 4.0;
+
+/// Thickness of the room's floor, walls and ceiling.
+const WALL_THICKNESS: f32 = 0.5;
+
+/// How far below the floor the player is rescued.
+const KILL_PLANE_DEPTH: f32 = 10.0;
+
+/// Build the safe room and show a warning about the level that failed.
+///
+/// Returns the player spawn position and the kill plane height.
+pub fn spawn_safe_room(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    level_name: &str,
+) -> (Vec3, f32) {
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.35, 0.33, 0.3),
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+
+    let half = ROOM_SIZE / 2.0 + WALL_THICKNESS / 2.0;
+    let outer = ROOM_SIZE + WALL_THICKNESS * 2.0;
+    let slabs = [
+        // Floor and ceiling
+        (Vec3::new(0.0, -WALL_THICKNESS / 2.0, 0.0), Vec3::new(outer, WALL_THICKNESS, outer)),
+        (
+            Vec3::new(0.0, ROOM_HEIGHT + WALL_THICKNESS / 2.0, 0.0),
+            Vec3::new(outer, WALL_THICKNESS, outer),
+        ),
+        // Walls
+        (
+            Vec3::new(half, ROOM_HEIGHT / 2.0, 0.0),
+            Vec3::new(WALL_THICKNESS, ROOM_HEIGHT, outer),
+        ),
+        (
+            Vec3::new(-half, ROOM_HEIGHT / 2.0, 0.0),
+            Vec3::new(WALL_THICKNESS, ROOM_HEIGHT, outer),
+        ),
+        (
+            Vec3::new(0.0, ROOM_HEIGHT / 2.0, half),
+            Vec3::new(outer, ROOM_HEIGHT, WALL_THICKNESS),
+        ),
+        (
+            Vec3::new(0.0, ROOM_HEIGHT / 2.0, -half),
+            Vec3::new(outer, ROOM_HEIGHT, WALL_THICKNESS),
+        ),
+    ];
+
+    for (position, size) in slabs {
+        commands.spawn((
+            Mesh3d(meshes.add(Cuboid::from_size(size))),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(position),
+            Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            LevelGeometry,
+        ));
+    }
+
+    commands.spawn((
+        PointLight {
+            intensity: 200000.0,
+            range: ROOM_SIZE * 2.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, ROOM_HEIGHT - 0.5, 0.0),
+        LevelGeometry,
+    ));
+
+    // Warning banner, cleaned up with the level
+    commands.spawn((
+        Text::new(format!(
+            "Level '{}' could not be loaded. See the log for details.",
+            level_name
+        )),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.3, 0.2)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            left: Val::Px(20.0),
+            ..default()
+        },
+        LevelGeometry,
+    ));
+
+    (Vec3::new(0.0, 1.0, 0.0), -KILL_PLANE_DEPTH)
+}
@@ -4,10 +4,13 @@ use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
 use super::builder::LevelGeometry;
-use super::data::{GeometryKind, LevelDefinition, ResolvedCeilingTile, ResolvedGeometryTile};
+use super::data::{FloorLayer, GeometryKind, ResolvedCeilingTile, ResolvedGeometryTile};
 use super::materials::MaterialRegistry;
 
 /// Spawn a floor tile (without ceiling - ceiling is handled separately).
+///
+/// `world_pos.y` is the floor layer's base elevation; all spawn functions
+/// in this module offset their geometry by it.
 pub fn spawn_floor_tile(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
@@ -18,7 +21,7 @@ pub fn spawn_floor_tile(
 ) {
     let floor_material = mat_registry.get_floor(&geo_tile.material);
     let floor_depth = geo_tile.floor_depth;
-    let floor_y = geo_tile.elevation;
+    let floor_y = world_pos.y + geo_tile.elevation;
 
     // Floor as a box extending downward from elevation
     // Top surface at y=elevation, bottom at y=elevation-floor_depth
@@ -48,7 +51,7 @@ pub fn spawn_ceiling_tile(
         MeshMaterial3d(mat_registry.get_ceiling(&ceiling_tile.material)),
         Transform::from_xyz(
             world_pos.x,
-            ceiling_tile.height + ceiling_tile.thickness / 2.0,
+            world_pos.y + ceiling_tile.height + ceiling_tile.thickness / 2.0,
             world_pos.z,
         ),
         LevelGeometry,
@@ -60,15 +63,19 @@ pub fn spawn_walls_for_tile(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     mat_registry: &MaterialRegistry,
-    level: &LevelDefinition,
+    layer: &FloorLayer,
     x: i32,
     z: i32,
     world_pos: Vec3,
     tile_size: f32,
     wall_thickness: f32,
 ) {
-    let current_tile = level.get_geometry(x, z);
-    let wall_height = current_tile.height;
+    let current_tile = layer.get_geometry(x, z);
+    // Walls reach from the layer base (or a sunken floor) to `height` above
+    // the tile's own floor, so raised tiles keep their full wall height
+    let wall_bottom = current_tile.elevation.min(0.0);
+    let wall_height = current_tile.elevation + current_tile.height - wall_bottom;
+    let wall_y = world_pos.y + wall_bottom + wall_height / 2.0;
     let wall_material = mat_registry.get_wall(&current_tile.material);
     let half_tile = tile_size / 2.0;
 
@@ -77,39 +84,102 @@ pub fn spawn_walls_for_tile(
         // North (z - 1)
         (
             (0, -1),
-            Vec3::new(world_pos.x, wall_height / 2.0, world_pos.z - half_tile),
+            Vec3::new(world_pos.x, wall_y, world_pos.z - half_tile),
             Vec3::new(tile_size, wall_height, wall_thickness),
         ),
         // South (z + 1)
         (
             (0, 1),
-            Vec3::new(world_pos.x, wall_height / 2.0, world_pos.z + half_tile),
+            Vec3::new(world_pos.x, wall_y, world_pos.z + half_tile),
             Vec3::new(tile_size, wall_height, wall_thickness),
         ),
         // West (x - 1)
         (
             (-1, 0),
-            Vec3::new(world_pos.x - half_tile, wall_height / 2.0, world_pos.z),
+            Vec3::new(world_pos.x - half_tile, wall_y, world_pos.z),
             Vec3::new(wall_thickness, wall_height, tile_size),
         ),
         // East (x + 1)
         (
             (1, 0),
-            Vec3::new(world_pos.x + half_tile, wall_height / 2.0, world_pos.z),
+            Vec3::new(world_pos.x + half_tile, wall_y, world_pos.z),
             Vec3::new(wall_thickness, wall_height, tile_size),
         ),
     ];
 
     for ((dx, dz), position, dimensions) in wall_configs {
-        if needs_wall(level, x + dx, z + dz) {
+        if needs_wall(layer, x + dx, z + dz) {
             spawn_wall(commands, meshes, wall_material.clone(), position, dimensions);
         }
     }
 }
 
+/// A tile within its floor layer, with the sizes geometry around it is built to.
+pub struct TileContext<'a> {
+    pub layer: &'a FloorLayer,
+    pub x: i32,
+    pub z: i32,
+    pub world_pos: Vec3,
+    pub tile_size: f32,
+    pub wall_thickness: f32,
+}
+
+/// Spawn risers on the edges where a tile's floor is higher than a
+/// neighboring floor, closing the gap under the raised floor slab.
+///
+/// Each riser spans from the lower floor up to this tile's floor and sits
+/// just inside this tile, so stairs arriving from the lower tile aren't
+/// blocked.
+pub fn spawn_risers_for_tile(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    mat_registry: &MaterialRegistry,
+    tile: &TileContext,
+) {
+    let TileContext {
+        layer,
+        x,
+        z,
+        world_pos,
+        tile_size,
+        wall_thickness,
+    } = *tile;
+    let current_tile = layer.get_geometry(x, z);
+    let wall_material = mat_registry.get_wall(&current_tile.material);
+    let inset = tile_size / 2.0 - wall_thickness / 2.0;
+
+    // (neighbor offset, direction toward the neighbor)
+    let directions = [
+        ((0, -1), Vec3::NEG_Z),
+        ((0, 1), Vec3::Z),
+        ((-1, 0), Vec3::NEG_X),
+        ((1, 0), Vec3::X),
+    ];
+
+    for ((dx, dz), direction) in directions {
+        let neighbor = layer.get_geometry(x + dx, z + dz);
+        if !neighbor.kind.has_floor() {
+            continue;
+        }
+        let rise = current_tile.elevation - neighbor.elevation;
+        if rise <= f32::EPSILON {
+            continue;
+        }
+
+        let center = world_pos + direction * inset
+            + Vec3::Y * (neighbor.elevation + rise / 2.0);
+        let size = if direction.x != 0.0 {
+            Vec3::new(wall_thickness, rise, tile_size)
+        } else {
+            Vec3::new(tile_size, rise, wall_thickness)
+        };
+        spawn_wall(commands, meshes, wall_material.clone(), center, size);
+    }
+}
+
 /// Check if a wall is needed against the neighboring tile.
-fn needs_wall(level: &LevelDefinition, x: i32, z: i32) -> bool {
-    let neighbor = level.get_geometry(x, z);
+fn needs_wall(layer: &FloorLayer, x: i32, z: i32) -> bool {
+    let neighbor = layer.get_geometry(x, z);
     // Only need edge wall against Void (Wall tiles are now solid cubes)
     neighbor.kind == GeometryKind::Void
 }
@@ -143,11 +213,11 @@ pub fn spawn_wall_cube(
     let wall_material = mat_registry.get_wall(&geo_tile.material);
     let wall_height = geo_tile.height;
 
-    // Solid cube: bottom at y=0, top at y=wall_height
+    // Solid cube: bottom at layer base, top at base + wall_height
     commands.spawn((
         Mesh3d(meshes.add(Cuboid::new(tile_size, wall_height, tile_size))),
         MeshMaterial3d(wall_material),
-        Transform::from_xyz(world_pos.x, wall_height / 2.0, world_pos.z),
+        Transform::from_xyz(world_pos.x, world_pos.y + wall_height / 2.0, world_pos.z),
         Collider::cuboid(tile_size / 2.0, wall_height / 2.0, tile_size / 2.0),
         LevelGeometry,
     ));
@@ -166,8 +236,18 @@ pub fn spawn_pillar(
     commands.spawn((
         Mesh3d(meshes.add(Cuboid::new(pillar_size, wall_height, pillar_size))),
         MeshMaterial3d(mat_registry.pillar.clone()),
-        Transform::from_xyz(world_pos.x, wall_height / 2.0, world_pos.z),
+        Transform::from_xyz(world_pos.x, world_pos.y + wall_height / 2.0, world_pos.z),
         Collider::cuboid(pillar_size / 2.0, wall_height / 2.0, pillar_size / 2.0),
         LevelGeometry,
     ));
 }
+
+/// Whether a point at `offset` from a tile's floor center stands on the tile:
+/// within `half_extent` of the center horizontally and no more than `height`
+/// above the floor. Shared by the tile-sized triggers (exits, trigger volumes,
+/// damage traps and checkpoints).
+pub fn is_on_tile(offset: Vec3, half_extent: f32, height: f32) -> bool {
+    offset.x.abs() <= half_extent
+        && offset.z.abs() <= half_extent
+        && (0.0..=height).contains(&offset.y)
+}
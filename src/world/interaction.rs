@@ -0,0 +1,278 @@
+//! Player interaction with world objects (pickups, doors, levers).
+//!
+//! Each frame the interactable closest to the crosshair, with no level
+//! geometry between it and the camera, is stored in `InteractionTarget`.
+//! Pressing the interact key sends an `InteractEvent` for that target; other
+//! systems react to the event based on what components the target has.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::core::{Action, ActionInput, GameState, PlayState};
+use crate::player::{Player, PlayerCamera};
+
+/// Width in degrees of the angle bands within which candidates are ranked by distance.
+const ANGLE_TIE_TOLERANCE: f32 = 2.0;
+
+/// Configuration for interaction target selection.
+#[derive(Resource, Clone, Debug)]
+pub struct InteractionConfig {
+    /// Largest angle in degrees between the camera forward and an interactable
+    /// for it to be selectable
+    pub max_angle: f32,
+}
+
+impl Default for InteractionConfig {
+    fn default() -> Self {
+        Self { max_angle: 45.0 }
+    }
+}
+
+/// Component for entities the player can interact with.
+#[derive(Component, Clone)]
+pub struct Interactable {
+    /// Short action description shown in prompts (e.g. "pick up", "open")
+    pub prompt: String,
+    /// Maximum interaction distance from the camera
+    pub range: f32,
+}
+
+impl Interactable {
+    pub fn new(prompt: impl Into<String>, range: f32) -> Self {
+        Self {
+            prompt: prompt.into(),
+            range,
+        }
+    }
+}
+
+/// Sent when an entity interacts with an `Interactable`.
+#[derive(Event)]
+pub struct InteractEvent {
+    /// Entity performing the interaction (usually the player)
+    pub interactor: Entity,
+    /// The interactable entity
+    pub target: Entity,
+}
+
+/// The interactable currently selected by the player, if any.
+#[derive(Resource, Default)]
+pub struct InteractionTarget {
+    pub entity: Option<Entity>,
+}
+
+/// Set up interaction systems.
+pub fn setup_interaction_systems(app: &mut App) {
+    app.init_resource::<InteractionTarget>()
+        .init_resource::<InteractionConfig>()
+        .add_event::<InteractEvent>()
+        .add_systems(
+            Update,
+            (update_interaction_target, handle_interact_input)
+                .chain()
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PlayState::Exploring)),
+        )
+        .add_systems(OnExit(GameState::InGame), clear_interaction_target);
+}
+
+/// Select the interactable in range most directly under the crosshair.
+///
+/// Candidates hidden behind walls or closed doors are skipped.
+fn update_interaction_target(
+    config: Res<InteractionConfig>,
+    rapier_context: Query<&RapierContext>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    interactables: Query<(Entity, &GlobalTransform, &Interactable)>,
+    parent_query: Query<&Parent>,
+    mut target: ResMut<InteractionTarget>,
+) {
+    let (Ok(context), Ok(camera_transform)) =
+        (rapier_context.get_single(), camera_query.get_single())
+    else {
+        target.entity = None;
+        return;
+    };
+
+    let camera_pos = camera_transform.translation();
+    let forward = camera_transform.forward().as_vec3();
+
+    let candidates = interactables.iter().filter_map(|(entity, transform, interactable)| {
+        let offset = transform.translation() - camera_pos;
+        let distance = offset.length();
+        if distance > interactable.range {
+            return None;
+        }
+        // Ignore anything outside the selection cone (including behind the camera)
+        let angle = offset.angle_between(forward).to_degrees();
+        if angle.is_nan() || angle > config.max_angle {
+            return None;
+        }
+        // A door's own panel doesn't hide it
+        let is_part_of_target =
+            |hit: Entity| hit == entity || parent_query.iter_ancestors(hit).any(|a| a == entity);
+        if is_occluded(context, camera_pos, offset, is_part_of_target) {
+            return None;
+        }
+        Some((entity, angle, distance))
+    });
+    let best = best_candidate(candidates);
+
+    if target.entity != best {
+        target.entity = best;
+    }
+}
+
+/// Whether level geometry blocks the line from the camera to a candidate
+/// `offset` away. Colliders for which `is_part_of_target` holds are ignored.
+fn is_occluded(
+    context: &RapierContext,
+    camera_pos: Vec3,
+    offset: Vec3,
+    is_part_of_target: impl Fn(Entity) -> bool,
+) -> bool {
+    let distance = offset.length();
+    if distance <= f32::EPSILON {
+        return false;
+    }
+    let predicate = |hit: Entity| !is_part_of_target(hit);
+    let filter = QueryFilter::only_fixed().exclude_sensors().predicate(&predicate);
+
+    context
+        .cast_ray(camera_pos, offset / distance, distance, true, filter)
+        .is_some()
+}
+
+/// Pick the candidate with the smallest angle to the crosshair.
+///
+/// Angles are grouped into bands `ANGLE_TIE_TOLERANCE` wide, and candidates
+/// in the same band are ranked by distance, so of two objects lined up the
+/// nearer one wins. Ranking by (band, distance) is a total order, so the
+/// pick doesn't depend on the order candidates are visited in.
+fn best_candidate(candidates: impl Iterator<Item = (Entity, f32, f32)>) -> Option<Entity> {
+    let band = |angle: f32| (angle / ANGLE_TIE_TOLERANCE).floor();
+    candidates
+        .min_by(|(_, angle_a, distance_a), (_, angle_b, distance_b)| {
+            band(*angle_a)
+                .total_cmp(&band(*angle_b))
+                .then(distance_a.total_cmp(distance_b))
+        })
+        .map(|(entity, _, _)| entity)
+}
+
+/// Send an InteractEvent for the current target when the interact key is pressed.
+fn handle_interact_input(
+    input: ActionInput,
+    target: Res<InteractionTarget>,
+    player_query: Query<Entity, With<Player>>,
+    mut interact_events: EventWriter<InteractEvent>,
+) {
+    if !input.just_pressed(Action::Interact) {
+        return;
+    }
+
+    let (Some(target_entity), Ok(player_entity)) = (target.entity, player_query.get_single()) else {
+        return;
+    };
+
+    interact_events.send(InteractEvent {
+        interactor: player_entity,
+        target: target_entity,
+    });
+}
+
+/// Forget the current target when leaving gameplay.
+fn clear_interaction_target(mut target: ResMut<InteractionTarget>) {
+    target.entity = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_rapier3d::rapier::prelude::{ColliderBuilder, Vector};
+
+    fn candidate(index: u32, angle: f32, distance: f32) -> (Entity, f32, f32) {
+        (Entity::from_raw(index), angle, distance)
+    }
+
+    #[test]
+    fn smallest_angle_wins() {
+        let candidates = [candidate(1, 20.0, 1.0), candidate(2, 5.0, 3.0)];
+        assert_eq!(best_candidate(candidates.into_iter()), Some(Entity::from_raw(2)));
+    }
+
+    #[test]
+    fn nearer_wins_within_angle_band() {
+        let candidates = [candidate(1, 10.2, 3.0), candidate(2, 11.5, 1.5)];
+        assert_eq!(best_candidate(candidates.into_iter()), Some(Entity::from_raw(2)));
+    }
+
+    #[test]
+    fn pick_does_not_depend_on_order() {
+        // Pairwise, each is "within tolerance" of its neighbor, which made
+        // the old fold pick a different winner depending on order
+        let candidates = [
+            candidate(1, 0.5, 10.0),
+            candidate(2, 1.9, 5.0),
+            candidate(3, 3.3, 1.0),
+        ];
+        let orders = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        for order in orders {
+            let shuffled = order.map(|index| candidates[index]);
+            assert_eq!(
+                best_candidate(shuffled.into_iter()),
+                Some(Entity::from_raw(2)),
+                "order {:?}",
+                order
+            );
+        }
+    }
+
+    #[test]
+    fn no_candidates_no_target() {
+        assert_eq!(best_candidate(std::iter::empty()), None);
+    }
+
+    /// A physics world with one fixed wall (entity 1) spanning x = 2..3.
+    fn context_with_wall() -> RapierContext {
+        let mut context = RapierContext::default();
+        let wall = ColliderBuilder::cuboid(0.5, 2.0, 5.0)
+            .translation(Vector::new(2.5, 0.0, 0.0))
+            .user_data(Entity::from_raw(1).to_bits() as u128)
+            .build();
+        context.colliders.insert(wall);
+        context.query_pipeline.update(&context.colliders);
+        context
+    }
+
+    #[test]
+    fn wall_hides_candidate_behind_it() {
+        let context = context_with_wall();
+        let behind = Vec3::new(4.0, 0.0, 0.0);
+        assert!(is_occluded(&context, Vec3::ZERO, behind, |_| false));
+    }
+
+    #[test]
+    fn candidate_in_front_of_wall_is_visible() {
+        let context = context_with_wall();
+        let in_front = Vec3::new(1.5, 0.0, 0.0);
+        assert!(!is_occluded(&context, Vec3::ZERO, in_front, |_| false));
+    }
+
+    #[test]
+    fn own_collider_does_not_hide_candidate() {
+        // Like a closed door, whose panel sits in front of its center
+        let context = context_with_wall();
+        let door = Vec3::new(2.5, 0.0, 0.0);
+        assert!(is_occluded(&context, Vec3::ZERO, door, |_| false));
+        assert!(!is_occluded(&context, Vec3::ZERO, door, |hit| hit == Entity::from_raw(1)));
+    }
+}
@@ -0,0 +1,194 @@
+//! Loading screen - preloads models before the main menu.
+//!
+//! Enemy scenes referenced by any level, every weapon model and the
+//! optional first-person arms are requested while in `GameState::Loading`.
+//! Their handles are kept alive in `PreloadedAssets`, so spawning a level later
+//! reuses the already loaded assets instead of stalling on the first frame. If
+//! loading takes longer than `LOADING_TIMEOUT`, the game continues anyway with
+//! a warning.
+
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy::prelude::*;
+use std::collections::BTreeSet;
+
+use super::data::LevelRegistry;
+use crate::combat::WeaponRegistry;
+use crate::core::GameState;
+use crate::enemies::data::EnemyRegistry;
+use crate::player::PlayerConfig;
+
+/// Seconds to wait for assets before giving up and continuing.
+const LOADING_TIMEOUT: f32 = 15.0;
+
+/// Handles requested during the loading state, kept alive for the session.
+#[derive(Resource, Default)]
+pub struct PreloadedAssets {
+    pub scenes: Vec<Handle<Scene>>,
+    /// Seconds spent waiting so far
+    elapsed: f32,
+}
+
+/// Marker for loading screen entities.
+#[derive(Component)]
+struct LoadingUi;
+
+/// Marker for the progress text on the loading screen.
+#[derive(Component)]
+struct LoadingProgressText;
+
+/// Configure loading state systems.
+pub fn setup_loading_systems(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Loading), setup_loading_screen)
+        .add_systems(
+            Update,
+            (
+                queue_preload.run_if(not(resource_exists::<PreloadedAssets>)),
+                check_preload.run_if(resource_exists::<PreloadedAssets>),
+            )
+                .chain()
+                .run_if(in_state(GameState::Loading)),
+        )
+        .add_systems(OnExit(GameState::Loading), cleanup_loading_screen);
+}
+
+/// Spawn the loading screen.
+fn setup_loading_screen(mut commands: Commands) {
+    // Camera for UI rendering, removed with the rest of the screen
+    commands.spawn((Camera2d, LoadingUi));
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.05, 0.05, 0.08)),
+            LoadingUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Loading..."),
+                TextFont {
+                    font_size: 36.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.7, 0.6)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.5, 0.5, 0.55)),
+                LoadingProgressText,
+            ));
+        });
+}
+
+/// Request every model the levels and player need.
+///
+/// Runs on the first update rather than on entering the state, because the
+/// level and enemy registries are only filled in by `Startup` systems.
+fn queue_preload(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level_registry: Option<Res<LevelRegistry>>,
+    enemy_registry: Res<EnemyRegistry>,
+    weapon_registry: Res<WeaponRegistry>,
+    player_config: Res<PlayerConfig>,
+) {
+    let mut paths = BTreeSet::new();
+
+    if let Some(level_registry) = level_registry {
+        for level in level_registry.levels.values() {
+            for layer in &level.floors {
+                for spawn in &layer.monster_spawns {
+                    // Traps share the monster grid but have no model
+                    for enemy_type in spawn.entry.enemy_types() {
+                        if let Some(definition) = enemy_registry.get(enemy_type) {
+                            paths.insert(definition.model_path.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Weapons can be picked up mid-level, so load all of them up front
+    let starter = weapon_registry.starter_weapon();
+    let weapon_models = weapon_registry
+        .definitions
+        .values()
+        .map(|definition| definition.model_path.clone())
+        .chain(std::iter::once(starter.model_path));
+    paths.extend(weapon_models.filter(|path| !path.is_empty()));
+    if let Some(arms_path) = &player_config.arms_model_path {
+        paths.insert(arms_path.clone());
+    }
+
+    info!("Preloading {} models", paths.len());
+
+    commands.insert_resource(PreloadedAssets {
+        scenes: paths.into_iter().map(|path| asset_server.load(path)).collect(),
+        elapsed: 0.0,
+    });
+}
+
+/// Update loading progress and continue to the main menu once done.
+fn check_preload(
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut preloaded: ResMut<PreloadedAssets>,
+    mut text_query: Query<&mut Text, With<LoadingProgressText>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    preloaded.elapsed += time.delta_secs();
+
+    let mut finished = 0;
+    let mut failed = Vec::new();
+    for handle in &preloaded.scenes {
+        match asset_server.recursive_dependency_load_state(handle) {
+            RecursiveDependencyLoadState::Loaded => finished += 1,
+            RecursiveDependencyLoadState::Failed(_) => {
+                finished += 1;
+                failed.push(handle.path());
+            }
+            _ => {}
+        }
+    }
+
+    let total = preloaded.scenes.len();
+    for mut text in text_query.iter_mut() {
+        **text = format!("{} / {}", finished, total);
+    }
+
+    if finished == total {
+        if !failed.is_empty() {
+            warn!("Failed to preload models: {:?}", failed);
+        }
+        next_state.set(GameState::MainMenu);
+    } else if preloaded.elapsed >= LOADING_TIMEOUT {
+        warn!(
+            "Loading timed out after {:.0}s with {} of {} models loaded, continuing anyway",
+            LOADING_TIMEOUT, finished, total
+        );
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+/// Remove the loading screen.
+fn cleanup_loading_screen(mut commands: Commands, query: Query<Entity, With<LoadingUi>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
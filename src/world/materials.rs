@@ -3,10 +3,17 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
+use super::data::MaterialDef;
+
 /// Material registry mapping material names to handles.
 pub struct MaterialRegistry {
     materials: HashMap<String, Handle<StandardMaterial>>,
     ceilings: HashMap<String, Handle<StandardMaterial>>,
+    /// Built-in fallbacks for unknown names, kept out of the maps so custom
+    /// materials can't replace them
+    default_floor: Handle<StandardMaterial>,
+    default_wall: Handle<StandardMaterial>,
+    default_ceiling: Handle<StandardMaterial>,
     pub pillar: Handle<StandardMaterial>,
 }
 
@@ -106,20 +113,52 @@ impl MaterialRegistry {
         });
 
         Self {
+            default_floor: registry["stone"].clone(),
+            default_wall: registry["stone_wall"].clone(),
+            default_ceiling: ceilings["ceiling"].clone(),
             materials: registry,
             ceilings,
             pillar,
         }
     }
 
+    /// Add a custom material, replacing any material of the same name.
+    ///
+    /// Custom materials work for floors, walls and ceilings. Replacing a
+    /// built-in also replaces its `_wall` variant and its ceiling material,
+    /// so the override applies everywhere the name is used.
+    pub fn register(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+        name: &str,
+        def: &MaterialDef,
+    ) {
+        let (r, g, b) = def.base_color;
+        let handle = materials.add(StandardMaterial {
+            base_color: Color::srgb(r, g, b),
+            perceptual_roughness: def.perceptual_roughness,
+            metallic: def.metallic,
+            emissive: def
+                .emissive
+                .map_or(LinearRgba::BLACK, |(r, g, b)| LinearRgba::rgb(r, g, b)),
+            ..default()
+        });
+        let wall_name = format!("{}_wall", name);
+        if self.materials.contains_key(&wall_name) {
+            self.materials.insert(wall_name, handle.clone());
+        }
+        if self.ceilings.contains_key(name) {
+            self.ceilings.insert(name.to_string(), handle.clone());
+        }
+        self.materials.insert(name.to_string(), handle);
+    }
+
     /// Get material for floor by name.
     pub fn get_floor(&self, material_name: &str) -> Handle<StandardMaterial> {
         self.materials
             .get(material_name)
             .cloned()
-            .unwrap_or_else(|| {
-                self.materials.get("stone").cloned().unwrap()
-            })
+            .unwrap_or_else(|| self.default_floor.clone())
     }
 
     /// Get material for walls by name.
@@ -130,18 +169,15 @@ impl MaterialRegistry {
             .get(&wall_name)
             .or_else(|| self.materials.get(material_name))
             .cloned()
-            .unwrap_or_else(|| {
-                self.materials.get("stone_wall").cloned().unwrap()
-            })
+            .unwrap_or_else(|| self.default_wall.clone())
     }
 
     /// Get material for ceilings by name.
     pub fn get_ceiling(&self, material_name: &str) -> Handle<StandardMaterial> {
         self.ceilings
             .get(material_name)
+            .or_else(|| self.materials.get(material_name))
             .cloned()
-            .unwrap_or_else(|| {
-                self.ceilings.get("ceiling").cloned().unwrap()
-            })
+            .unwrap_or_else(|| self.default_ceiling.clone())
     }
 }
@@ -1,15 +1,47 @@
 //! World module - levels, environments, and interactables.
 
+mod ambient_particles;
+mod barrels;
+mod bounds;
 mod builder;
+mod checkpoints;
+mod damage_traps;
 mod data;
+mod doors;
 mod error;
+mod exits;
+mod exploration;
+mod fallback;
 mod geometry;
+mod interaction;
+mod loading;
 mod materials;
+mod platforms;
 mod prefabs;
 mod plugin;
 mod spawning;
+mod triggers;
+mod validation;
 
-pub use builder::LevelGeometry;
-pub use data::{CurrentLevel, GeometryKind, LevelDefinition, LevelRegistry, PaletteRegistry, PrefabInstance, PrefabKind};
+pub use ambient_particles::{AmbientParticleKind, ParticleEmitter};
+pub use barrels::{BarrelConfig, ExplosiveBarrel};
+pub use bounds::LevelBounds;
+pub use builder::{build_level_from_data, spawn_level_in_world, LevelGeometry, Registries};
+pub use checkpoints::{Checkpoint, LastCheckpoint};
+pub use damage_traps::DamageTrap;
+pub use data::{
+    read_palette_files, CurrentLevel, DamageTrapDef, ExitDef, FloorLayer, GeometryKind,
+    GeometryTileDef, LevelDefinition, LevelDefinitionRaw, LevelRegistry, MaterialDef,
+    MonsterPaletteEntry, PaletteRegistry, PrefabInstance, PrefabKind, TriggerDef, WeightedEnemy,
+    LEVELS_DIR, PALETTES_DIR,
+};
+pub use doors::{Door, DoorPanel};
 pub use error::DataLoadError;
+pub use exits::{ExitTrigger, LevelTransition};
+pub use exploration::ExploredTiles;
+pub use interaction::{InteractEvent, Interactable, InteractionConfig, InteractionTarget};
+pub use loading::PreloadedAssets;
+pub use platforms::MovingPlatform;
 pub use plugin::{setup_level, WorldPlugin};
+pub use triggers::{TriggerEvent, TriggerVolume};
+pub use validation::{validate_level_dir, validate_level_str, LevelReport};
@@ -0,0 +1,176 @@
+//! Moving platforms - lifts travelling between two elevations.
+//!
+//! Platforms are `MovingPlatform` prefabs: a kinematic slab the size of a
+//! tile. Untriggered platforms travel back and forth continuously; platforms
+//! with a `trigger` wait at an end until that `TriggerEvent` fires, then ride
+//! to the other end. Each frame's movement is added to the player's
+//! character controller while they stand on the platform, so they ride it
+//! instead of sinking into it or being left behind.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::builder::LevelGeometry;
+use super::data::PrefabInstance;
+use super::materials::MaterialRegistry;
+use super::triggers::TriggerEvent;
+use crate::core::{GameState, PlayState};
+use crate::player::{player_movement, Player};
+
+/// Thickness of the platform slab.
+const PLATFORM_THICKNESS: f32 = 0.3;
+
+/// Round trip time when the prefab doesn't give a period.
+const DEFAULT_PERIOD: f32 = 6.0;
+
+/// Material of platforms.
+const PLATFORM_MATERIAL: &str = "metal";
+
+/// How far below the player's feet counts as standing on a platform.
+/// Slightly more than the player's own ground check, so a platform moving
+/// down doesn't drop them for a frame.
+const RIDE_CHECK_DISTANCE: f32 = 0.25;
+
+/// Distance from the player's center to their feet.
+const PLAYER_FOOT_OFFSET: f32 = 0.8;
+
+/// A platform moving between two elevations.
+#[derive(Component)]
+pub struct MovingPlatform {
+    /// Top surface height at each end of the path
+    pub from_y: f32,
+    pub to_y: f32,
+    /// Seconds for a full round trip
+    pub period: f32,
+    /// Trigger event id that starts a ride (None = always moving)
+    pub trigger: Option<String>,
+    /// Position along the path (0.0 = `from_y`, 1.0 = `to_y`)
+    progress: f32,
+    /// 1.0 toward `to_y`, -1.0 toward `from_y`, 0.0 waiting at an end
+    direction: f32,
+    /// Movement this frame, passed on to a player riding the platform
+    delta: Vec3,
+}
+
+impl MovingPlatform {
+    /// Top surface height at the current progress, eased at both ends.
+    fn surface_y(&self) -> f32 {
+        let t = self.progress;
+        let eased = t * t * (3.0 - 2.0 * t);
+        self.from_y + (self.to_y - self.from_y) * eased
+    }
+}
+
+/// Set up moving platform systems.
+pub fn setup_platform_systems(app: &mut App) {
+    app.add_systems(
+        Update,
+        (start_triggered_platforms, move_platforms, carry_player_on_platforms)
+            .chain()
+            .after(player_movement)
+            .run_if(in_state(GameState::InGame))
+            .run_if(in_state(PlayState::Exploring)),
+    );
+}
+
+/// Spawn a moving platform prefab, starting at `from_elevation`.
+pub fn spawn_moving_platform(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    prefab: &PrefabInstance,
+    tile_size: f32,
+    mat_registry: &MaterialRegistry,
+) {
+    let x = prefab.position.0 as f32 * tile_size + tile_size / 2.0;
+    let z = prefab.position.1 as f32 * tile_size + tile_size / 2.0;
+    let platform = MovingPlatform {
+        from_y: prefab.from_elevation,
+        to_y: prefab.to_elevation,
+        period: prefab.period.unwrap_or(DEFAULT_PERIOD).max(0.1),
+        trigger: prefab.trigger.clone(),
+        progress: 0.0,
+        direction: if prefab.trigger.is_some() { 0.0 } else { 1.0 },
+        delta: Vec3::ZERO,
+    };
+    let y = platform.surface_y() - PLATFORM_THICKNESS / 2.0;
+
+    commands.spawn((
+        platform,
+        Mesh3d(meshes.add(Cuboid::new(tile_size, PLATFORM_THICKNESS, tile_size))),
+        MeshMaterial3d(mat_registry.get_floor(PLATFORM_MATERIAL)),
+        Transform::from_xyz(x, y, z),
+        RigidBody::KinematicPositionBased,
+        Collider::cuboid(tile_size / 2.0, PLATFORM_THICKNESS / 2.0, tile_size / 2.0),
+        LevelGeometry,
+    ));
+}
+
+/// Send triggered platforms waiting at an end toward the other end.
+fn start_triggered_platforms(
+    mut trigger_events: EventReader<TriggerEvent>,
+    mut platform_query: Query<&mut MovingPlatform>,
+) {
+    for event in trigger_events.read() {
+        for mut platform in platform_query.iter_mut() {
+            if platform.trigger.as_deref() != Some(event.id.as_str()) || platform.direction != 0.0 {
+                continue;
+            }
+            platform.direction = if platform.progress < 0.5 { 1.0 } else { -1.0 };
+        }
+    }
+}
+
+/// Advance platforms along their paths and record how far they moved.
+fn move_platforms(time: Res<Time>, mut query: Query<(&mut MovingPlatform, &mut Transform)>) {
+    for (mut platform, mut transform) in query.iter_mut() {
+        if platform.direction == 0.0 {
+            platform.delta = Vec3::ZERO;
+            continue;
+        }
+
+        // One way is half a round trip
+        let step = 2.0 / platform.period * time.delta_secs();
+        platform.progress += platform.direction * step;
+        if !(0.0..=1.0).contains(&platform.progress) {
+            platform.progress = platform.progress.clamp(0.0, 1.0);
+            platform.direction = if platform.trigger.is_some() {
+                0.0
+            } else {
+                -platform.direction
+            };
+        }
+
+        let y = platform.surface_y() - PLATFORM_THICKNESS / 2.0;
+        platform.delta = Vec3::Y * (y - transform.translation.y);
+        transform.translation.y = y;
+    }
+}
+
+/// Move the player with the platform they're standing on.
+fn carry_player_on_platforms(
+    rapier_context: Query<&RapierContext>,
+    platform_query: Query<&MovingPlatform>,
+    mut player_query: Query<(Entity, &Transform, &mut KinematicCharacterController), With<Player>>,
+) {
+    let (Ok(context), Ok((player_entity, transform, mut controller))) =
+        (rapier_context.get_single(), player_query.get_single_mut())
+    else {
+        return;
+    };
+
+    let ray_origin = transform.translation - Vec3::Y * (PLAYER_FOOT_OFFSET - 0.05);
+    let Some((hit_entity, _)) = context.cast_ray(
+        ray_origin,
+        Vec3::NEG_Y,
+        RIDE_CHECK_DISTANCE,
+        true,
+        QueryFilter::default().exclude_collider(player_entity),
+    ) else {
+        return;
+    };
+    let Ok(platform) = platform_query.get(hit_entity) else {
+        return;
+    };
+
+    controller.translation = Some(controller.translation.unwrap_or(Vec3::ZERO) + platform.delta);
+}
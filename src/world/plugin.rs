@@ -1,61 +1,163 @@
 //! World plugin - level loading, environment, and interactables.
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::core::GameState;
+use crate::combat::WeaponRegistry;
+use crate::core::GameSession;
 use crate::enemies::data::EnemyRegistry;
-use crate::player::spawn_player;
+use crate::enemies::TrapRegistry;
+use crate::player::{spawn_player, PlayerConfig};
 use crate::rendering::VisualConfig;
 
-use super::builder::{build_level_from_data, LevelGeometry};
+use super::ambient_particles;
+use super::barrels;
+use super::bounds::{self, LevelBounds};
+use super::builder::{build_level_from_data, LevelGeometry, Registries};
+use super::checkpoints;
+use super::damage_traps;
 use super::data::{load_level_definitions, load_palette_files, CurrentLevel, LevelRegistry};
+use super::doors;
+use super::exits::{self, LevelTransition};
+use super::exploration;
+use super::fallback::spawn_safe_room;
+use super::interaction;
+use super::loading;
+use super::platforms;
+use super::triggers;
 
 /// World plugin - handles level loading and world setup.
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
+        // Setup interaction systems
+        interaction::setup_interaction_systems(app);
+
+        // Setup loading screen systems
+        loading::setup_loading_systems(app);
+
+        // Setup kill plane systems
+        bounds::setup_bounds_systems(app);
+
+        // Setup explosive barrel systems
+        barrels::setup_barrel_systems(app);
+
+        // Setup ambient particle systems
+        ambient_particles::setup_ambient_particle_systems(app);
+
+        // Setup door systems
+        doors::setup_door_systems(app);
+
+        // Setup level exit systems
+        exits::setup_exit_systems(app);
+
+        // Setup explored tile tracking
+        exploration::setup_exploration_systems(app);
+
+        // Setup trigger volume systems
+        triggers::setup_trigger_systems(app);
+
+        // Setup damaging floor systems
+        damage_traps::setup_damage_trap_systems(app);
+
+        // Setup moving platform systems
+        platforms::setup_platform_systems(app);
+
+        // Setup checkpoint and respawn systems
+        checkpoints::setup_checkpoint_systems(app);
+
         app.add_systems(
             Startup,
             (load_palette_files, load_level_definitions).chain(),
         )
-        .add_systems(OnEnter(GameState::InGame), setup_level)
-        .add_systems(OnExit(GameState::InGame), cleanup_level);
+        // Keyed on the session rather than InGame so pausing keeps the level
+        .add_systems(OnEnter(GameSession), setup_level)
+        .add_systems(OnExit(GameSession), cleanup_level);
     }
 }
 
-/// Set up the level from data.
+/// Everything needed to build a level from the `LevelRegistry`.
+#[derive(SystemParam)]
+pub struct LevelBuilder<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    level_registry: Res<'w, LevelRegistry>,
+    asset_server: Res<'w, AssetServer>,
+    enemy_registry: Res<'w, EnemyRegistry>,
+    trap_registry: Res<'w, TrapRegistry>,
+    visual_config: Res<'w, VisualConfig>,
+}
+
+impl LevelBuilder<'_> {
+    /// Build the level with this registry key and set its `LevelBounds`.
+    ///
+    /// Falls back to a safe room if the level isn't available, so broken
+    /// level data never leaves the player in an empty world. Returns where
+    /// the player should be placed: the tile `spawn` if given, otherwise the
+    /// level's player start.
+    pub fn build(&mut self, commands: &mut Commands, name: &str, spawn: Option<(i32, i32)>) -> Vec3 {
+        let Some(level) = self.level_registry.get(name) else {
+            error!(
+                "Cannot build level '{}': {}. Spawning a safe room instead.",
+                name,
+                self.level_registry.missing_reason(name)
+            );
+            let (player_pos, kill_plane_y) =
+                spawn_safe_room(commands, &mut self.meshes, &mut self.materials, name);
+            commands.insert_resource(LevelBounds {
+                kill_plane_y,
+                respawn: player_pos,
+            });
+            return player_pos;
+        };
+
+        info!("Building level: {}", level.name);
+
+        let start_pos = build_level_from_data(
+            commands,
+            &mut self.meshes,
+            &mut self.materials,
+            level,
+            &self.asset_server,
+            &Registries {
+                enemies: &self.enemy_registry,
+                traps: &self.trap_registry,
+            },
+            &self.visual_config,
+        );
+        let player_pos = spawn.map_or(start_pos, |tile| level.spawn_position(tile));
+
+        commands.insert_resource(LevelBounds {
+            kill_plane_y: level.kill_plane_y(),
+            respawn: player_pos,
+        });
+
+        player_pos
+    }
+}
+
+/// Set up the current level from data and spawn the player in it.
 pub fn setup_level(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    level_registry: Res<LevelRegistry>,
+    mut builder: LevelBuilder,
     current_level: Res<CurrentLevel>,
-    asset_server: Res<AssetServer>,
-    enemy_registry: Res<EnemyRegistry>,
     visual_config: Res<VisualConfig>,
+    player_config: Res<PlayerConfig>,
+    weapon_registry: Res<WeaponRegistry>,
 ) {
-    let Some(level) = level_registry.get(&current_level.name) else {
-        error!("Level '{}' not found in registry!", current_level.name);
-        return;
-    };
+    let player_pos = builder.build(&mut commands, &current_level.name, None);
 
-    info!("Building level: {}", level.name);
-
-    let player_pos = build_level_from_data(
+    spawn_player(
         &mut commands,
-        &mut meshes,
-        &mut materials,
-        level,
-        &asset_server,
-        &enemy_registry,
+        player_pos,
+        &player_config,
         &visual_config,
+        weapon_registry.starter_weapon(),
     );
-
-    spawn_player(&mut commands, player_pos, &visual_config);
 }
 
-/// Clean up level entities when leaving InGame state.
+/// Clean up level entities when the game session ends.
 fn cleanup_level(
     mut commands: Commands,
     level_query: Query<Entity, With<LevelGeometry>>,
@@ -67,4 +169,6 @@ fn cleanup_level(
     for entity in player_query.iter() {
         commands.entity(entity).despawn_recursive();
     }
+    commands.remove_resource::<LevelBounds>();
+    commands.remove_resource::<LevelTransition>();
 }
@@ -1,10 +1,23 @@
-//! Prefab spawning for complex structures like stairs.
+//! Prefab spawning for complex structures like stairs, lifts and checkpoints.
 
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
+use super::barrels::{ExplosiveBarrel, BARREL_HEIGHT, BARREL_RADIUS};
 use super::builder::LevelGeometry;
+use super::checkpoints::spawn_checkpoint;
 use super::data::{PrefabInstance, PrefabKind};
+use super::materials::MaterialRegistry;
+use super::platforms::spawn_moving_platform;
+use crate::combat::HitFx;
+
+/// Height of stair side walls above each step.
+const STAIR_WALL_HEIGHT: f32 = 2.5;
+const STAIR_WALL_THICKNESS: f32 = 0.2;
+
+/// Height of stair railings above each step.
+const STAIR_RAILING_HEIGHT: f32 = 0.9;
+const STAIR_RAILING_THICKNESS: f32 = 0.08;
 
 /// Spawn a prefab instance.
 pub fn spawn_prefab(
@@ -12,22 +25,58 @@ pub fn spawn_prefab(
     meshes: &mut Assets<Mesh>,
     prefab: &PrefabInstance,
     tile_size: f32,
-    stair_material: Handle<StandardMaterial>,
+    mat_registry: &MaterialRegistry,
 ) {
     match prefab.kind {
         PrefabKind::StepStairs => spawn_step_stairs(
-            commands, meshes, prefab, tile_size, stair_material
+            commands, meshes, prefab, tile_size, mat_registry
+        ),
+        PrefabKind::ExplosiveBarrel => spawn_explosive_barrel(
+            commands, meshes, prefab, tile_size, mat_registry.get_wall("wood")
+        ),
+        PrefabKind::MovingPlatform => spawn_moving_platform(
+            commands, meshes, prefab, tile_size, mat_registry
+        ),
+        PrefabKind::Checkpoint => spawn_checkpoint(
+            commands, meshes, prefab, tile_size, mat_registry.get_floor("metal")
         ),
     }
 }
 
+/// Spawn an explosive barrel. Health and blast are added from `BarrelConfig`
+/// once the barrel is in the world.
+fn spawn_explosive_barrel(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    prefab: &PrefabInstance,
+    tile_size: f32,
+    material: Handle<StandardMaterial>,
+) {
+    let x = prefab.position.0 as f32 * tile_size + tile_size / 2.0;
+    let z = prefab.position.1 as f32 * tile_size + tile_size / 2.0;
+    let y = prefab.from_elevation + BARREL_HEIGHT / 2.0;
+
+    commands.spawn((
+        ExplosiveBarrel,
+        Mesh3d(meshes.add(Cylinder::new(BARREL_RADIUS, BARREL_HEIGHT))),
+        MeshMaterial3d(material),
+        Transform::from_xyz(x, y, z),
+        Collider::cylinder(BARREL_HEIGHT / 2.0, BARREL_RADIUS),
+        HitFx::Sparks,
+        LevelGeometry,
+    ));
+}
+
 /// Spawn step stairs (cube steps that work with autostep).
+///
+/// With `side_walls` or `railings` set, each step also gets a matching wall
+/// segment or railing on both sides, so the sides follow the steps up.
 fn spawn_step_stairs(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     prefab: &PrefabInstance,
     tile_size: f32,
-    material: Handle<StandardMaterial>,
+    mat_registry: &MaterialRegistry,
 ) {
     let height_diff = prefab.to_elevation - prefab.from_elevation;
     let length_tiles = prefab.length.unwrap_or(1) as f32;
@@ -52,6 +101,10 @@ fn spawn_step_stairs(
     // Rotation quaternion
     let rotation = Quat::from_rotation_y(prefab.rotation.to_radians());
 
+    let material = mat_registry.get_floor("stone");
+    let wall_material = mat_registry.get_wall("stone");
+    let railing_material = mat_registry.get_wall("wood");
+
     for i in 0..num_steps {
         // Calculate local offset along the stair direction
         // Steps go in +Z direction (forward) relative to rotation
@@ -75,6 +128,45 @@ fn spawn_step_stairs(
             Collider::cuboid(tile_size / 2.0, actual_step_height / 2.0, step_depth / 2.0),
             LevelGeometry,
         ));
+
+        let step_top = prefab.from_elevation + (i as f32 + 1.0) * actual_step_height;
+
+        for side in [-1.0, 1.0] {
+            if prefab.side_walls {
+                // Outside the step edge, from the stair base up past the step
+                let height = step_top - prefab.from_elevation + STAIR_WALL_HEIGHT;
+                let offset = Vec3::new(
+                    side * (tile_size + STAIR_WALL_THICKNESS) / 2.0,
+                    prefab.from_elevation + height / 2.0,
+                    local_offset.z,
+                );
+                spawn_stair_side(
+                    commands,
+                    meshes,
+                    wall_material.clone(),
+                    Vec3::new(STAIR_WALL_THICKNESS, height, step_depth),
+                    Vec3::new(base_x, 0.0, base_z) + rotation * offset,
+                    rotation,
+                );
+            }
+
+            if prefab.railings {
+                // Standing on the step edge
+                let offset = Vec3::new(
+                    side * (tile_size - STAIR_RAILING_THICKNESS) / 2.0,
+                    step_top + STAIR_RAILING_HEIGHT / 2.0,
+                    local_offset.z,
+                );
+                spawn_stair_side(
+                    commands,
+                    meshes,
+                    railing_material.clone(),
+                    Vec3::new(STAIR_RAILING_THICKNESS, STAIR_RAILING_HEIGHT, step_depth),
+                    Vec3::new(base_x, 0.0, base_z) + rotation * offset,
+                    rotation,
+                );
+            }
+        }
     }
 
     info!(
@@ -83,3 +175,21 @@ fn spawn_step_stairs(
         prefab.position.0, prefab.position.1
     );
 }
+
+/// Spawn one side wall or railing segment of a staircase.
+fn spawn_stair_side(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    size: Vec3,
+    position: Vec3,
+    rotation: Quat,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_size(size))),
+        MeshMaterial3d(material),
+        Transform::from_translation(position).with_rotation(rotation),
+        Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+        LevelGeometry,
+    ));
+}
@@ -3,12 +3,15 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use super::builder::LevelGeometry;
-use super::data::{LevelDefinition, ResolvedMonsterSpawn};
-use crate::combat::Health;
+use super::builder::{LevelGeometry, Registries};
+use super::data::{LevelDefinition, MonsterSpawn};
+use super::materials::MaterialRegistry;
+use crate::combat::{Health, Knockback};
 use crate::enemies::animation::NeedsAnimationSetup;
-use crate::enemies::data::EnemyRegistry;
-use crate::enemies::{AiState, AttackTimer, Enemy, EnemyType};
+use crate::enemies::awareness::Awareness;
+use crate::enemies::collider_fit::NeedsColliderFit;
+use crate::enemies::traps::{spawn_trap, TrapRegistry};
+use crate::enemies::{AiLod, AiState, AttackTimer, Enemy, EnemyType, SpawnPoint, Wander};
 
 /// Spawn a point light.
 pub fn spawn_light(
@@ -32,32 +35,49 @@ pub fn spawn_light(
     ));
 }
 
-/// Spawn monsters from the resolved monster grid.
+/// Spawn monsters from the resolved monster grid of a floor layer.
 pub fn spawn_monsters_from_grid(
     commands: &mut Commands,
     level: &LevelDefinition,
-    monster_spawns: &[ResolvedMonsterSpawn],
+    monster_spawns: &[MonsterSpawn],
+    floor: usize,
+    base_elevation: f32,
     asset_server: &AssetServer,
-    enemy_registry: &EnemyRegistry,
+    registries: &Registries,
 ) {
     for spawn in monster_spawns {
-        let Some(definition) = enemy_registry.get(&spawn.enemy_type) else {
-            warn!("Unknown enemy type in monster grid: {}", spawn.enemy_type);
+        let Some(definition) = registries.enemies.get(&spawn.enemy_type) else {
+            // Traps share the monster grid and are spawned by spawn_traps_from_grid
+            if registries.traps.get(&spawn.enemy_type).is_none() {
+                warn!("Unknown enemy type in monster grid: {}", spawn.enemy_type);
+            }
             continue;
         };
 
         let world_pos = level.grid_to_world(spawn.grid_pos.0, spawn.grid_pos.1);
-        let spawn_pos = Vec3::new(world_pos.x, 0.0, world_pos.z);
+        let spawn_pos = Vec3::new(world_pos.x, base_elevation, world_pos.z);
 
         let collider_config = definition.collider.clone().unwrap_or_default();
 
-        commands.spawn((
+        let mut enemy = commands.spawn((
             Enemy,
             EnemyType(spawn.enemy_type.clone()),
-            AiState::default(),
+            SpawnPoint {
+                floor,
+                tile: spawn.grid_pos,
+            },
+            // AI
+            (
+                AiState::default(),
+                AiLod::default(),
+                AttackTimer::default(),
+                Awareness::default(),
+            ),
             definition.to_stats(),
+            definition.to_attacks(),
             Health::new(definition.max_health),
-            AttackTimer::default(),
+            Knockback::with_resistance(definition.knockback_resistance),
+            definition.hit_fx,
             NeedsAnimationSetup,
             SceneRoot(asset_server.load(&definition.model_path)),
             Transform::from_translation(spawn_pos)
@@ -67,6 +87,37 @@ pub fn spawn_monsters_from_grid(
             LevelGeometry, // Mark as level geometry so enemies get cleaned up with the level
         ));
 
+        // Without an explicit collider, fit one to the model once it loads
+        if definition.collider.is_none() {
+            enemy.insert(NeedsColliderFit);
+        }
+
+        if definition.wander {
+            enemy.insert(Wander::new(spawn_pos));
+        }
+
         info!("Spawned {} at grid ({}, {})", definition.name, spawn.grid_pos.0, spawn.grid_pos.1);
     }
 }
+
+/// Spawn stationary traps from the resolved monster grid of a floor layer.
+pub fn spawn_traps_from_grid(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    mat_registry: &MaterialRegistry,
+    level: &LevelDefinition,
+    monster_spawns: &[MonsterSpawn],
+    base_elevation: f32,
+    trap_registry: &TrapRegistry,
+) {
+    for spawn in monster_spawns {
+        let Some(definition) = trap_registry.get(&spawn.enemy_type) else {
+            continue;
+        };
+
+        let world_pos = level.grid_to_world(spawn.grid_pos.0, spawn.grid_pos.1);
+        let spawn_pos = Vec3::new(world_pos.x, base_elevation, world_pos.z);
+
+        spawn_trap(commands, meshes, mat_registry.get_wall("metal"), definition, spawn_pos);
+    }
+}
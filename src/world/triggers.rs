@@ -0,0 +1,121 @@
+//! Trigger volumes that fire named events when the player steps in.
+//!
+//! Triggers come from a level's trigger grid and palette. Entering one sends
+//! a `TriggerEvent` with its id, and puzzle pieces react by listening for
+//! the ids they care about (door tiles with a `trigger`, for one). Triggers
+//! marked `once` stop firing after the first time. Pressure plates are
+//! triggers with a visible plate on the floor.
+
+use bevy::prelude::*;
+
+use super::builder::LevelGeometry;
+use super::data::{LevelDefinition, ResolvedTrigger};
+use super::geometry::is_on_tile;
+use super::materials::MaterialRegistry;
+use crate::core::{GameState, PlayState};
+use crate::player::Player;
+
+/// Height above a trigger's floor within which the player is inside it.
+const TRIGGER_HEIGHT: f32 = 2.5;
+
+/// Width of a pressure plate as a fraction of the tile size.
+const PLATE_SIZE: f32 = 0.7;
+
+/// Thickness of a pressure plate.
+const PLATE_THICKNESS: f32 = 0.06;
+
+/// Material of pressure plates.
+const PLATE_MATERIAL: &str = "metal";
+
+/// A tile that sends a `TriggerEvent` when the player enters it.
+#[derive(Component)]
+pub struct TriggerVolume {
+    pub event_id: String,
+    /// Fire only the first time the player enters
+    pub once: bool,
+    /// Half the tile size, the horizontal extent of the volume
+    pub half_extent: f32,
+    /// Whether the player was inside last frame
+    occupied: bool,
+}
+
+/// Sent when the player enters a `TriggerVolume`.
+#[derive(Event, Clone, Debug)]
+pub struct TriggerEvent {
+    pub id: String,
+}
+
+/// Set up trigger volume systems.
+pub fn setup_trigger_systems(app: &mut App) {
+    app.add_event::<TriggerEvent>().add_systems(
+        Update,
+        detect_trigger_entry
+            .run_if(in_state(GameState::InGame))
+            .run_if(in_state(PlayState::Exploring)),
+    );
+}
+
+/// Spawn the volume (and plate, if any) for a trigger on the level's ground floor.
+pub fn spawn_trigger_volume(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    mat_registry: &MaterialRegistry,
+    level: &LevelDefinition,
+    trigger: &ResolvedTrigger,
+) {
+    let (x, z) = trigger.grid_pos;
+    let floor_pos = level.grid_to_world(x, z) + Vec3::Y * level.get_geometry(x, z).elevation;
+
+    let mut volume = commands.spawn((
+        TriggerVolume {
+            event_id: trigger.trigger.event_id.clone(),
+            once: trigger.trigger.once,
+            half_extent: level.tile_size / 2.0,
+            occupied: false,
+        },
+        Transform::from_translation(floor_pos),
+        Visibility::default(),
+        LevelGeometry,
+    ));
+
+    if trigger.trigger.pressure_plate {
+        let plate_size = level.tile_size * PLATE_SIZE;
+        let mesh = meshes.add(Cuboid::new(plate_size, PLATE_THICKNESS, plate_size));
+        volume.with_children(|parent| {
+            parent.spawn((
+                Mesh3d(mesh),
+                MeshMaterial3d(mat_registry.get_floor(PLATE_MATERIAL)),
+                Transform::from_xyz(0.0, PLATE_THICKNESS / 2.0, 0.0),
+            ));
+        });
+    }
+}
+
+/// Fire triggers the player has just stepped into.
+fn detect_trigger_entry(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    mut trigger_query: Query<(Entity, &mut TriggerVolume, &Transform), Without<Player>>,
+    mut trigger_events: EventWriter<TriggerEvent>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation;
+
+    for (entity, mut volume, transform) in trigger_query.iter_mut() {
+        let offset = player_pos - transform.translation;
+        let inside = is_on_tile(offset, volume.half_extent, TRIGGER_HEIGHT);
+
+        if inside && !volume.occupied {
+            debug!("Trigger '{}' fired", volume.event_id);
+            trigger_events.send(TriggerEvent {
+                id: volume.event_id.clone(),
+            });
+            if volume.once {
+                commands.entity(entity).remove::<TriggerVolume>();
+            }
+        }
+        volume.occupied = inside;
+    }
+}
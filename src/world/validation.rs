@@ -0,0 +1,291 @@
+//! Level data validation without running the game.
+//!
+//! Levels go through the same `from_raw` path the game uses, then get the
+//! stricter checks the loader glosses over: unknown palette characters
+//! (which the loader turns into void or empty tiles), external palette files
+//! that aren't loaded (the loader falls back to inline palettes), and
+//! monster spawns naming types with no enemy or trap definition. Validating
+//! a whole directory also checks exits against the other levels in it: the
+//! target level must exist and its arrival tile must be walkable. Used by
+//! the `validate_levels` binary.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::data::{
+    level_name, LevelDefinition, LevelDefinitionRaw, PaletteRegistry, ResolvedPalettes,
+};
+use super::error::DataLoadError;
+use crate::enemies::{EnemyRegistry, TrapRegistry};
+
+/// Problems found in one level file.
+#[derive(Debug)]
+pub struct LevelReport {
+    pub path: PathBuf,
+    pub errors: Vec<DataLoadError>,
+}
+
+impl LevelReport {
+    /// Whether the level had no problems.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Validate every level file in `dir`, in filename order.
+pub fn validate_level_dir(
+    dir: &Path,
+    palettes: &PaletteRegistry,
+    enemies: &EnemyRegistry,
+    traps: &TrapRegistry,
+) -> Result<Vec<LevelReport>, DataLoadError> {
+    let entries = fs::read_dir(dir)
+        .map_err(|_| DataLoadError::FileNotFound(dir.to_string_lossy().to_string()))?;
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .collect();
+    paths.sort();
+
+    // Levels that resolve, by registry key, for checking exits between them
+    let mut levels = HashMap::new();
+    let mut reports: Vec<(LevelReport, Option<String>)> = paths
+        .into_iter()
+        .map(|path| {
+            let name = level_name(&path);
+            let errors = match fs::read_to_string(&path) {
+                Ok(contents) => {
+                    let source = path.to_string_lossy();
+                    let errors = validate_level_str(&contents, &source, palettes, enemies, traps);
+                    if let (Some(name), Ok(level)) = (
+                        &name,
+                        LevelDefinition::from_ron_str(&contents, &source, palettes),
+                    ) {
+                        levels.insert(name.clone(), level);
+                    }
+                    errors
+                }
+                Err(e) => vec![DataLoadError::ReadError {
+                    path: path.to_string_lossy().to_string(),
+                    details: e.to_string(),
+                }],
+            };
+            (LevelReport { path, errors }, name)
+        })
+        .collect();
+
+    for (report, name) in &mut reports {
+        if let Some(level) = name.as_ref().and_then(|name| levels.get(name)) {
+            report.errors.extend(invalid_exits(level, &levels));
+        }
+    }
+
+    Ok(reports.into_iter().map(|(report, _)| report).collect())
+}
+
+/// Exits of `level` leading to a level not in `levels`, or arriving on a
+/// tile the player can't stand on.
+fn invalid_exits(
+    level: &LevelDefinition,
+    levels: &HashMap<String, LevelDefinition>,
+) -> Vec<DataLoadError> {
+    level
+        .exits
+        .iter()
+        .filter_map(|resolved| {
+            let (x, z) = resolved.grid_pos;
+            let exit = &resolved.exit;
+            let Some(target) = levels.get(&exit.target_level) else {
+                return Some(DataLoadError::UnknownExitTarget {
+                    target_level: exit.target_level.clone(),
+                    x,
+                    z,
+                });
+            };
+            // Exits arrive on the target's ground floor
+            let (spawn_x, spawn_z) = exit.target_spawn;
+            if target.get_geometry(spawn_x, spawn_z).kind.is_walkable() {
+                return None;
+            }
+            Some(DataLoadError::ExitSpawnNotWalkable {
+                target_level: exit.target_level.clone(),
+                spawn_x,
+                spawn_z,
+                x,
+                z,
+            })
+        })
+        .collect()
+}
+
+/// Validate a level from RON text. `source` names it in error messages.
+pub fn validate_level_str(
+    contents: &str,
+    source: &str,
+    palettes: &PaletteRegistry,
+    enemies: &EnemyRegistry,
+    traps: &TrapRegistry,
+) -> Vec<DataLoadError> {
+    let raw = match ron::from_str::<LevelDefinitionRaw>(contents) {
+        Ok(raw) => raw,
+        Err(e) => {
+            return vec![DataLoadError::ParseError {
+                path: source.to_string(),
+                details: e.to_string(),
+            }];
+        }
+    };
+
+    // Monster grids spawn both enemies and traps
+    let is_known_type =
+        |spawn_type: &str| enemies.get(spawn_type).is_some() || traps.get(spawn_type).is_some();
+
+    let mut errors = missing_palette_files(&raw, palettes);
+
+    // Same path as the game; stops at the first structural error
+    if let Err(e) = LevelDefinition::from_raw(raw.clone(), palettes) {
+        errors.push(e);
+        return errors;
+    }
+
+    let resolved = ResolvedPalettes::resolve(&raw, palettes);
+
+    let floors = std::iter::once((&raw.geometry, &raw.ambient, &raw.monsters)).chain(
+        raw.floors
+            .iter()
+            .map(|floor| (&floor.geometry, &floor.ambient, &floor.monsters)),
+    );
+    for (index, (geometry, ambient, monsters)) in floors.enumerate() {
+        let floor_name = |grid: &str| {
+            if index == 0 {
+                grid.to_string()
+            } else {
+                format!("{} (floor {})", grid, index)
+            }
+        };
+
+        for (x, z, character) in grid_chars(geometry) {
+            if !resolved.geometry.contains_key(&character) {
+                errors.push(DataLoadError::UnknownPaletteEntry {
+                    grid: floor_name("geometry"),
+                    character,
+                    x,
+                    z,
+                });
+            }
+        }
+
+        for (x, z, character) in grid_chars(ambient).filter(|(_, _, c)| !is_empty_cell(*c)) {
+            if !resolved.ambient.contains_key(&character) {
+                errors.push(DataLoadError::UnknownPaletteEntry {
+                    grid: floor_name("ambient"),
+                    character,
+                    x,
+                    z,
+                });
+            }
+        }
+
+        for (x, z, character) in grid_chars(monsters).filter(|(_, _, c)| !is_empty_cell(*c)) {
+            let Some(entry) = resolved.monster.get(&character) else {
+                errors.push(DataLoadError::UnknownPaletteEntry {
+                    grid: floor_name("monster"),
+                    character,
+                    x,
+                    z,
+                });
+                continue;
+            };
+            for enemy_type in entry.enemy_types() {
+                if !is_known_type(enemy_type) {
+                    errors.push(DataLoadError::UnknownEnemyType {
+                        enemy_type: enemy_type.to_string(),
+                        x: x as i32,
+                        z: z as i32,
+                    });
+                }
+            }
+        }
+    }
+
+    for (x, z, character) in grid_chars(&raw.exits).filter(|(_, _, c)| !is_empty_cell(*c)) {
+        if !raw.exit_palette.contains_key(&character) {
+            errors.push(DataLoadError::UnknownPaletteEntry {
+                grid: "exit".to_string(),
+                character,
+                x,
+                z,
+            });
+        }
+    }
+
+    for (x, z, character) in grid_chars(&raw.triggers).filter(|(_, _, c)| !is_empty_cell(*c)) {
+        if !raw.trigger_palette.contains_key(&character) {
+            errors.push(DataLoadError::UnknownPaletteEntry {
+                grid: "trigger".to_string(),
+                character,
+                x,
+                z,
+            });
+        }
+    }
+
+    for zone in &raw.spawn_zones {
+        if !is_known_type(&zone.enemy_type) {
+            errors.push(DataLoadError::UnknownEnemyType {
+                enemy_type: zone.enemy_type.clone(),
+                x: zone.pos.0,
+                z: zone.pos.1,
+            });
+        }
+    }
+
+    errors
+}
+
+/// External palette files the level names that aren't in the registry.
+fn missing_palette_files(
+    raw: &LevelDefinitionRaw,
+    palettes: &PaletteRegistry,
+) -> Vec<DataLoadError> {
+    // (kind, referenced file, whether it's loaded)
+    let references = [
+        raw.geometry_palette_file
+            .as_deref()
+            .map(|file| ("Geometry", file, palettes.get_geometry(file).is_some())),
+        raw.ambient_palette_file
+            .as_deref()
+            .map(|file| ("Ambient", file, palettes.get_ambient(file).is_some())),
+        raw.monster_palette_file
+            .as_deref()
+            .map(|file| ("Monster", file, palettes.get_monster(file).is_some())),
+        raw.ceiling_palette_file
+            .as_deref()
+            .map(|file| ("Ceiling", file, palettes.get_ceiling(file).is_some())),
+    ];
+
+    references
+        .into_iter()
+        .flatten()
+        .filter(|(_, _, loaded)| !loaded)
+        .map(|(kind, file, _)| DataLoadError::MissingPaletteFile {
+            kind: kind.to_string(),
+            file: file.to_string(),
+        })
+        .collect()
+}
+
+/// Characters the ambient, monster, exit and trigger grids use for "nothing here".
+fn is_empty_cell(character: char) -> bool {
+    character == '.' || character == ' '
+}
+
+/// Every character of a grid with its (x, z) position.
+fn grid_chars(rows: &[String]) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+    rows.iter().enumerate().flat_map(|(z, row)| {
+        row.chars().enumerate().map(move |(x, character)| (x, z, character))
+    })
+}